@@ -0,0 +1,49 @@
+use core::sync::atomic::Ordering;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use utils_atomics::AtomicBitBox;
+
+const LEN: usize = 1_000_000;
+
+#[inline]
+fn pattern(i: usize) -> bool {
+    i.is_multiple_of(3) || i.is_multiple_of(7)
+}
+
+fn benchmark_bitfield(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bitfield init");
+
+    group.bench_function("naive", |b| {
+        b.iter(|| {
+            let bit_box = AtomicBitBox::<usize>::new(LEN);
+            for i in 0..LEN {
+                bit_box.set_value(pattern(i), i, Ordering::Relaxed);
+            }
+            black_box(bit_box)
+        })
+    });
+    group.bench_function("batched", |b| {
+        b.iter(|| black_box(AtomicBitBox::<usize>::from_fn(LEN, pattern)))
+    });
+
+    group.finish();
+
+    let mut group = c.benchmark_group("bitfield set range");
+    let bit_box = AtomicBitBox::<usize>::new(LEN);
+
+    group.bench_function("naive", |b| {
+        b.iter(|| {
+            for i in 1..LEN - 1 {
+                bit_box.set(i, Ordering::Relaxed);
+            }
+        })
+    });
+    group.bench_function("batched", |b| {
+        b.iter(|| bit_box.set_range(1..LEN - 1, Ordering::Relaxed))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_bitfield);
+criterion_main!(benches);
@@ -0,0 +1,131 @@
+use std::sync::Mutex;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use utils_atomics::AtomicCell;
+
+const RUNS_PER_THREAD: usize = 50;
+const THREADS: usize = 8;
+
+// Only used for it's size
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, Default)]
+struct Large([u64; 8]);
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Zst;
+
+/// Common interface over the benchmarked cells
+trait OptionCell<T>: Send + Sync {
+    fn new(v: Option<T>) -> Self;
+    fn replace(&self, v: Option<T>) -> Option<T>;
+    fn take(&self) -> Option<T>;
+}
+
+impl<T: Send + Sync> OptionCell<T> for AtomicCell<T> {
+    #[inline]
+    fn new(v: Option<T>) -> Self {
+        AtomicCell::new(v)
+    }
+
+    #[inline]
+    fn replace(&self, v: Option<T>) -> Option<T> {
+        AtomicCell::replace(self, v)
+    }
+
+    #[inline]
+    fn take(&self) -> Option<T> {
+        AtomicCell::take(self)
+    }
+}
+
+impl<T: Send> OptionCell<T> for Mutex<Option<T>> {
+    #[inline]
+    fn new(v: Option<T>) -> Self {
+        Mutex::new(v)
+    }
+
+    #[inline]
+    fn replace(&self, v: Option<T>) -> Option<T> {
+        core::mem::replace(&mut *self.lock().unwrap(), v)
+    }
+
+    #[inline]
+    fn take(&self) -> Option<T> {
+        self.lock().unwrap().take()
+    }
+}
+
+impl<T: Send> OptionCell<T> for crossbeam::atomic::AtomicCell<Option<T>> {
+    #[inline]
+    fn new(v: Option<T>) -> Self {
+        crossbeam::atomic::AtomicCell::new(v)
+    }
+
+    #[inline]
+    fn replace(&self, v: Option<T>) -> Option<T> {
+        self.swap(v)
+    }
+
+    #[inline]
+    fn take(&self) -> Option<T> {
+        crossbeam::atomic::AtomicCell::take(self)
+    }
+}
+
+fn benchmark_cell(c: &mut Criterion) {
+    bench_type::<i32>(c, "i32", 1);
+    bench_type::<Large>(c, "64 bytes", Large([1; 8]));
+    bench_type::<Zst>(c, "zst", Zst);
+}
+
+fn bench_type<T: 'static + Copy + Send + Sync>(c: &mut Criterion, name: &str, v: T) {
+    let mut group = c.benchmark_group(format!("cell {name}"));
+
+    group.bench_function("utils_atomics", |b| {
+        let cell = <AtomicCell<T> as OptionCell<T>>::new(None);
+        b.iter(|| replace_and_take(&cell, v, 1))
+    });
+    group.bench_function("mutex option", |b| {
+        let cell = <Mutex<Option<T>> as OptionCell<T>>::new(None);
+        b.iter(|| replace_and_take(&cell, v, 1))
+    });
+    group.bench_function("crossbeam", |b| {
+        let cell = <crossbeam::atomic::AtomicCell<Option<T>> as OptionCell<T>>::new(None);
+        b.iter(|| replace_and_take(&cell, v, 1))
+    });
+
+    group.bench_with_input(BenchmarkId::new("utils_atomics", THREADS), &THREADS, |b, &threads| {
+        let cell = <AtomicCell<T> as OptionCell<T>>::new(None);
+        b.iter(|| bench_through_threads(&cell, v, threads))
+    });
+    group.bench_with_input(BenchmarkId::new("mutex option", THREADS), &THREADS, |b, &threads| {
+        let cell = <Mutex<Option<T>> as OptionCell<T>>::new(None);
+        b.iter(|| bench_through_threads(&cell, v, threads))
+    });
+    group.bench_with_input(BenchmarkId::new("crossbeam", THREADS), &THREADS, |b, &threads| {
+        let cell = <crossbeam::atomic::AtomicCell<Option<T>> as OptionCell<T>>::new(None);
+        b.iter(|| bench_through_threads(&cell, v, threads))
+    });
+
+    group.finish();
+}
+
+#[inline]
+fn replace_and_take<T: Copy, C: OptionCell<T>>(cell: &C, v: T, runs: usize) {
+    for _ in 0..runs {
+        criterion::black_box(cell.replace(Some(v)));
+        criterion::black_box(cell.take());
+    }
+}
+
+#[inline]
+fn bench_through_threads<T: Copy + Send + Sync, C: OptionCell<T>>(cell: &C, v: T, threads: usize) {
+    std::thread::scope(|s| {
+        for _ in 0..threads {
+            s.spawn(|| replace_and_take(cell, v, RUNS_PER_THREAD));
+        }
+    })
+}
+
+criterion_group!(benches, benchmark_cell);
+criterion_main!(benches);
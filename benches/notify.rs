@@ -0,0 +1,28 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use utils_atomics::{lock, wake_all, FillQueue, Lock};
+
+const WAITERS: usize = 10_000;
+
+// Every lock belongs to the benchmarking thread, which isn't parked, so the wake-ups don't block.
+// This measures how the chop-and-wake loop is structured, rather than the cost of the `unpark` syscalls themselves.
+fn setup() -> FillQueue<Lock> {
+    let mut queue = FillQueue::new();
+    (0..WAITERS).for_each(|_| queue.push_mut(lock().0));
+    queue
+}
+
+fn benchmark_notify(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("notify {WAITERS} waiters"));
+
+    group.bench_function("current", |b| {
+        b.iter_batched(setup, |queue| queue.chop().for_each(Lock::wake), BatchSize::LargeInput)
+    });
+    group.bench_function("batched", |b| {
+        b.iter_batched(setup, |queue| wake_all(queue.chop()), BatchSize::LargeInput)
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_notify);
+criterion_main!(benches);
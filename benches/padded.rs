@@ -0,0 +1,38 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::sync::atomic::{AtomicU64, Ordering};
+use utils_atomics::Padded;
+
+const THREADS: usize = 4;
+const ITERS: u64 = 100_000;
+
+// Every thread increments it's own counter, so any slowdown of the unpadded version comes from false sharing.
+fn increment<A: std::ops::Deref<Target = AtomicU64> + Sync>(counters: &[A]) {
+    std::thread::scope(|s| {
+        for counter in counters {
+            s.spawn(move || {
+                for _ in 0..ITERS {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+}
+
+fn benchmark_padded(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("{THREADS} threads incrementing adjacent counters"));
+
+    group.bench_function("unpadded", |b| {
+        let values: [AtomicU64; THREADS] = Default::default();
+        let counters = values.each_ref();
+        b.iter(|| increment(&counters))
+    });
+    group.bench_function("padded", |b| {
+        let counters: [Padded<AtomicU64>; THREADS] = Default::default();
+        b.iter(|| increment(&counters))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_padded);
+criterion_main!(benches);
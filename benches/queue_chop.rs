@@ -1,4 +1,3 @@
-#![feature(mem_copy_fn)]
 use std::{sync::Mutex, hint::black_box};
 
 use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
@@ -8,7 +7,7 @@ use utils_atomics::FillQueue;
 fn benchmark_queue_chop(c: &mut Criterion) {
     for i in [1, 10, 100, 1_000, 10_000] {
         let queue = SegQueue::new();
-        (0usize..i).into_iter().for_each(|i| queue.push(i));
+        (0usize..i).for_each(|i| queue.push(i));
         c.bench_with_input(BenchmarkId::new("crossbeam", i), &(queue, i), |b, (queue, _)| {
             b.iter(|| {
                 while let Some(x) = queue.pop() {
@@ -17,7 +16,7 @@ fn benchmark_queue_chop(c: &mut Criterion) {
             })
         });
     
-        let queue = Mutex::new((0..i).into_iter().collect::<Vec<_>>());
+        let queue = Mutex::new((0..i).collect::<Vec<_>>());
         c.bench_with_input(BenchmarkId::new("mutex vec", i), &(queue, i), |b, (queue, _)| {
             b.iter(|| {
                 let mut queue = queue.lock().unwrap();
@@ -28,7 +27,7 @@ fn benchmark_queue_chop(c: &mut Criterion) {
         });
         
         let mut queue = FillQueue::new();
-        (0..i).into_iter().for_each(|i| queue.push_mut(i));
+        (0..i).for_each(|i| queue.push_mut(i));
         c.bench_with_input(BenchmarkId::new("utils_atomics", i), &(queue, i), |b, (queue, _)| {
             b.iter(|| {
                 for x in queue.chop() {
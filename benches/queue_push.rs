@@ -1,4 +1,3 @@
-#![feature(mem_copy_fn)]
 use std::sync::Mutex;
 
 use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
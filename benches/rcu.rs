@@ -0,0 +1,82 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use utils_atomics::RcuCell;
+
+const READS_PER_THREAD: usize = 1_000;
+
+/// Common interface over the benchmarked read-mostly cells
+trait ReadCell: Send + Sync {
+    fn new(v: usize) -> Self;
+    fn read(&self) -> usize;
+}
+
+impl ReadCell for RcuCell<usize> {
+    #[inline]
+    fn new(v: usize) -> Self {
+        RcuCell::new(v)
+    }
+
+    #[inline]
+    fn read(&self) -> usize {
+        *RcuCell::read(self)
+    }
+}
+
+impl ReadCell for RwLock<Arc<usize>> {
+    #[inline]
+    fn new(v: usize) -> Self {
+        RwLock::new(Arc::new(v))
+    }
+
+    #[inline]
+    fn read(&self) -> usize {
+        **RwLock::read(self).unwrap()
+    }
+}
+
+impl ReadCell for Mutex<Arc<usize>> {
+    #[inline]
+    fn new(v: usize) -> Self {
+        Mutex::new(Arc::new(v))
+    }
+
+    #[inline]
+    fn read(&self) -> usize {
+        **self.lock().unwrap()
+    }
+}
+
+fn benchmark_rcu(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rcu read");
+    for threads in [1, 2, 4, 8] {
+        bench_reads::<RcuCell<usize>>(&mut group, "utils_atomics", threads);
+        bench_reads::<RwLock<Arc<usize>>>(&mut group, "rwlock arc", threads);
+        bench_reads::<Mutex<Arc<usize>>>(&mut group, "mutex arc", threads);
+    }
+    group.finish();
+}
+
+fn bench_reads<C: ReadCell>(
+    group: &mut criterion::BenchmarkGroup<criterion::measurement::WallTime>,
+    name: &str,
+    threads: usize,
+) {
+    let cell = C::new(1);
+    group.bench_with_input(BenchmarkId::new(name, threads), &threads, |b, &threads| {
+        b.iter(|| {
+            std::thread::scope(|s| {
+                for _ in 0..threads {
+                    s.spawn(|| {
+                        for _ in 0..READS_PER_THREAD {
+                            black_box(cell.read());
+                        }
+                    });
+                }
+            })
+        })
+    });
+}
+
+criterion_group!(benches, benchmark_rcu);
+criterion_main!(benches);
@@ -0,0 +1,320 @@
+use crate::FillQueue;
+use alloc::sync::Arc;
+use core::{
+    future::{poll_fn, Future},
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Poll, Waker},
+};
+
+/// An asynchronous counting semaphore.
+///
+/// Tasks that can't acquire a permit register their waker and are woken every time a permit is released,
+/// at which point they race to acquire it again. No fairness is guaranteed between waiting tasks.
+///
+/// # Example
+/// ```rust
+/// use utils_atomics::AsyncSemaphore;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let semaphore = AsyncSemaphore::new(1);
+///
+/// let permit = semaphore.acquire().await;
+/// assert!(semaphore.try_acquire().is_none());
+///
+/// drop(permit);
+/// assert!(semaphore.try_acquire().is_some());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AsyncSemaphore {
+    permits: AtomicUsize,
+    wakers: FillQueue<Waker>,
+    // Incremented every time the wakers are chopped, so waiting futures know if their waker is still registered
+    generation: AtomicUsize,
+}
+
+impl AsyncSemaphore {
+    /// Creates a new semaphore with the specified amount of permits
+    #[inline]
+    pub const fn new(permits: usize) -> Self {
+        return Self {
+            permits: AtomicUsize::new(permits),
+            wakers: FillQueue::new(),
+            generation: AtomicUsize::new(0),
+        };
+    }
+
+    /// Returns the amount of permits that are currently available
+    #[inline]
+    pub fn available_permits(&self) -> usize {
+        self.permits.load(Ordering::Relaxed)
+    }
+
+    /// Adds `n` permits to the semaphore, waking the tasks waiting for them.
+    ///
+    /// # Panics
+    /// This method panics if the amount of available permits overflows.
+    #[inline]
+    pub fn add_permits(&self, n: usize) {
+        let added = self
+            .permits
+            .fetch_update(Ordering::Release, Ordering::Relaxed, |x| x.checked_add(n));
+        assert!(added.is_ok(), "too many permits");
+
+        let wakers = self.wakers.chop();
+        // Bumped after chopping, so a waker registered before the chop is never mistaken for a pending one
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        wakers.for_each(Waker::wake);
+    }
+
+    /// Attempts to acquire a permit, returning `None` if none are available
+    #[inline]
+    pub fn try_acquire(&self) -> Option<AsyncSemaphorePermit<'_>> {
+        return self
+            .try_acquire_inner()
+            .then(|| AsyncSemaphorePermit { parent: self });
+    }
+
+    /// Acquires a permit, waiting until one is available
+    #[inline]
+    pub fn acquire(&self) -> impl Future<Output = AsyncSemaphorePermit<'_>> {
+        let mut registered = None;
+        return poll_fn(move |cx| match self.poll_acquire(cx.waker(), &mut registered) {
+            true => Poll::Ready(AsyncSemaphorePermit { parent: self }),
+            false => Poll::Pending,
+        });
+    }
+
+    /// Attempts to acquire a permit that owns a reference to the semaphore, returning `None` if none are available
+    #[inline]
+    pub fn try_acquire_owned(self: Arc<Self>) -> Option<OwnedPermit> {
+        return match self.try_acquire_inner() {
+            true => Some(OwnedPermit { parent: self }),
+            false => None,
+        };
+    }
+
+    /// Acquires a permit that owns a reference to the semaphore, waiting until one is available.
+    ///
+    /// Unlike [`acquire`](AsyncSemaphore::acquire), neither the future nor the permit borrow the semaphore,
+    /// so they can be moved into `'static` tasks.
+    ///
+    /// # Panics
+    /// The returned future panics if it's polled after it has completed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::AsyncSemaphore;
+    /// use std::sync::Arc;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let semaphore = Arc::new(AsyncSemaphore::new(1));
+    ///
+    /// let permit = semaphore.clone().acquire_owned().await;
+    /// assert_eq!(semaphore.available_permits(), 0);
+    ///
+    /// drop(permit);
+    /// assert_eq!(semaphore.available_permits(), 1);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn acquire_owned(self: Arc<Self>) -> impl Future<Output = OwnedPermit> {
+        let mut this = Some(self);
+        let mut registered = None;
+        return poll_fn(move |cx| {
+            let parent = this.take().expect("future polled after completion");
+            if parent.poll_acquire(cx.waker(), &mut registered) {
+                return Poll::Ready(OwnedPermit { parent });
+            }
+
+            this = Some(parent);
+            return Poll::Pending;
+        });
+    }
+
+    fn try_acquire_inner(&self) -> bool {
+        return self
+            .permits
+            .fetch_update(Ordering::Acquire, Ordering::Relaxed, |x| x.checked_sub(1))
+            .is_ok();
+    }
+
+    /// Attempts to acquire a permit, registering `waker` otherwise.
+    ///
+    /// `registered` holds the last waker registered by the polling future, alongside the generation it was registered on.
+    /// If it's still registered (and it wakes the same task), it isn't registered again.
+    fn poll_acquire(&self, waker: &Waker, registered: &mut Option<(Waker, usize)>) -> bool {
+        if self.try_acquire_inner() {
+            return true;
+        }
+
+        let generation = self.generation.load(Ordering::Acquire);
+        if let Some((prev, prev_generation)) = registered {
+            if *prev_generation == generation && prev.will_wake(waker) {
+                return false;
+            }
+        }
+
+        self.wakers.push(waker.clone());
+        *registered = Some((waker.clone(), generation));
+        // A permit may have been released before our waker was registered
+        return self.try_acquire_inner();
+    }
+
+    #[inline]
+    fn release(&self) {
+        self.add_permits(1);
+    }
+}
+
+/// A permit of an [`AsyncSemaphore`], which is released when dropped
+#[derive(Debug)]
+#[must_use = "the permit is released as soon as it's dropped"]
+pub struct AsyncSemaphorePermit<'a> {
+    parent: &'a AsyncSemaphore,
+}
+
+impl Drop for AsyncSemaphorePermit<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.parent.release();
+    }
+}
+
+/// A permit of an [`AsyncSemaphore`] that owns a reference to it, which is released when dropped
+#[derive(Debug)]
+#[must_use = "the permit is released as soon as it's dropped"]
+pub struct OwnedPermit {
+    parent: Arc<AsyncSemaphore>,
+}
+
+impl OwnedPermit {
+    /// Returns the semaphore the permit belongs to
+    #[inline]
+    pub fn semaphore(&self) -> &Arc<AsyncSemaphore> {
+        &self.parent
+    }
+}
+
+impl Drop for OwnedPermit {
+    #[inline]
+    fn drop(&mut self) {
+        self.parent.release();
+    }
+}
+
+// Thanks ChatGPT!
+#[cfg(all(feature = "std", test))]
+mod tests {
+    use super::AsyncSemaphore;
+    use std::{sync::Arc, time::Duration};
+
+    #[tokio::test]
+    async fn test_acquire_and_release() {
+        let semaphore = AsyncSemaphore::new(2);
+        let first = semaphore.acquire().await;
+        let second = semaphore.try_acquire().unwrap();
+        assert!(semaphore.try_acquire().is_none());
+        assert_eq!(semaphore.available_permits(), 0);
+
+        drop(first);
+        assert_eq!(semaphore.available_permits(), 1);
+        drop(second);
+        assert_eq!(semaphore.available_permits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_owned_permit_in_task() {
+        let semaphore = Arc::new(AsyncSemaphore::new(1));
+        let permit = semaphore.clone().acquire_owned().await;
+        assert!(semaphore.clone().try_acquire_owned().is_none());
+
+        let handle = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            // Dropping the permit inside the task releases it
+            drop(permit);
+        });
+
+        let waiter = tokio::spawn(semaphore.clone().acquire_owned());
+        let second = tokio::time::timeout(Duration::from_secs(10), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+
+        handle.await.unwrap();
+        assert!(Arc::ptr_eq(second.semaphore(), &semaphore));
+        assert_eq!(semaphore.available_permits(), 0);
+
+        drop(second);
+        assert_eq!(semaphore.available_permits(), 1);
+    }
+
+    #[test]
+    fn test_add_permits_overflow() {
+        let semaphore = AsyncSemaphore::new(usize::MAX);
+        let res = std::panic::catch_unwind(|| semaphore.add_permits(1));
+        assert!(res.is_err());
+        // The counter is checked before it's updated, so it never wraps around
+        assert_eq!(semaphore.available_permits(), usize::MAX);
+    }
+
+    #[test]
+    fn test_repeated_polls_register_once() {
+        use core::{future::Future, pin::pin, task::Context};
+        use std::task::{Wake, Waker};
+
+        struct NoopWake;
+        impl Wake for NoopWake {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        let semaphore = AsyncSemaphore::new(0);
+        let waker = Waker::from(Arc::new(NoopWake));
+        let mut cx = Context::from_waker(&waker);
+
+        let mut fut = pin!(semaphore.acquire());
+        for _ in 0..10 {
+            assert!(fut.as_mut().poll(&mut cx).is_pending());
+        }
+        assert_eq!(semaphore.wakers.len(), 1);
+
+        // Releasing a permit chops the waker, so the next poll registers it again
+        semaphore.add_permits(1);
+        let permit = semaphore.try_acquire().unwrap();
+        assert!(fut.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(semaphore.wakers.len(), 1);
+
+        drop(permit);
+        assert!(fut.as_mut().poll(&mut cx).is_ready());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_permits() {
+        const TASKS: usize = 16;
+        const PERMITS: usize = 3;
+
+        let semaphore = Arc::new(AsyncSemaphore::new(PERMITS));
+        let active = Arc::new(core::sync::atomic::AtomicUsize::new(0));
+
+        let handles = (0..TASKS)
+            .map(|_| {
+                let semaphore = semaphore.clone();
+                let active = active.clone();
+                tokio::spawn(async move {
+                    let _permit = semaphore.acquire_owned().await;
+                    let prev = active.fetch_add(1, core::sync::atomic::Ordering::SeqCst);
+                    assert!(prev < PERMITS);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    active.fetch_sub(1, core::sync::atomic::Ordering::SeqCst);
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        assert_eq!(semaphore.available_permits(), PERMITS);
+    }
+}
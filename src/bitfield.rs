@@ -1,16 +1,36 @@
-use crate::traits::{Atomic, AtomicBitAnd, AtomicBitOr, HasAtomicInt};
+use crate::traits::{clear_bit, set_bit, Atomic, AtomicBitAnd, AtomicBitOr, AtomicBitXor, HasAtomicInt};
 use crate::AllocError;
 use crate::{div_ceil, InnerFlag};
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use bytemuck::Zeroable;
 use core::{
-    ops::{BitAnd, Not, Shl, Shr},
+    ops::{BitAnd, BitOr, Bound, Not, Range, RangeBounds, Shl, Shr},
     sync::atomic::Ordering,
 };
-use num_traits::Num;
+use num_traits::{Num, PrimInt};
 #[cfg(feature = "alloc_api")]
 use {alloc::alloc::Global, core::alloc::*};
 
+macro_rules! impl_all {
+    ($(#[$meta:meta])* impl $(@$tr:path =>)? $target:ident {
+        $($t:tt)*
+    }) => {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "alloc_api")] {
+                $(#[$meta])*
+                impl<T: HasAtomicInt + BitFieldAble, A: Allocator> $($tr for)? $target <T, A> {
+                    $($t)*
+                }
+            } else {
+                $(#[$meta])*
+                impl<T: HasAtomicInt + BitFieldAble> $($tr for)? $target <T> {
+                    $($t)*
+                }
+            }
+        }
+    };
+}
+
 /// An atomic bitfield with a static size, stored in a boxed slice.
 ///
 /// This struct provides methods for working with atomic bitfields, allowing
@@ -79,6 +99,84 @@ where
 
         Ok(Self { bits, len })
     }
+
+    /// Allocates a new bitfield with the values of `v`, packing them a whole word at a time.
+    ///
+    /// # Panics
+    /// This method panics if the memory allocation fails
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::AtomicBitBox;
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// let bit_box = AtomicBitBox::<u8>::from_bools(&[true, false, true]);
+    /// assert_eq!(bit_box.get(2, Ordering::Relaxed), Some(true));
+    /// assert_eq!(bit_box.to_bools(Ordering::Relaxed), [true, false, true]);
+    /// ```
+    pub fn from_bools(v: &[bool]) -> Self {
+        let bits = v
+            .chunks(Self::BIT_SIZE)
+            .map(|chunk| <T::AtomicInt as Atomic>::new(Self::pack_word(chunk.iter().copied())))
+            .collect::<Vec<_>>();
+
+        return Self {
+            bits: bits.into_boxed_slice(),
+            len: v.len(),
+        };
+    }
+
+    /// Allocates a new bitfield of `len` bits, where the bit at index `i` is initialized to `f(i)`.
+    ///
+    /// The bits are packed a whole word at a time, without any atomic operations nor per-bit branches,
+    /// which allows the compiler to vectorize the initialization of large bitfields.
+    ///
+    /// # Panics
+    /// This method panics if the memory allocation fails
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::AtomicBitBox;
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// let bit_box = AtomicBitBox::<u8>::from_fn(10, |i| i % 3 == 0);
+    /// assert_eq!(bit_box.get(9, Ordering::Relaxed), Some(true));
+    /// assert_eq!(bit_box.get(8, Ordering::Relaxed), Some(false));
+    /// ```
+    pub fn from_fn<F: FnMut(usize) -> bool>(len: usize, mut f: F) -> Self {
+        let bits = (0..div_ceil(len, Self::BIT_SIZE))
+            .map(|word| {
+                let start = word * Self::BIT_SIZE;
+                let end = (start + Self::BIT_SIZE).min(len);
+                <T::AtomicInt as Atomic>::new(Self::pack_word((start..end).map(&mut f)))
+            })
+            .collect::<Vec<_>>();
+
+        return Self {
+            bits: bits.into_boxed_slice(),
+            len,
+        };
+    }
+
+    /// Packs up to a word's worth of bits into a word, starting from the lowest one.
+    #[inline]
+    fn pack_word<I: Iterator<Item = bool>>(bits: I) -> T {
+        return bits.enumerate().fold(T::zero(), |word, (i, bit)| {
+            // Selecting the bit instead of branching on it keeps the loop vectorizable
+            let bit = if bit { T::one() } else { T::zero() };
+            word | (bit << i)
+        });
+    }
+}
+
+impl<T: HasAtomicInt> From<&[bool]> for AtomicBitBox<T>
+where
+    T: BitFieldAble,
+{
+    #[inline]
+    fn from(v: &[bool]) -> Self {
+        Self::from_bools(v)
+    }
 }
 
 cfg_if::cfg_if! {
@@ -110,6 +208,37 @@ cfg_if::cfg_if! {
                 Ok(Self { bits, len })
             }
 
+            /// Resizes the bitfield to `len` bits, keeping the values of the bits that are still in bounds.
+            /// New bits are initialized to `false`.
+            ///
+            /// This is a non-atomic operation, made safe by the mutable reference.
+            ///
+            /// # Panics
+            /// This method panics if the memory allocation fails
+            #[inline]
+            pub fn resize (&mut self, len: usize) where A: Clone {
+                self.try_resize(len).unwrap()
+            }
+
+            /// Resizes the bitfield to `len` bits, keeping the values of the bits that are still in bounds.
+            /// New bits are initialized to `false`.
+            ///
+            /// This is a non-atomic operation, made safe by the mutable reference.
+            ///
+            /// # Errors
+            /// This method returns an error if the memory allocation fails, in which case the bitfield is left unchanged
+            pub fn try_resize (&mut self, len: usize) -> Result<(), AllocError> where A: Clone {
+                let count = len.div_ceil(Self::BIT_SIZE);
+                let alloc = Box::allocator(&self.bits).clone();
+                let mut bits = unsafe { Box::<[T::AtomicInt], _>::try_new_zeroed_slice_in(count, alloc)?.assume_init() };
+
+                self.mask_last_word();
+                Self::copy_words(&mut self.bits, &mut bits, len);
+                self.bits = bits;
+                self.len = len;
+                Ok(())
+            }
+
             /// Returns the value of the bit at the specified index, or `None` if the index is out of bounds.
             ///
             /// `order` defines the memory ordering for this operation.
@@ -150,7 +279,7 @@ cfg_if::cfg_if! {
 
                 let byte = unsafe { <[T::AtomicInt]>::get_unchecked(&self.bits, byte) };
                 let mask = T::one() << idx;
-                let prev = byte.fetch_or(mask, order);
+                let prev = set_bit(byte, mask, order);
                 return Some((prev & mask) != T::zero())
             }
 
@@ -168,22 +297,52 @@ cfg_if::cfg_if! {
 
                 let byte = unsafe { <[T::AtomicInt]>::get_unchecked(&self.bits, byte) };
                 let mask = T::one() << idx;
-                let prev = byte.fetch_and(!mask, order);
+                let prev = clear_bit(byte, mask, order);
                 return Some((prev & mask) != T::zero())
             }
 
             #[inline]
             fn check_bounds (&self, major: usize, minor: usize) -> bool {
-                if major < self.bits.len() - 1 {
-                    return minor < Self::BIT_SIZE
-                }
-                return minor < self.len % Self::BIT_SIZE
+                return major * Self::BIT_SIZE + minor < self.len
             }
         }
     } else {
         impl<T: HasAtomicInt> AtomicBitBox<T> where T: BitFieldAble {
             const BIT_SIZE: usize = 8 * core::mem::size_of::<T>();
 
+            /// Resizes the bitfield to `len` bits, keeping the values of the bits that are still in bounds.
+            /// New bits are initialized to `false`.
+            ///
+            /// This is a non-atomic operation, made safe by the mutable reference.
+            ///
+            /// # Panics
+            /// This method panics if the memory allocation fails
+            #[inline]
+            pub fn resize (&mut self, len: usize) {
+                self.try_resize(len).unwrap()
+            }
+
+            /// Resizes the bitfield to `len` bits, keeping the values of the bits that are still in bounds.
+            /// New bits are initialized to `false`.
+            ///
+            /// This is a non-atomic operation, made safe by the mutable reference.
+            ///
+            /// # Errors
+            /// This method returns an error if the memory allocation fails, in which case the bitfield is left unchanged
+            pub fn try_resize (&mut self, len: usize) -> Result<(), AllocError> {
+                let count = div_ceil(len, Self::BIT_SIZE);
+                let mut bits = Vec::new();
+                bits.try_reserve_exact(count).map_err(|_| AllocError)?;
+                bits.resize_with(count, || <T::AtomicInt as Atomic>::new(T::zero()));
+                let mut bits = bits.into_boxed_slice();
+
+                self.mask_last_word();
+                Self::copy_words(&mut self.bits, &mut bits, len);
+                self.bits = bits;
+                self.len = len;
+                Ok(())
+            }
+
             /// Returns the value of the bit at the specified index, or `None` if the index is out of bounds.
             ///
             /// `order` defines the memory ordering for this operation.
@@ -224,7 +383,7 @@ cfg_if::cfg_if! {
 
                 let byte = unsafe { <[T::AtomicInt]>::get_unchecked(&self.bits, byte) };
                 let mask = T::one() << idx;
-                let prev = byte.fetch_or(mask, order);
+                let prev = set_bit(byte, mask, order);
                 return Some((prev & mask) != T::zero())
             }
 
@@ -242,27 +401,786 @@ cfg_if::cfg_if! {
 
                 let byte = unsafe { <[T::AtomicInt]>::get_unchecked(&self.bits, byte) };
                 let mask = T::one() << idx;
-                let prev = byte.fetch_and(!mask, order);
+                let prev = clear_bit(byte, mask, order);
                 return Some((prev & mask) != T::zero())
             }
 
             #[inline]
             fn check_bounds (&self, major: usize, minor: usize) -> bool {
-                if major < self.bits.len() - 1 {
-                    return minor < Self::BIT_SIZE
+                return major * Self::BIT_SIZE + minor < self.len
+            }
+        }
+    }
+}
+
+impl_all! {
+    /// Formats the bitfield as a string of `0`s and `1`s, starting with the bit at index `0` (LSB order).
+    /// The whole bitfield is rendered, from a [`Relaxed`](Ordering::Relaxed) snapshot of it's bits.
+    impl @core::fmt::Display => AtomicBitBox {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            for i in 0..self.len {
+                let bit = self.get(i, Ordering::Relaxed).unwrap_or_default();
+                core::fmt::Write::write_char(f, if bit { '1' } else { '0' })?;
+            }
+            Ok(())
+        }
+    }
+}
+
+impl_all! {
+    /// Compares the bitfield against a slice of `bool`s, starting with the bit at index `0`.
+    /// The bitfield is equal to the slice if both have the same length and every bit matches it's value in the slice.
+    ///
+    /// Each backing word is loaded once with [`Relaxed`](Ordering::Relaxed) ordering, and the bits of the last word that are
+    /// outside the bitfield's bounds are ignored.
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::AtomicBitBox;
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// let bit_box = AtomicBitBox::<u8>::new(3);
+    /// bit_box.set(1, Ordering::Relaxed);
+    /// assert_eq!(bit_box, [false, true, false][..]);
+    /// ```
+    impl @PartialEq<[bool]> => AtomicBitBox {
+        fn eq(&self, other: &[bool]) -> bool {
+            if self.len != other.len() {
+                return false;
+            }
+
+            return self.bits.iter().zip(other.chunks(Self::BIT_SIZE)).all(|(word, chunk)| {
+                let mask = if chunk.len() == Self::BIT_SIZE { !T::zero() } else { !(!T::zero() << chunk.len()) };
+                let expected = chunk
+                    .iter()
+                    .enumerate()
+                    .fold(T::zero(), |acc, (i, &v)| if v { acc | (T::one() << i) } else { acc });
+                (word.load(Ordering::Relaxed) & mask) == expected
+            });
+        }
+    }
+}
+
+impl_all! {
+    /// See the implementation of `PartialEq<[bool]>`
+    impl @PartialEq<&[bool]> => AtomicBitBox {
+        #[inline]
+        fn eq(&self, other: &&[bool]) -> bool {
+            <Self as PartialEq<[bool]>>::eq(self, other)
+        }
+    }
+}
+
+impl_all! {
+    impl @core::fmt::Debug => AtomicBitBox {
+        #[inline]
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_struct("AtomicBitBox")
+                .field("len", &self.len)
+                .field("bits", &format_args!("{self}"))
+                .finish()
+        }
+    }
+}
+
+impl_all! {
+    impl AtomicBitBox {
+        /// Returns the values of the bits at the specified indices, in the same order as `indices`.
+        /// Out of bounds indices yield `None` in their slot.
+        ///
+        /// Indices are grouped by their backing word, so each word is loaded at most once, even when multiple indices share it.
+        /// Every load is performed with the specified `order`, but the bitfield as a whole isn't read atomically.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(10);
+        /// bit_box.set(2, Ordering::Relaxed);
+        /// bit_box.set(9, Ordering::Relaxed);
+        ///
+        /// let bits = bit_box.get_many(&[9, 3, 10, 2], Ordering::Relaxed);
+        /// assert_eq!(bits, [Some(true), Some(false), None, Some(true)]);
+        /// ```
+        pub fn get_many(&self, indices: &[usize], order: Ordering) -> Vec<Option<bool>> {
+            let mut result = alloc::vec![None; indices.len()];
+            let mut sorted = (0..indices.len())
+                .filter(|&i| indices[i] < self.len)
+                .collect::<Vec<_>>();
+            sorted.sort_unstable_by_key(|&i| indices[i] / Self::BIT_SIZE);
+
+            let mut current = None;
+            for i in sorted {
+                let word = indices[i] / Self::BIT_SIZE;
+                let v = match current {
+                    Some((w, v)) if w == word => v,
+                    _ => {
+                        let v = self.bits[word].load(order);
+                        current = Some((word, v));
+                        v
+                    }
+                };
+
+                let mask = T::one() << (indices[i] % Self::BIT_SIZE);
+                result[i] = Some((v & mask) != T::zero());
+            }
+
+            return result;
+        }
+
+        /// Sets the bits at the specified indices. Out of bounds indices are ignored.
+        ///
+        /// Indices are grouped by their backing word, so each affected word is updated with a single `fetch_or`, even when multiple indices share it.
+        /// Every update is performed with the specified `order`, but the bitfield as a whole isn't updated atomically.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(10);
+        /// bit_box.set_indices(&[9, 2, 10, 3], Ordering::Relaxed);
+        ///
+        /// let bits = bit_box.get_many(&[2, 3, 4, 9], Ordering::Relaxed);
+        /// assert_eq!(bits, [Some(true), Some(true), Some(false), Some(true)]);
+        /// ```
+        pub fn set_indices(&self, indices: &[usize], order: Ordering) {
+            let mut sorted = indices.iter().copied().filter(|&i| i < self.len).collect::<Vec<_>>();
+            sorted.sort_unstable();
+
+            let mut current: Option<(usize, T)> = None;
+            for i in sorted {
+                let (word, bit) = (i / Self::BIT_SIZE, T::one() << (i % Self::BIT_SIZE));
+                current = match current {
+                    Some((w, mask)) if w == word => Some((w, mask | bit)),
+                    prev => {
+                        if let Some((w, mask)) = prev {
+                            self.bits[w].fetch_or(mask, order);
+                        }
+                        Some((word, bit))
+                    }
+                };
+            }
+
+            if let Some((w, mask)) = current {
+                self.bits[w].fetch_or(mask, order);
+            }
+        }
+
+        /// Sets the bits at the specified indices to their paired values. Out of bounds indices are ignored, and if an index
+        /// appears multiple times, it's last update wins.
+        ///
+        /// Updates are grouped by their backing word, so each affected word is updated atomically with a single operation
+        /// (a `fetch_or` or `fetch_and` if it only has bits being set or cleared, and a `fetch_update` otherwise).
+        /// Every update is performed with the specified `order`, but the bitfield as a whole isn't updated atomically.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(10);
+        /// bit_box.set_range(0..4, Ordering::Relaxed);
+        /// bit_box.set_values(&[(1, false), (9, true), (3, false), (5, true)], Ordering::Relaxed);
+        ///
+        /// let bits = bit_box.get_many(&[0, 1, 2, 3, 5, 9], Ordering::Relaxed);
+        /// assert_eq!(bits, [Some(true), Some(false), Some(true), Some(false), Some(true), Some(true)]);
+        /// ```
+        pub fn set_values(&self, updates: &[(usize, bool)], order: Ordering) {
+            let mut sorted = updates.iter().copied().filter(|&(i, _)| i < self.len).collect::<Vec<_>>();
+            // Stable, so repeated indices keep their relative order
+            sorted.sort_by_key(|&(i, _)| i / Self::BIT_SIZE);
+
+            let apply = |word: usize, set: T, clear: T| {
+                let word = &self.bits[word];
+                if clear == T::zero() {
+                    word.fetch_or(set, order);
+                } else if set == T::zero() {
+                    word.fetch_and(!clear, order);
+                } else {
+                    let _ = word.fetch_update(order, crate::traits::failure_ordering(order), |v| Some((v | set) & !clear));
+                }
+            };
+
+            let mut current: Option<(usize, T, T)> = None;
+            for (i, v) in sorted {
+                let (word, bit) = (i / Self::BIT_SIZE, T::one() << (i % Self::BIT_SIZE));
+                let (set, clear) = match current {
+                    Some((w, set, clear)) if w == word => (set, clear),
+                    prev => {
+                        if let Some((w, set, clear)) = prev {
+                            apply(w, set, clear);
+                        }
+                        (T::zero(), T::zero())
+                    }
+                };
+
+                current = Some(match v {
+                    true => (word, set | bit, clear & !bit),
+                    false => (word, set & !bit, clear | bit),
+                });
+            }
+
+            if let Some((w, set, clear)) = current {
+                apply(w, set, clear);
+            }
+        }
+
+        /// Returns the values of every bit of the bitfield, starting with the bit at index `0`.
+        ///
+        /// Each backing word is loaded once with the specified `order`, but the bitfield as a whole isn't read atomically.
+        pub fn to_bools(&self, order: Ordering) -> Vec<bool> {
+            let mut result = Vec::with_capacity(self.len);
+            for word in &*self.bits {
+                let v = word.load(order);
+                let count = Self::BIT_SIZE.min(self.len - result.len());
+                result.extend((0..count).map(|i| (v & (T::one() << i)) != T::zero()));
+            }
+            return result;
+        }
+
+        /// Folds every backing word into an accumulator, starting with `init` and the word holding the bit at index `0`.
+        ///
+        /// Each word is loaded once with the specified `order`, but the bitfield as a whole isn't read atomically.
+        /// The bits of the last word that are outside the bitfield's bounds are always passed to `f` as `false`.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(10);
+        /// bit_box.set(2, Ordering::Relaxed);
+        /// bit_box.set(9, Ordering::Relaxed);
+        ///
+        /// let ones = bit_box.fold_words(0, |acc, word| acc + word.count_ones(), Ordering::Relaxed);
+        /// assert_eq!(ones, 2);
+        /// ```
+        pub fn fold_words<B, F: FnMut(B, T) -> B>(&self, init: B, mut f: F, order: Ordering) -> B {
+            let rem = self.len % Self::BIT_SIZE;
+            let mut acc = init;
+            if let Some((last, rest)) = self.bits.split_last() {
+                for word in rest {
+                    acc = f(acc, word.load(order));
+                }
+
+                let v = last.load(order);
+                acc = f(acc, if rem > 0 { v & !(!T::zero() << rem) } else { v });
+            }
+            return acc;
+        }
+
+        /// Returns `true` if every bit in `range` is set.
+        ///
+        /// The range is clamped to the bitfield's bounds, and an empty range is considered to have every (no) bit set.
+        /// Each affected word is loaded once with the specified `order`, but the range as a whole isn't read atomically.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(10);
+        /// (6..10).for_each(|i| { bit_box.set(i, Ordering::Relaxed); });
+        ///
+        /// assert!(bit_box.all_set(6.., Ordering::Relaxed));
+        /// assert!(!bit_box.all_set(5.., Ordering::Relaxed));
+        /// assert!(bit_box.all_set(3..3, Ordering::Relaxed));
+        /// ```
+        pub fn all_set<R: RangeBounds<usize>>(&self, range: R, order: Ordering) -> bool {
+            return self.range_words(self.clamp_range(range), order).all(|(v, mask)| v == mask);
+        }
+
+        /// Returns `true` if at least one bit in `range` is set.
+        ///
+        /// The range is clamped to the bitfield's bounds, and an empty range is considered to have no bit set.
+        /// Each affected word is loaded once with the specified `order`, but the range as a whole isn't read atomically.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(10);
+        /// bit_box.set(7, Ordering::Relaxed);
+        ///
+        /// assert!(bit_box.any_set(5..8, Ordering::Relaxed));
+        /// assert!(!bit_box.any_set(..7, Ordering::Relaxed));
+        /// ```
+        pub fn any_set<R: RangeBounds<usize>>(&self, range: R, order: Ordering) -> bool {
+            return self.range_words(self.clamp_range(range), order).any(|(v, _)| v != T::zero());
+        }
+
+        /// Returns `true` if no bit in `range` is set.
+        ///
+        /// The range is clamped to the bitfield's bounds, and an empty range is considered to have no bit set.
+        /// Each affected word is loaded once with the specified `order`, but the range as a whole isn't read atomically.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(10);
+        /// bit_box.set(7, Ordering::Relaxed);
+        ///
+        /// assert!(bit_box.none_set(..7, Ordering::Relaxed));
+        /// assert!(!bit_box.none_set(.., Ordering::Relaxed));
+        /// ```
+        #[inline]
+        pub fn none_set<R: RangeBounds<usize>>(&self, range: R, order: Ordering) -> bool {
+            return !self.any_set(range, order);
+        }
+
+        /// Returns the amount of bits in `range` that are set.
+        ///
+        /// The range is clamped to the bitfield's bounds.
+        /// Each affected word is loaded once with the specified `order`, but the range as a whole isn't read atomically.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(20);
+        /// bit_box.set_range(5..15, Ordering::Relaxed);
+        ///
+        /// assert_eq!(bit_box.count_ones_range(.., Ordering::Relaxed), 10);
+        /// assert_eq!(bit_box.count_ones_range(7..=9, Ordering::Relaxed), 3);
+        /// assert_eq!(bit_box.count_ones_range(12..100, Ordering::Relaxed), 3);
+        /// ```
+        pub fn count_ones_range<R: RangeBounds<usize>>(&self, range: R, order: Ordering) -> usize {
+            return self
+                .range_words(self.clamp_range(range), order)
+                .map(|(v, _)| v.count_ones() as usize)
+                .sum();
+        }
+
+        /// Returns the index of the lowest bit that's set, or `None` if no bit is set.
+        ///
+        /// Each backing word is loaded once with the specified `order` (stopping at the first one with a set bit),
+        /// but the bitfield as a whole isn't read atomically.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(10);
+        /// assert_eq!(bit_box.first_set(Ordering::Relaxed), None);
+        ///
+        /// bit_box.set(9, Ordering::Relaxed);
+        /// bit_box.set(8, Ordering::Relaxed);
+        /// assert_eq!(bit_box.first_set(Ordering::Relaxed), Some(8));
+        /// ```
+        pub fn first_set(&self, order: Ordering) -> Option<usize> {
+            for (word, mask) in Self::range_masks(0..self.len) {
+                let v = self.bits[word].load(order) & mask;
+                if v != T::zero() {
+                    return Some(word * Self::BIT_SIZE + v.trailing_zeros() as usize);
+                }
+            }
+            return None;
+        }
+
+        /// Returns the index of the lowest bit that's clear, or `None` if every bit is set.
+        ///
+        /// Each backing word is loaded once with the specified `order` (stopping at the first one with a clear bit),
+        /// but the bitfield as a whole isn't read atomically. Bits of the last word that are outside the bitfield's bounds are never reported.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(10);
+        /// assert_eq!(bit_box.first_clear(Ordering::Relaxed), Some(0));
+        ///
+        /// (0..9).for_each(|i| { bit_box.set(i, Ordering::Relaxed); });
+        /// assert_eq!(bit_box.first_clear(Ordering::Relaxed), Some(9));
+        ///
+        /// bit_box.set(9, Ordering::Relaxed);
+        /// assert_eq!(bit_box.first_clear(Ordering::Relaxed), None);
+        /// ```
+        pub fn first_clear(&self, order: Ordering) -> Option<usize> {
+            for (word, mask) in Self::range_masks(0..self.len) {
+                let v = !self.bits[word].load(order) & mask;
+                if v != T::zero() {
+                    return Some(word * Self::BIT_SIZE + v.trailing_zeros() as usize);
+                }
+            }
+            return None;
+        }
+
+        /// Flips every bit in `range`, clamped to the bitfield's bounds.
+        ///
+        /// Each affected word is updated with a single `fetch_xor` with the specified `order`, but the range as a whole isn't updated atomically.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(10);
+        /// bit_box.set(7, Ordering::Relaxed);
+        /// bit_box.toggle_range(6..9, Ordering::Relaxed);
+        ///
+        /// assert_eq!(bit_box.get(6, Ordering::Relaxed), Some(true));
+        /// assert_eq!(bit_box.get(7, Ordering::Relaxed), Some(false));
+        /// assert_eq!(bit_box.get(8, Ordering::Relaxed), Some(true));
+        /// ```
+        pub fn toggle_range<R: RangeBounds<usize>>(&self, range: R, order: Ordering) {
+            for (word, mask) in Self::range_masks(self.clamp_range(range)) {
+                self.bits[word].fetch_xor(mask, order);
+            }
+        }
+
+        /// Sets every bit in `range` to `true`, clamped to the bitfield's bounds.
+        ///
+        /// Each affected word is updated with a single `fetch_or` with the specified `order`, so the range as a whole isn't updated atomically.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(20);
+        /// bit_box.set_range(6..17, Ordering::Relaxed);
+        /// assert!(bit_box.all_set(6..17, Ordering::Relaxed));
+        /// assert!(bit_box.none_set(..6, Ordering::Relaxed));
+        /// assert!(bit_box.none_set(17.., Ordering::Relaxed));
+        /// ```
+        pub fn set_range<R: RangeBounds<usize>>(&self, range: R, order: Ordering) {
+            for (word, mask) in Self::range_masks(self.clamp_range(range)) {
+                self.bits[word].fetch_or(mask, order);
+            }
+        }
+
+        /// Sets every bit in `range` to `false`, clamped to the bitfield's bounds.
+        ///
+        /// Each affected word is updated with a single `fetch_and` with the specified `order`, so the range as a whole isn't updated atomically.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(20);
+        /// bit_box.set_all(Ordering::Relaxed);
+        /// bit_box.clear_range(6..17, Ordering::Relaxed);
+        /// assert!(bit_box.none_set(6..17, Ordering::Relaxed));
+        /// assert!(bit_box.all_set(..6, Ordering::Relaxed));
+        /// assert!(bit_box.all_set(17.., Ordering::Relaxed));
+        /// ```
+        pub fn clear_range<R: RangeBounds<usize>>(&self, range: R, order: Ordering) {
+            for (word, mask) in Self::range_masks(self.clamp_range(range)) {
+                self.bits[word].fetch_and(!mask, order);
+            }
+        }
+
+        /// Copies the words of `from` into `to` (non-atomically), clearing the bits of `to` past `len`.
+        fn copy_words(from: &mut [T::AtomicInt], to: &mut [T::AtomicInt], len: usize) {
+            for (to, from) in to.iter_mut().zip(from.iter_mut()) {
+                *to.get_mut() = *from.get_mut();
+            }
+
+            let rem = len % Self::BIT_SIZE;
+            if let (Some(last), true) = (to.last_mut(), rem > 0) {
+                *last.get_mut() = *last.get_mut() & !(!T::zero() << rem);
+            }
+        }
+
+        /// Returns the words affected by `range`, alongside the mask of the bits inside it.
+        /// The loaded words are already masked.
+        fn range_words(&self, range: Range<usize>, order: Ordering) -> impl '_ + Iterator<Item = (T, T)> {
+            return Self::range_masks(range)
+                .map(move |(word, mask)| (self.bits[word].load(order) & mask, mask));
+        }
+
+        /// Returns the indices of the words affected by `range`, alongside the mask of the bits inside it.
+        fn range_masks(Range { start, end }: Range<usize>) -> impl Iterator<Item = (usize, T)> {
+            let ones = |n: usize| if n >= Self::BIT_SIZE { !T::zero() } else { !(!T::zero() << n) };
+
+            let words = if start < end { start / Self::BIT_SIZE..div_ceil(end, Self::BIT_SIZE) } else { 0..0 };
+            return words.map(move |word| {
+                let lo = start.saturating_sub(word * Self::BIT_SIZE);
+                let hi = (end - word * Self::BIT_SIZE).min(Self::BIT_SIZE);
+                (word, ones(hi) & !ones(lo))
+            });
+        }
+
+        #[inline]
+        fn clamp_range<R: RangeBounds<usize>>(&self, range: R) -> Range<usize> {
+            let start = match range.start_bound() {
+                Bound::Included(&x) => x,
+                Bound::Excluded(&x) => x.saturating_add(1),
+                Bound::Unbounded => 0,
+            };
+            let end = match range.end_bound() {
+                Bound::Included(&x) => x.saturating_add(1),
+                Bound::Excluded(&x) => x,
+                Bound::Unbounded => self.len,
+            };
+            return start.min(self.len)..end.min(self.len);
+        }
+
+        /// Returns an iterator over the indices of the set bits, clearing them as they're yielded.
+        ///
+        /// Each backing word is atomically cleared (with a single `fetch_and`) once the iterator reaches it, and the indices of it's
+        /// previously set bits are then yielded in ascending order. If the iterator is fully consumed, every bit that was set when
+        /// it's word got processed will have been cleared. Bits set concurrently on a word that was already processed won't be yielded,
+        /// and will remain set.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(10);
+        /// bit_box.set(2, Ordering::Relaxed);
+        /// bit_box.set(9, Ordering::Relaxed);
+        ///
+        /// let drained = bit_box.drain_set(Ordering::AcqRel).collect::<Vec<_>>();
+        /// assert_eq!(drained, [2, 9]);
+        /// assert_eq!(bit_box.get(9, Ordering::Relaxed), Some(false));
+        /// ```
+        pub fn drain_set(&self, order: Ordering) -> impl '_ + Iterator<Item = usize> {
+            return (0..self.bits.len()).flat_map(move |word| {
+                let v = self.bits[word].fetch_and(T::zero(), order);
+                (0..Self::BIT_SIZE)
+                    .filter(move |&i| (v & (T::one() << i)) != T::zero())
+                    .map(move |i| word * Self::BIT_SIZE + i)
+                    .take_while(move |&i| i < self.len)
+            });
+        }
+
+        /// Sets every bit of the bitfield to `true`.
+        ///
+        /// Each backing word is written with a separate `store` with the specified `order`, so the bitfield as a whole isn't updated atomically.
+        /// Bits of the last word that are outside the bitfield's bounds are kept as `false`.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(10);
+        /// bit_box.set_all(Ordering::Relaxed);
+        /// assert!((0..10).all(|i| bit_box.get(i, Ordering::Relaxed) == Some(true)));
+        /// ```
+        pub fn set_all(&self, order: Ordering) {
+            let rem = self.len % Self::BIT_SIZE;
+            if let Some((last, rest)) = self.bits.split_last() {
+                for word in rest {
+                    word.store(!T::zero(), order);
+                }
+                last.store(if rem > 0 { !(!T::zero() << rem) } else { !T::zero() }, order);
+            }
+        }
+
+        /// Sets every bit of the bitfield to `false`.
+        ///
+        /// Each backing word is written with a separate `store` with the specified `order`, so the bitfield as a whole isn't updated atomically.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let bit_box = AtomicBitBox::<u8>::new(10);
+        /// bit_box.set(9, Ordering::Relaxed);
+        /// bit_box.clear_all(Ordering::Relaxed);
+        /// assert_eq!(bit_box.get(9, Ordering::Relaxed), Some(false));
+        /// ```
+        #[inline]
+        pub fn clear_all(&self, order: Ordering) {
+            self.bits.iter().for_each(|x| x.store(T::zero(), order));
+        }
+
+        /// Shifts every bit of the bitfield `n` positions towards the higher indices (the bit at index `i` is moved to index `i + n`).
+        /// Bits shifted past the bitfield's length are discarded, and the lowest `n` bits are set to `false`.
+        ///
+        /// This is a non-atomic operation, made safe by the mutable reference.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let mut bit_box = AtomicBitBox::<u8>::new(10);
+        /// bit_box.set(6, Ordering::Relaxed);
+        /// bit_box.shift_left(3);
+        /// assert_eq!(bit_box.get(6, Ordering::Relaxed), Some(false));
+        /// assert_eq!(bit_box.get(9, Ordering::Relaxed), Some(true));
+        /// ```
+        pub fn shift_left(&mut self, n: usize) {
+            if n >= self.len {
+                self.bits.iter_mut().for_each(|x| *x.get_mut() = T::zero());
+                return
+            }
+
+            let word_shift = n / Self::BIT_SIZE;
+            let bit_shift = n % Self::BIT_SIZE;
+
+            for i in (0..self.bits.len()).rev() {
+                let mut v = T::zero();
+                if i >= word_shift {
+                    v = *self.bits[i - word_shift].get_mut() << bit_shift;
+                    if bit_shift > 0 && i > word_shift {
+                        v = v | (*self.bits[i - word_shift - 1].get_mut() >> (Self::BIT_SIZE - bit_shift));
+                    }
+                }
+                *self.bits[i].get_mut() = v;
+            }
+
+            self.mask_last_word();
+        }
+
+        /// Shifts every bit of the bitfield `n` positions towards the lower indices (the bit at index `i` is moved to index `i - n`).
+        /// Bits shifted below index `0` are discarded, and the highest `n` bits are set to `false`.
+        ///
+        /// This is a non-atomic operation, made safe by the mutable reference.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let mut bit_box = AtomicBitBox::<u8>::new(10);
+        /// bit_box.set(9, Ordering::Relaxed);
+        /// bit_box.shift_right(3);
+        /// assert_eq!(bit_box.get(9, Ordering::Relaxed), Some(false));
+        /// assert_eq!(bit_box.get(6, Ordering::Relaxed), Some(true));
+        /// ```
+        pub fn shift_right(&mut self, n: usize) {
+            if n >= self.len {
+                self.bits.iter_mut().for_each(|x| *x.get_mut() = T::zero());
+                return
+            }
+
+            self.mask_last_word();
+            let word_shift = n / Self::BIT_SIZE;
+            let bit_shift = n % Self::BIT_SIZE;
+            let count = self.bits.len();
+
+            for i in 0..count {
+                let mut v = T::zero();
+                if i + word_shift < count {
+                    v = *self.bits[i + word_shift].get_mut() >> bit_shift;
+                    if bit_shift > 0 && i + word_shift + 1 < count {
+                        v = v | (*self.bits[i + word_shift + 1].get_mut() << (Self::BIT_SIZE - bit_shift));
+                    }
                 }
-                return minor < self.len % Self::BIT_SIZE
+                *self.bits[i].get_mut() = v;
+            }
+
+            self.mask_last_word();
+        }
+
+        /// Returns the backing words of the bitfield as a mutable slice of primitives, allowing fast non-atomic bulk edits.
+        /// The bit at index `i` is stored in the bit `i % T::BITS` of the word `i / T::BITS`.
+        ///
+        /// This is a non-atomic operation, made safe by the mutable reference.
+        ///
+        /// The bits of the last word that are outside the bitfield's bounds may be freely modified, but they'll be masked off
+        /// by every subsequent read.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// let mut bit_box = AtomicBitBox::<u8>::new(10);
+        /// bit_box.as_mut_words().fill(u8::MAX);
+        ///
+        /// assert_eq!(bit_box.get(9, Ordering::Relaxed), Some(true));
+        /// assert_eq!(bit_box.drain_set(Ordering::Relaxed).count(), 10);
+        /// ```
+        #[inline]
+        pub fn as_mut_words(&mut self) -> &mut [T] {
+            return <T::AtomicInt as Atomic>::get_mut_slice(&mut self.bits);
+        }
+
+        /// Returns a reference to the bit at the specified index, or `None` if the index is out of bounds.
+        ///
+        /// The reference reads and writes the bit through non-atomic operations, made safe by the mutable reference.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AtomicBitBox;
+        ///
+        /// let mut bit_box = AtomicBitBox::<u8>::new(10);
+        /// let mut bit = bit_box.bit(9).unwrap();
+        /// assert!(!bit.toggle());
+        /// assert!(bit.get());
+        ///
+        /// assert!(bit_box.bit(10).is_none());
+        /// ```
+        #[inline]
+        pub fn bit(&mut self, idx: usize) -> Option<BitRef<'_, T>> {
+            if idx >= self.len {
+                return None
+            }
+
+            let word = self.bits[idx / Self::BIT_SIZE].get_mut();
+            return Some(BitRef { word, mask: T::one() << (idx % Self::BIT_SIZE) });
+        }
+
+        /// Clears the bits of the last word that are outside the bitfield's bounds.
+        #[inline]
+        fn mask_last_word(&mut self) {
+            let rem = self.len % Self::BIT_SIZE;
+            if let (true, Some(last)) = (rem > 0, self.bits.last_mut()) {
+                let last = last.get_mut();
+                *last = *last & !(!T::zero() << rem);
             }
         }
     }
 }
 
+/// A mutable reference to a single bit of an [`AtomicBitBox`], returned by [`bit`](AtomicBitBox::bit).
+#[derive(Debug)]
+pub struct BitRef<'a, T> {
+    word: &'a mut T,
+    mask: T,
+}
+
+impl<T: BitFieldAble> BitRef<'_, T> {
+    /// Returns the value of the bit
+    #[inline]
+    pub fn get(&self) -> bool {
+        return (*self.word & self.mask) != T::zero();
+    }
+
+    /// Sets the value of the bit, returning it's previous value
+    #[inline]
+    pub fn set(&mut self, v: bool) -> bool {
+        let prev = self.get();
+        *self.word = match v {
+            true => *self.word | self.mask,
+            false => *self.word & !self.mask,
+        };
+        return prev;
+    }
+
+    /// Flips the value of the bit, returning it's previous value
+    #[inline]
+    pub fn toggle(&mut self) -> bool {
+        let prev = self.get();
+        *self.word = *self.word ^ self.mask;
+        return prev;
+    }
+}
+
 pub trait BitFieldAble:
     Num
+    + PrimInt
     + Copy
     + Zeroable
     + Eq
     + BitAnd<Output = Self>
+    + BitOr<Output = Self>
     + Shl<usize, Output = Self>
     + Shr<usize, Output = Self>
     + Not<Output = Self>
@@ -270,10 +1188,12 @@ pub trait BitFieldAble:
 }
 impl<T> BitFieldAble for T where
     T: Num
+        + PrimInt
         + Copy
         + Zeroable
         + Eq
         + BitAnd<Output = Self>
+        + BitOr<Output = Self>
         + Shl<usize, Output = Self>
         + Shr<usize, Output = Self>
         + Not<Output = Self>
@@ -283,6 +1203,7 @@ impl<T> BitFieldAble for T where
 // Thanks ChatGPT!
 #[cfg(test)]
 mod tests {
+    use alloc::{format, string::ToString};
     use core::sync::atomic::Ordering;
 
     pub type AtomicBitBox = super::AtomicBitBox<u16>;
@@ -331,6 +1252,491 @@ mod tests {
         assert_eq!(bitbox.clear(11, Ordering::SeqCst), None);
     }
 
+    #[test]
+    fn full_words_in_bounds() {
+        let bitbox = AtomicBitBox::new(32);
+        assert_eq!(bitbox.set(31, Ordering::SeqCst), Some(false));
+        assert_eq!(bitbox.get(31, Ordering::SeqCst), Some(true));
+        assert_eq!(bitbox.get(32, Ordering::SeqCst), None);
+
+        let bitbox = AtomicBitBox::new(0);
+        assert_eq!(bitbox.get(0, Ordering::SeqCst), None);
+    }
+
+    fn ones(bitbox: &AtomicBitBox) -> alloc::vec::Vec<usize> {
+        (0..bitbox.len)
+            .filter(|i| bitbox.get(*i, Ordering::Relaxed).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn count_ones_range_matches_brute_force() {
+        let bitbox = super::AtomicBitBox::<u8>::from_fn(45, |i| i % 3 == 0 || i % 7 == 0);
+        let ranges = [(0, 45), (0, 0), (3, 5), (7, 8), (5, 30), (8, 16), (15, 17), (40, 45), (44, 100), (60, 100), (20, 10)];
+
+        for (start, end) in ranges {
+            let expected = (start..end.min(45))
+                .filter(|&i| bitbox.get(i, Ordering::Relaxed).unwrap())
+                .count();
+            assert_eq!(bitbox.count_ones_range(start..end, Ordering::Relaxed), expected, "{start}..{end}");
+        }
+
+        assert_eq!(bitbox.count_ones_range(.., Ordering::Relaxed), bitbox.count_ones_range(0..45, Ordering::Relaxed));
+        assert_eq!(bitbox.count_ones_range(3..=3, Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn set_indices_scattered() {
+        let bitbox = AtomicBitBox::new(70);
+        let indices = [69, 3, 17, 16, 3, 40, 0, 70, 1000, 31, 32];
+        bitbox.set_indices(&indices, Ordering::Relaxed);
+
+        for i in 0..70 {
+            assert_eq!(bitbox.get(i, Ordering::Relaxed), Some(indices.contains(&i)), "{i}");
+        }
+        assert_eq!(ones(&bitbox), [0, 3, 16, 17, 31, 32, 40, 69]);
+
+        bitbox.set_indices(&[], Ordering::Relaxed);
+        bitbox.set_indices(&[70, 71], Ordering::Relaxed);
+        assert_eq!(bitbox.count_ones_range(.., Ordering::Relaxed), 8);
+    }
+
+    #[test]
+    fn set_values_mixed() {
+        let bitbox = AtomicBitBox::from_fn(40, |i| i % 2 == 0);
+        let updates = [
+            (0, false), (1, true), (2, false), (2, true), // word 0, with a repeated index
+            (16, false), (17, true), (31, true), (30, false), // word 1
+            (33, true), // word 2, sets only
+            (39, false), (38, false), // word 2, clears only
+            (40, true), (1000, false),
+        ];
+        bitbox.set_values(&updates, Ordering::Relaxed);
+
+        for i in 0..40 {
+            let expected = updates.iter().rev().find(|&&(j, _)| j == i).map_or(i % 2 == 0, |&(_, v)| v);
+            assert_eq!(bitbox.get(i, Ordering::Relaxed), Some(expected), "{i}");
+        }
+
+        bitbox.set_values(&[], Ordering::Relaxed);
+        assert_eq!(bitbox.get(40, Ordering::Relaxed), None);
+    }
+
+    #[test]
+    fn bit_ref() {
+        let mut bitbox = AtomicBitBox::new(20);
+        bitbox.set(3, Ordering::Relaxed);
+
+        let mut bit = bitbox.bit(17).unwrap();
+        assert!(!bit.get());
+        assert!(!bit.toggle());
+        assert!(bit.get());
+        assert!(bit.set(true));
+        assert_eq!(bitbox.get(17, Ordering::Relaxed), Some(true));
+
+        let mut bit = bitbox.bit(3).unwrap();
+        assert!(bit.toggle());
+        assert!(!bit.set(false));
+        assert_eq!(ones(&bitbox), [17]);
+
+        assert!(bitbox.bit(20).is_none());
+    }
+
+    #[test]
+    fn as_mut_words_bulk_edit() {
+        let mut bitbox = AtomicBitBox::new(20);
+        let words = bitbox.as_mut_words();
+        assert_eq!(words.len(), 2);
+        words[0] = 0b1010_0000_0000_0001;
+        // Bits 20 and up are padding
+        words[1] = u16::MAX;
+
+        assert_eq!(bitbox.get(0, Ordering::Relaxed), Some(true));
+        assert_eq!(bitbox.get(1, Ordering::Relaxed), Some(false));
+        assert_eq!(bitbox.get(13, Ordering::Relaxed), Some(true));
+        assert_eq!(bitbox.get(15, Ordering::Relaxed), Some(true));
+        assert!(bitbox.all_set(16..20, Ordering::Relaxed));
+        assert_eq!(bitbox.get(20, Ordering::Relaxed), None);
+
+        let count = bitbox.fold_words(0, |acc, word| acc + word.count_ones(), Ordering::Relaxed);
+        assert_eq!(count, 7);
+
+        bitbox.shift_right(16);
+        assert_eq!(ones(&bitbox), [0, 1, 2, 3]);
+
+        bitbox.as_mut_words()[1] = u16::MAX;
+        bitbox.resize(32);
+        assert_eq!(ones(&bitbox), [0, 1, 2, 3, 16, 17, 18, 19]);
+    }
+
+    #[test]
+    fn shift_across_words() {
+        let mut bitbox = AtomicBitBox::new(40);
+        for i in [0, 15, 17, 39] {
+            bitbox.set(i, Ordering::Relaxed);
+        }
+
+        bitbox.shift_left(3);
+        assert_eq!(ones(&bitbox), [3, 18, 20]);
+
+        bitbox.shift_left(16);
+        assert_eq!(ones(&bitbox), [19, 34, 36]);
+
+        bitbox.shift_right(17);
+        assert_eq!(ones(&bitbox), [2, 17, 19]);
+
+        bitbox.shift_right(3);
+        assert_eq!(ones(&bitbox), [14, 16]);
+
+        // Bits shifted past the length must stay masked in the last word
+        bitbox.shift_left(25);
+        assert_eq!(ones(&bitbox), [39]);
+        bitbox.shift_right(39);
+        assert_eq!(ones(&bitbox), [0]);
+
+        bitbox.shift_left(40);
+        assert_eq!(ones(&bitbox), []);
+    }
+
+    // Only runs on targets with native 128-bit atomics (e.g. aarch64), which excludes x86_64
+    #[cfg(all(feature = "nightly", target_has_atomic = "128"))]
+    #[test]
+    fn u128_words() {
+        let bitbox = super::AtomicBitBox::<u128>::new(200);
+        assert_eq!(bitbox.bits.len(), 2);
+
+        for i in [0, 127, 128, 199] {
+            assert_eq!(bitbox.set(i, Ordering::Relaxed), Some(false));
+        }
+
+        for i in 0..200 {
+            let expected = matches!(i, 0 | 127 | 128 | 199);
+            assert_eq!(bitbox.get(i, Ordering::Relaxed), Some(expected));
+        }
+
+        assert_eq!(bitbox.clear(127, Ordering::Relaxed), Some(true));
+        assert_eq!(bitbox.get(127, Ordering::Relaxed), Some(false));
+        assert_eq!(bitbox.get(128, Ordering::Relaxed), Some(true));
+        assert_eq!(bitbox.get(200, Ordering::Relaxed), None);
+        assert_eq!(bitbox.set(255, Ordering::Relaxed), None);
+    }
+
+    #[test]
+    fn bools_round_trip() {
+        for len in [0, 1, 15, 16, 17, 40] {
+            let bools = (0..len).map(|i| i % 3 == 0 || i % 5 == 0).collect::<alloc::vec::Vec<_>>();
+            let bitbox = AtomicBitBox::from(&bools as &[bool]);
+            assert_eq!(bitbox.len, len);
+            assert_eq!(bitbox.to_bools(Ordering::Relaxed), bools);
+
+            for (i, &bit) in bools.iter().enumerate() {
+                assert_eq!(bitbox.get(i, Ordering::Relaxed), Some(bit));
+            }
+            assert_eq!(bitbox.get(len, Ordering::Relaxed), None);
+        }
+
+        // Bits past the bitfield's length are never set
+        let bitbox = AtomicBitBox::from_bools(&[true; 17]);
+        assert_eq!(bitbox.bits[1].load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn set_and_clear_all() {
+        let bitbox = AtomicBitBox::new(40);
+        bitbox.set_all(Ordering::Relaxed);
+        assert_eq!(ones(&bitbox), (0..40).collect::<alloc::vec::Vec<_>>());
+        // The bits past the bitfield's length stay cleared
+        assert_eq!(bitbox.bits[2].load(Ordering::Relaxed), 0xff);
+
+        bitbox.clear_all(Ordering::Relaxed);
+        assert!(ones(&bitbox).is_empty());
+        assert!(bitbox.bits.iter().all(|x| x.load(Ordering::Relaxed) == 0));
+
+        let bitbox = AtomicBitBox::new(32);
+        bitbox.set_all(Ordering::Relaxed);
+        assert!(bitbox.bits.iter().all(|x| x.load(Ordering::Relaxed) == u16::MAX));
+
+        AtomicBitBox::new(0).set_all(Ordering::Relaxed);
+    }
+
+    #[test]
+    fn fold_words_popcount() {
+        let bitbox = AtomicBitBox::new(37);
+        for i in [0, 5, 15, 16, 30, 36] {
+            bitbox.set(i, Ordering::Relaxed);
+        }
+
+        let folded = bitbox.fold_words(0, |acc, word| acc + word.count_ones(), Ordering::Relaxed);
+        let expected = bitbox.to_bools(Ordering::Relaxed).into_iter().filter(|&x| x).count();
+        assert_eq!(folded as usize, expected);
+        assert_eq!(folded, 6);
+
+        let words = bitbox.fold_words(0, |acc, _| acc + 1, Ordering::Relaxed);
+        assert_eq!(words, 3);
+    }
+
+    #[test]
+    fn range_queries_across_words() {
+        use core::ops::Bound;
+
+        let bitbox = AtomicBitBox::new(40);
+        (10..35).for_each(|i| {
+            bitbox.set(i, Ordering::Relaxed);
+        });
+
+        assert!(bitbox.all_set(10..35, Ordering::Relaxed));
+        assert!(bitbox.all_set(16..=31, Ordering::Relaxed));
+        assert!(!bitbox.all_set(9..35, Ordering::Relaxed));
+        assert!(!bitbox.all_set(10..36, Ordering::Relaxed));
+
+        assert!(bitbox.any_set(0..11, Ordering::Relaxed));
+        assert!(bitbox.any_set(34.., Ordering::Relaxed));
+        assert!(!bitbox.any_set(..10, Ordering::Relaxed));
+        assert!(bitbox.none_set(35.., Ordering::Relaxed));
+        assert!(!bitbox.none_set(.., Ordering::Relaxed));
+
+        bitbox.clear(20, Ordering::Relaxed);
+        assert!(!bitbox.all_set(10..35, Ordering::Relaxed));
+        assert!(bitbox.all_set(21..35, Ordering::Relaxed));
+
+        // Empty ranges
+        assert!(bitbox.all_set(15..15, Ordering::Relaxed));
+        assert!(!bitbox.any_set(15..15, Ordering::Relaxed));
+        assert!(bitbox.none_set((Bound::Excluded(30), Bound::Excluded(31)), Ordering::Relaxed));
+
+        // Out of bounds ranges are clamped
+        assert!(bitbox.none_set(35..100, Ordering::Relaxed));
+        assert!(bitbox.all_set(100..200, Ordering::Relaxed));
+        assert!(!bitbox.any_set(100..200, Ordering::Relaxed));
+
+        bitbox.set_all(Ordering::Relaxed);
+        assert!(bitbox.all_set(.., Ordering::Relaxed));
+        assert!(bitbox.all_set(..usize::MAX, Ordering::Relaxed));
+        assert!(bitbox.all_set(..=usize::MAX, Ordering::Relaxed));
+    }
+
+    #[test]
+    fn from_fn_and_ranges() {
+        let f = |i: usize| matches!((i % 7, i % 5), (0, _) | (_, 1));
+        let bitbox = AtomicBitBox::from_fn(75, f);
+        assert_eq!(bitbox.len, 75);
+        assert_eq!(bitbox.to_bools(Ordering::Relaxed), (0..75).map(f).collect::<alloc::vec::Vec<_>>());
+        assert_eq!(AtomicBitBox::from_fn(0, f).bits.len(), 0);
+
+        // Matches the per-bit operations
+        let expected = AtomicBitBox::from_fn(75, f);
+        bitbox.set_range(9..=50, Ordering::Relaxed);
+        (9..=50).for_each(|i| {
+            expected.set(i, Ordering::Relaxed);
+        });
+        assert_eq!(bitbox.to_bools(Ordering::Relaxed), expected.to_bools(Ordering::Relaxed));
+
+        bitbox.clear_range(3..70, Ordering::Relaxed);
+        (3..70).for_each(|i| {
+            expected.clear(i, Ordering::Relaxed);
+        });
+        assert_eq!(bitbox.to_bools(Ordering::Relaxed), expected.to_bools(Ordering::Relaxed));
+
+        // Out of bounds ranges are clamped, and the bits past the length are kept cleared
+        bitbox.set_range(60..1000, Ordering::Relaxed);
+        assert!(bitbox.all_set(70.., Ordering::Relaxed));
+        assert_eq!(bitbox.bits[4].load(Ordering::Relaxed) & !0x7ff, 0);
+    }
+
+    #[test]
+    fn toggle_range_across_words() {
+        let bitbox = AtomicBitBox::new(40);
+        let expected = AtomicBitBox::new(40);
+        for i in (0..40).filter(|i| i % 3 == 0) {
+            bitbox.set(i, Ordering::Relaxed);
+            expected.set(i, Ordering::Relaxed);
+        }
+        let original = bitbox.to_bools(Ordering::Relaxed);
+
+        bitbox.toggle_range(5..=33, Ordering::Relaxed);
+        for i in 5..=33 {
+            match expected.get(i, Ordering::Relaxed) {
+                Some(true) => expected.clear(i, Ordering::Relaxed),
+                _ => expected.set(i, Ordering::Relaxed),
+            };
+        }
+        assert_eq!(bitbox.to_bools(Ordering::Relaxed), expected.to_bools(Ordering::Relaxed));
+
+        bitbox.toggle_range(5..=33, Ordering::Relaxed);
+        assert_eq!(bitbox.to_bools(Ordering::Relaxed), original);
+
+        // Out of bounds ranges are clamped, and the bits past the length are kept cleared
+        bitbox.toggle_range(30..100, Ordering::Relaxed);
+        assert!((30..40).all(|i| bitbox.get(i, Ordering::Relaxed) == Some(i % 3 != 0)));
+        assert_eq!(bitbox.bits[2].load(Ordering::Relaxed) & !0xff, 0);
+
+        bitbox.toggle_range(30..30, Ordering::Relaxed);
+        bitbox.toggle_range(30.., Ordering::Relaxed);
+        assert_eq!(bitbox.to_bools(Ordering::Relaxed), original);
+    }
+
+    #[test]
+    fn first_set_and_clear() {
+        // Sparse
+        let bitbox = AtomicBitBox::new(40);
+        assert_eq!(bitbox.first_set(Ordering::Relaxed), None);
+        assert_eq!(bitbox.first_clear(Ordering::Relaxed), Some(0));
+
+        bitbox.set(37, Ordering::Relaxed);
+        assert_eq!(bitbox.first_set(Ordering::Relaxed), Some(37));
+        bitbox.set(16, Ordering::Relaxed);
+        assert_eq!(bitbox.first_set(Ordering::Relaxed), Some(16));
+        bitbox.set(3, Ordering::Relaxed);
+        assert_eq!(bitbox.first_set(Ordering::Relaxed), Some(3));
+
+        // Dense
+        bitbox.set_all(Ordering::Relaxed);
+        assert_eq!(bitbox.first_clear(Ordering::Relaxed), None);
+        bitbox.clear(38, Ordering::Relaxed);
+        assert_eq!(bitbox.first_clear(Ordering::Relaxed), Some(38));
+        bitbox.clear(17, Ordering::Relaxed);
+        assert_eq!(bitbox.first_clear(Ordering::Relaxed), Some(17));
+        assert_eq!(bitbox.first_set(Ordering::Relaxed), Some(0));
+
+        // The cleared bits past the length aren't reported
+        let bitbox = AtomicBitBox::new(20);
+        (0..20).for_each(|i| {
+            bitbox.set(i, Ordering::Relaxed);
+        });
+        assert_eq!(bitbox.first_clear(Ordering::Relaxed), None);
+        assert_eq!(AtomicBitBox::new(0).first_clear(Ordering::Relaxed), None);
+    }
+
+    #[test]
+    fn drain_set_empties_field() {
+        let bitbox = AtomicBitBox::new(40);
+        let pattern = (0..40).filter(|i| i % 3 == 0 || i % 7 == 0).collect::<alloc::vec::Vec<_>>();
+        for &i in &pattern {
+            bitbox.set(i, Ordering::Relaxed);
+        }
+
+        let drained = bitbox.drain_set(Ordering::AcqRel).collect::<alloc::vec::Vec<_>>();
+        assert_eq!(drained, pattern);
+        assert!(ones(&bitbox).is_empty());
+        assert_eq!(bitbox.drain_set(Ordering::AcqRel).next(), None);
+
+        // Partially consumed iterators only clear the words they reached
+        bitbox.set(1, Ordering::Relaxed);
+        bitbox.set(35, Ordering::Relaxed);
+        assert_eq!(bitbox.drain_set(Ordering::AcqRel).next(), Some(1));
+        assert_eq!(ones(&bitbox), [35]);
+    }
+
+    #[test]
+    fn get_many_across_words() {
+        let bitbox = AtomicBitBox::new(40);
+        for i in [1, 8, 15, 19, 33] {
+            bitbox.set(i, Ordering::Relaxed);
+        }
+
+        let indices = [33, 19, 0, 8, 40, 1, 15, 9, usize::MAX, 8];
+        let bits = bitbox.get_many(&indices, Ordering::Relaxed);
+        assert_eq!(
+            bits,
+            [
+                Some(true),
+                Some(true),
+                Some(false),
+                Some(true),
+                None,
+                Some(true),
+                Some(true),
+                Some(false),
+                None,
+                Some(true)
+            ]
+        );
+
+        for (&i, &bit) in indices.iter().zip(&bits) {
+            assert_eq!(bitbox.get(i, Ordering::Relaxed), bit);
+        }
+        assert!(bitbox.get_many(&[], Ordering::Relaxed).is_empty());
+    }
+
+    #[test]
+    fn format_bits() {
+        let bitbox = AtomicBitBox::new(20);
+        bitbox.set(0, Ordering::SeqCst);
+        bitbox.set(3, Ordering::SeqCst);
+        bitbox.set(17, Ordering::SeqCst);
+
+        assert_eq!(bitbox.to_string(), "10010000000000000100");
+        assert_eq!(
+            format!("{bitbox:?}"),
+            "AtomicBitBox { len: 20, bits: 10010000000000000100 }"
+        );
+    }
+
+    #[test]
+    fn resize_keeps_bits() {
+        let mut bitbox = AtomicBitBox::new(20);
+        bitbox.set(3, Ordering::Relaxed);
+        bitbox.set(19, Ordering::Relaxed);
+
+        bitbox.resize(50);
+        assert_eq!(bitbox.len, 50);
+        assert_eq!(bitbox.bits.len(), 4);
+        assert!((0..50).all(|i| bitbox.get(i, Ordering::Relaxed) == Some(i == 3 || i == 19)));
+
+        // Shrinking discards the bits past the new length, so they don't reappear when growing again
+        assert_eq!(bitbox.try_resize(10), Ok(()));
+        assert_eq!(bitbox.get(19, Ordering::Relaxed), None);
+        assert_eq!(bitbox.bits[0].load(Ordering::Relaxed), 1 << 3);
+        bitbox.resize(20);
+        assert_eq!(bitbox.get(19, Ordering::Relaxed), Some(false));
+
+        bitbox.resize(0);
+        assert!(bitbox.bits.is_empty());
+    }
+
+    #[test]
+    fn eq_bools() {
+        let expected: &[bool] = &[
+            true, false, false, true, true, false, true, false, false, false, false, false, false, false, false,
+            false, true, true,
+        ];
+        let bitbox = AtomicBitBox::from(expected);
+        assert_eq!(bitbox, expected);
+        assert_eq!(bitbox, *expected);
+
+        assert_ne!(bitbox, &expected[..17]);
+        let mut other = expected.to_vec();
+        other[16] = false;
+        assert_ne!(bitbox, other.as_slice());
+
+        // The padding bits of the last word are ignored
+        let mut bitbox = AtomicBitBox::new(3);
+        bitbox.set(0, Ordering::Relaxed);
+        *bitbox.bits[0].get_mut() |= 1 << 10;
+        assert_eq!(bitbox, &[true, false, false][..]);
+        assert_eq!(AtomicBitBox::new(0), &[][..]);
+    }
+
+    #[test]
+    fn resize_preserves_pattern() {
+        let pattern = |i: usize| i % 3 == 1 || i % 7 == 2;
+
+        for (from, to) in [(100, 37), (37, 100), (32, 31), (31, 32), (33, 65), (65, 1)] {
+            let mut bitbox = AtomicBitBox::from_fn(from, pattern);
+            bitbox.resize(to);
+            assert_eq!(bitbox.len, to);
+
+            let expected = (0..to).map(|i| i < from && pattern(i)).collect::<alloc::vec::Vec<_>>();
+            assert_eq!(bitbox.to_bools(Ordering::Relaxed), expected, "resizing {from} -> {to}");
+            // The padding bits of the final word are always cleared
+            assert_eq!(bitbox.count_ones_range(.., Ordering::Relaxed), expected.iter().filter(|x| **x).count());
+            let rem = to % 16;
+            if let (true, Some(last)) = (rem > 0, bitbox.as_mut_words().last()) {
+                assert_eq!(*last >> rem, 0);
+            }
+        }
+    }
+
     #[cfg(feature = "alloc_api")]
     mod custom_allocator {
         use core::sync::atomic::Ordering;
@@ -374,6 +1780,39 @@ mod tests {
             }
         }
 
+        #[derive(Clone)]
+        struct FailingAlloc<'a>(&'a core::sync::atomic::AtomicBool);
+
+        unsafe impl core::alloc::Allocator for FailingAlloc<'_> {
+            fn allocate(&self, layout: core::alloc::Layout) -> Result<core::ptr::NonNull<[u8]>, core::alloc::AllocError> {
+                match self.0.load(Ordering::Relaxed) {
+                    true => Err(core::alloc::AllocError),
+                    false => System.allocate(layout),
+                }
+            }
+
+            unsafe fn deallocate(&self, ptr: core::ptr::NonNull<u8>, layout: core::alloc::Layout) {
+                System.deallocate(ptr, layout)
+            }
+        }
+
+        #[test]
+        fn failed_resize_leaves_field_intact() {
+            let fail = core::sync::atomic::AtomicBool::new(false);
+            let mut bitbox = super::super::AtomicBitBox::<u16, _>::new_in(20, FailingAlloc(&fail));
+            bitbox.set(7, Ordering::Relaxed);
+            bitbox.set(19, Ordering::Relaxed);
+
+            fail.store(true, Ordering::Relaxed);
+            assert!(bitbox.try_resize(1000).is_err());
+            assert_eq!(bitbox.len, 20);
+            assert!((0..20).all(|i| bitbox.get(i, Ordering::Relaxed) == Some(i == 7 || i == 19)));
+
+            fail.store(false, Ordering::Relaxed);
+            assert_eq!(bitbox.try_resize(1000), Ok(()));
+            assert!((0..1000).all(|i| bitbox.get(i, Ordering::Relaxed) == Some(i == 7 || i == 19)));
+        }
+
         #[test]
         fn out_of_bounds() {
             let bitbox = AtomicBitBox::new_in(10, System);
@@ -0,0 +1,145 @@
+use crate::{
+    notify::{notify, Listener, Notify},
+    FillQueue,
+};
+use std::{
+    fmt::Debug,
+    sync::{Mutex, PoisonError},
+};
+
+/// A FIFO (First In First Out) queue whose consumers can block until an element is available.
+///
+/// Producers push to a [`FillQueue`] with a single atomic operation, and notify the blocked consumers.
+/// Consumers chop the whole queue at once, buffering the elements they didn't pop. The buffer is only refilled once it's empty,
+/// so elements are always popped in the order they were pushed. Since the buffer is guarded by a mutex, consumers don't contend
+/// with producers, but they do contend with each other.
+///
+/// # Example
+/// ```rust
+/// use utils_atomics::BlockingQueue;
+///
+/// let queue = BlockingQueue::new();
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         for i in 0..3 {
+///             queue.push(i);
+///         }
+///     });
+///
+///     assert_eq!(queue.pop_wait(), 0);
+///     assert_eq!(queue.pop_wait(), 1);
+///     assert_eq!(queue.pop_wait(), 2);
+/// });
+/// ```
+pub struct BlockingQueue<T> {
+    queue: FillQueue<T>,
+    // Chopped elements that haven't been popped yet, with the oldest one last
+    buffer: Mutex<Vec<T>>,
+    notify: Notify,
+    listener: Listener,
+}
+
+impl<T> BlockingQueue<T> {
+    /// Creates a new, empty queue
+    #[inline]
+    pub fn new() -> Self {
+        let (notify, listener) = notify();
+        return Self {
+            queue: FillQueue::new(),
+            buffer: Mutex::new(Vec::new()),
+            notify,
+            listener,
+        };
+    }
+
+    /// Pushes an element to the queue, waking the blocked consumers.
+    ///
+    /// # Panics
+    /// This method panics if the memory allocation for the element fails.
+    #[inline]
+    pub fn push(&self, v: T) {
+        self.queue.push(v);
+        self.notify.notify_all();
+    }
+
+    /// Pops the oldest element of the queue, returning `None` if it's empty.
+    pub fn try_pop(&self) -> Option<T> {
+        // The buffer is left consistent by every operation, so it's safe to use after a panic
+        let mut buffer = self.buffer.lock().unwrap_or_else(PoisonError::into_inner);
+        if buffer.is_empty() {
+            // Chops are LIFO, so the oldest element ends up last
+            buffer.extend(self.queue.chop());
+        }
+        return buffer.pop();
+    }
+
+    /// Pops the oldest element of the queue, blocking the current thread until one is available.
+    pub fn pop_wait(&self) -> T {
+        loop {
+            let mut popped = None;
+            // The notifier is owned by the queue, so `recv_while` only returns once an element was popped
+            self.listener.recv_while(|| {
+                popped = self.try_pop();
+                popped.is_none()
+            });
+
+            if let Some(v) = popped {
+                return v;
+            }
+        }
+    }
+}
+
+impl<T> Default for BlockingQueue<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for BlockingQueue<T> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockingQueue").finish_non_exhaustive()
+    }
+}
+
+// Thanks ChatGPT!
+#[cfg(test)]
+mod tests {
+    use super::BlockingQueue;
+    use std::time::Duration;
+
+    #[test]
+    fn test_try_pop_order() {
+        let queue = BlockingQueue::new();
+        assert_eq!(queue.try_pop(), None);
+
+        queue.push(1);
+        queue.push(2);
+        assert_eq!(queue.try_pop(), Some(1));
+
+        // Elements pushed after a chop are popped after the buffered ones
+        queue.push(3);
+        assert_eq!(queue.try_pop(), Some(2));
+        assert_eq!(queue.try_pop(), Some(3));
+        assert_eq!(queue.try_pop(), None);
+    }
+
+    #[test]
+    fn test_blocked_consumer() {
+        const ITEMS: usize = 1000;
+
+        let queue = BlockingQueue::new();
+        std::thread::scope(|s| {
+            let consumer = s.spawn(|| (0..ITEMS).map(|_| queue.pop_wait()).collect::<Vec<_>>());
+
+            // Let the consumer block on the empty queue first
+            std::thread::sleep(Duration::from_millis(50));
+            (0..ITEMS).for_each(|i| queue.push(i));
+
+            assert_eq!(consumer.join().unwrap(), (0..ITEMS).collect::<Vec<_>>());
+        });
+        assert_eq!(queue.try_pop(), None);
+    }
+}
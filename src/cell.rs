@@ -3,8 +3,12 @@ use alloc::alloc::*;
 #[cfg(feature = "alloc_api")]
 use core::mem::ManuallyDrop;
 
-use alloc::boxed::Box;
-use core::sync::atomic::{AtomicPtr, Ordering};
+use alloc::{boxed::Box, sync::Arc};
+use core::ops::{Bound, RangeBounds};
+use crate::sync::{
+    atomic::{AtomicPtr, Ordering},
+    get_mut_ptr,
+};
 use docfg::docfg;
 
 /// An atomic cell that can be safely shared between threads and can contain an optional value.
@@ -30,13 +34,47 @@ use docfg::docfg;
 /// // Check that the value was replaced
 /// assert_eq!(atomic_cell.get_mut().copied(), Some(24));
 /// ```
+///
+/// # Unsized values
+///
+/// The value is stored behind an [`AtomicPtr`], which can only hold thin pointers, so `T` must be [`Sized`].
+/// Unsized values (like trait objects or slices) can still be stored by boxing them first, with the cell holding a thin pointer to their (fat) [`Box`].
+///
+/// ```rust
+/// use utils_atomics::AtomicCell;
+///
+/// let atomic_cell = AtomicCell::<Box<dyn Fn() -> i32 + Send>>::new(Some(Box::new(|| 42) as Box<_>));
+/// let f = atomic_cell.take().unwrap();
+/// assert_eq!(f(), 42);
+/// ```
+///
+/// # Layout
+///
+/// Without the `alloc_api` feature, `AtomicCell<T>` is `#[repr(transparent)]` over an [`AtomicPtr<T>`], which is either null
+/// or points to a value allocated by [`Box`]. This makes it safe to use inside FFI structs, and to [`transmute`](core::mem::transmute) it
+/// to and from an [`AtomicPtr<T>`] or `*mut T` that follows the same rules.
+///
+/// ```rust
+/// use utils_atomics::AtomicCell;
+/// use core::sync::atomic::AtomicPtr;
+///
+/// let ptr = AtomicPtr::new(Box::into_raw(Box::new(42)));
+/// let atomic_cell = unsafe { core::mem::transmute::<AtomicPtr<i32>, AtomicCell<i32>>(ptr) };
+/// assert_eq!(atomic_cell.take(), Some(42));
+/// ```
 #[derive(Debug)]
+#[cfg_attr(not(feature = "alloc_api"), repr(transparent))]
 pub struct AtomicCell<T, #[cfg(feature = "alloc_api")] A: Allocator = Global> {
     inner: AtomicPtr<T>,
     #[cfg(feature = "alloc_api")]
     alloc: ManuallyDrop<A>,
 }
 
+#[cfg(not(any(loom, feature = "alloc_api")))]
+static_assertions::assert_eq_size!(AtomicCell<u64>, AtomicPtr<u64>, *mut u64);
+#[cfg(not(any(loom, feature = "alloc_api")))]
+static_assertions::assert_eq_align!(AtomicCell<u64>, AtomicPtr<u64>, *mut u64);
+
 #[docfg(feature = "alloc_api")]
 impl<T, A: Allocator> AtomicCell<T, A> {
     /// Constructs a new `AtomicCell` containing an optional value t and an allocator alloc.
@@ -156,7 +194,7 @@ impl<T, A: Allocator> AtomicCell<T, A> {
     #[inline]
     pub fn replace_in(&self, new: impl Into<Option<T>>) -> Option<Box<T, &A>> {
         let new = match new.into() {
-            Some(new) => Box::into_raw(Box::new_in(new, core::ops::Deref::deref(&self.alloc))),
+            Some(new) => Box::into_raw_with_allocator(Box::new_in(new, core::ops::Deref::deref(&self.alloc))).0,
             None => core::ptr::null_mut(),
         };
 
@@ -224,6 +262,7 @@ impl<T> AtomicCell<T> {
     /// let old_value = atomic_cell.replace(Some(24));
     /// ```
     #[inline]
+    #[must_use]
     pub fn replace(&self, new: impl Into<Option<T>>) -> Option<T> {
         self.replace_boxed(new.into().map(Box::new)).map(|x| *x)
     }
@@ -256,6 +295,124 @@ impl<T> AtomicCell<T> {
         return unsafe { Some(Box::from_raw(prev)) };
     }
 
+    /// Stores `v` inside the `AtomicCell` only if it's currently empty, with a single compare-and-swap.
+    /// If the `AtomicCell` already contains a value, it's left untouched and `v` is returned back.
+    ///
+    /// # Errors
+    /// This method returns `Err(v)` if the `AtomicCell` wasn't empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use utils_atomics::AtomicCell;
+    ///
+    /// let atomic_cell = AtomicCell::<i32>::new(None);
+    /// assert_eq!(atomic_cell.replace_if_none(1), Ok(()));
+    /// assert_eq!(atomic_cell.replace_if_none(2), Err(2));
+    /// assert_eq!(atomic_cell.take(), Some(1));
+    /// ```
+    pub fn replace_if_none(&self, v: T) -> Result<(), T> {
+        let new = Box::into_raw(Box::new(v));
+        return match self.inner.compare_exchange(
+            core::ptr::null_mut(),
+            new,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => Ok(()),
+            // The box was never published, so we still own it
+            Err(_) => Err(*unsafe { Box::from_raw(new) }),
+        };
+    }
+
+    /// Returns a pointer to the value currently inside the `AtomicCell`, or a null pointer if it's empty.
+    ///
+    /// The pointer is only meant to be compared against, like with [`compare_exchange`](AtomicCell::compare_exchange).
+    /// Another thread may take and drop the value at any time, so dereferencing it is unsound.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.inner.load(Ordering::Acquire)
+    }
+
+    /// Stores `new` inside the `AtomicCell` if it's current value is the one pointed by `current` (compared by pointer identity),
+    /// returning the previous value.
+    ///
+    /// A null `current` pointer matches an empty cell.
+    ///
+    /// # Errors
+    /// This method returns `new` back if the `AtomicCell` didn't contain the value pointed by `current`.
+    ///
+    /// # ABA
+    /// Values are compared by their address, so if the value pointed by `current` is dropped and a new one is allocated at the same address,
+    /// the exchange will succeed even though the value has changed. Use a [`TaggedCell`](crate::TaggedCell) when this matters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use utils_atomics::AtomicCell;
+    ///
+    /// let atomic_cell = AtomicCell::<i32>::new(Some(1));
+    /// let current = atomic_cell.as_ptr();
+    ///
+    /// let prev = atomic_cell.compare_exchange(current, Some(Box::new(2))).unwrap();
+    /// assert_eq!(prev.as_deref(), Some(&1));
+    ///
+    /// // The cell no longer holds the value pointed by `current`
+    /// assert_eq!(atomic_cell.compare_exchange(core::ptr::null(), Some(Box::new(3))).unwrap_err().as_deref(), Some(&3));
+    /// assert_eq!(atomic_cell.take(), Some(2));
+    /// ```
+    #[inline]
+    pub fn compare_exchange(&self, current: *const T, new: Option<Box<T>>) -> Result<Option<Box<T>>, Option<Box<T>>> {
+        let new = new.map_or(core::ptr::null_mut(), Box::into_raw);
+        let res = self.inner.compare_exchange(current.cast_mut(), new, Ordering::AcqRel, Ordering::Acquire);
+        return Self::exchange_result(res, new);
+    }
+
+    /// Stores `new` inside the `AtomicCell` if it's current value is the one pointed by `current` (compared by pointer identity),
+    /// returning the previous value.
+    ///
+    /// Unlike [`compare_exchange`](AtomicCell::compare_exchange), this method is allowed to spuriously fail even when the comparison succeeds,
+    /// which can result in more efficient code on some platforms (like the ones based on LL/SC). It should be called in a loop,
+    /// reloading `current` (and reusing the returned `new`) on every failure.
+    ///
+    /// # Errors
+    /// This method returns `new` back if the `AtomicCell` didn't contain the value pointed by `current`, or if the exchange failed spuriously.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use utils_atomics::AtomicCell;
+    ///
+    /// let atomic_cell = AtomicCell::<i32>::new(Some(1));
+    ///
+    /// let mut new = Some(Box::new(2));
+    /// let prev = loop {
+    ///     match atomic_cell.compare_exchange_weak(atomic_cell.as_ptr(), new) {
+    ///         Ok(prev) => break prev,
+    ///         Err(back) => new = back,
+    ///     }
+    /// };
+    ///
+    /// assert_eq!(prev.as_deref(), Some(&1));
+    /// assert_eq!(atomic_cell.take(), Some(2));
+    /// ```
+    #[inline]
+    pub fn compare_exchange_weak(&self, current: *const T, new: Option<Box<T>>) -> Result<Option<Box<T>>, Option<Box<T>>> {
+        let new = new.map_or(core::ptr::null_mut(), Box::into_raw);
+        let res = self.inner.compare_exchange_weak(current.cast_mut(), new, Ordering::AcqRel, Ordering::Acquire);
+        return Self::exchange_result(res, new);
+    }
+
+    #[inline]
+    fn exchange_result(res: Result<*mut T, *mut T>, new: *mut T) -> Result<Option<Box<T>>, Option<Box<T>>> {
+        return match res {
+            // We now own the previous value
+            Ok(prev) => Ok((!prev.is_null()).then(|| unsafe { Box::from_raw(prev) })),
+            // The box was never published, so we still own it
+            Err(_) => Err((!new.is_null()).then(|| unsafe { Box::from_raw(new) })),
+        };
+    }
+
     /// Takes the value out of the `AtomicCell`, leaving it empty.
     /// Returns an optional boxed value. If the `AtomicCell` is empty, returns `None`.
     ///
@@ -268,9 +425,104 @@ impl<T> AtomicCell<T> {
     /// let taken_value = atomic_cell.take_boxed();
     /// ```
     #[inline]
+    #[must_use]
     pub fn take_boxed(&self) -> Option<Box<T>> {
         self.replace_boxed(None)
     }
+
+    /// Takes the boxed value out of the `AtomicCell`, hands it's ownership to `f`, and stores the box returned by `f`.
+    ///
+    /// The value is swapped out before `f` is called and the result swapped in afterwards, so other threads will see the cell
+    /// as empty while `f` runs. If another thread stores a value in between both swaps, it will be replaced (and dropped)
+    /// by the value returned from `f`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use utils_atomics::AtomicCell;
+    ///
+    /// let atomic_cell = AtomicCell::<Vec<i32>>::new(vec![1, 2]);
+    /// atomic_cell.replace_map(|prev| {
+    ///     let mut prev = prev?;
+    ///     prev.push(3);
+    ///     Some(prev)
+    /// });
+    /// assert_eq!(atomic_cell.take(), Some(vec![1, 2, 3]));
+    /// ```
+    #[inline]
+    pub fn replace_map<F: FnOnce(Option<Box<T>>) -> Option<Box<T>>>(&self, f: F) {
+        let new = f(self.take_boxed());
+        let _: Option<Box<T>> = self.replace_boxed(new);
+    }
+
+    /// Takes the value out of the `AtomicCell`, leaving it empty.
+    /// If the `AtomicCell` is empty, returns the result of `f` instead, without storing it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use utils_atomics::AtomicCell;
+    ///
+    /// let atomic_cell = AtomicCell::<i32>::new(Some(42));
+    /// assert_eq!(atomic_cell.take_or_else(|| 24), 42);
+    /// assert_eq!(atomic_cell.take_or_else(|| 24), 24);
+    /// assert!(atomic_cell.is_none());
+    /// ```
+    #[inline]
+    pub fn take_or_else<F: FnOnce() -> T>(&self, f: F) -> T {
+        match self.take_boxed() {
+            Some(x) => *x,
+            None => f(),
+        }
+    }
+
+    /// Takes the value out of the `AtomicCell`, leaving it empty.
+    /// If the `AtomicCell` is empty, returns the default value of `T` instead, without storing it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use utils_atomics::AtomicCell;
+    ///
+    /// let atomic_cell = AtomicCell::<i32>::new(Some(42));
+    /// assert_eq!(atomic_cell.take_or_default(), 42);
+    /// assert_eq!(atomic_cell.take_or_default(), 0);
+    /// ```
+    #[inline]
+    pub fn take_or_default(&self) -> T
+    where
+        T: Default,
+    {
+        self.take_or_else(T::default)
+    }
+
+    /// Replaces the value inside the `AtomicCell` with a new optional value `new`, returning a guard holding the old one.
+    ///
+    /// Unless [`commit`](RestoreGuard::commit) is called, dropping the guard (including while unwinding from a panic) stores
+    /// the old value back into the cell, undoing the replacement. The restore unconditionally overwrites the cell,
+    /// so any value stored in it by other threads in the meantime will be dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use utils_atomics::AtomicCell;
+    ///
+    /// let atomic_cell = AtomicCell::<i32>::new(Some(1));
+    ///
+    /// let guard = atomic_cell.replace_guarded(Some(2));
+    /// assert_eq!(guard.old(), Some(&1));
+    /// drop(guard);
+    /// assert_eq!(atomic_cell.take(), Some(1));
+    ///
+    /// let guard = atomic_cell.replace_guarded(Some(3));
+    /// assert_eq!(guard.commit(), None);
+    /// assert_eq!(atomic_cell.take(), Some(3));
+    /// ```
+    #[inline]
+    pub fn replace_guarded(&self, new: impl Into<Option<T>>) -> RestoreGuard<'_, T> {
+        let old = self.replace_boxed(new.into().map(Box::new));
+        return RestoreGuard { cell: self, old };
+    }
 }
 
 cfg_if::cfg_if! {
@@ -289,6 +541,7 @@ cfg_if::cfg_if! {
             /// assert_eq!(atomic_cell.take(), None);
             /// ```
             #[inline]
+            #[must_use]
             pub fn take(&self) -> Option<T> {
                 self.take_in().map(|x| *x)
             }
@@ -308,11 +561,34 @@ cfg_if::cfg_if! {
             /// ```
             #[inline]
             pub fn get_mut (&mut self) -> Option<&mut T> {
-                let ptr = *self.inner.get_mut();
+                let ptr = get_mut_ptr(&mut self.inner);
                 if ptr.is_null() { return None }
                 return unsafe { Some(&mut *ptr) }
             }
 
+            /// Returns a mutable reference to the value inside the `AtomicCell`, inserting `v` first if it's empty.
+            /// If the `AtomicCell` already contains a value, `v` is dropped.
+            ///
+            /// This is a non-atomic operation, made safe by the mutable reference.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use utils_atomics::AtomicCell;
+            ///
+            /// let mut atomic_cell = AtomicCell::new(None);
+            /// *atomic_cell.get_mut_or_insert(1) += 1;
+            /// assert_eq!(atomic_cell.get_mut_or_insert(5), &mut 2);
+            /// ```
+            pub fn get_mut_or_insert (&mut self, v: T) -> &mut T {
+                let mut ptr = get_mut_ptr(&mut self.inner);
+                if ptr.is_null() {
+                    ptr = Box::into_raw_with_allocator(Box::new_in(v, &*self.alloc)).0;
+                    self.inner.store(ptr, Ordering::Relaxed);
+                }
+                return unsafe { &mut *ptr }
+            }
+
             /// Returns `true` if the `AtomicCell` contains a value.
             ///
             /// # Examples
@@ -347,7 +623,7 @@ cfg_if::cfg_if! {
         impl<T, A: Allocator> Drop for AtomicCell<T, A> {
             fn drop(&mut self) {
                 unsafe {
-                    let ptr = *self.inner.get_mut();
+                    let ptr = get_mut_ptr(&mut self.inner);
                     if ptr.is_null() {
                         ManuallyDrop::drop(&mut self.alloc);
                     } else {
@@ -374,6 +650,7 @@ cfg_if::cfg_if! {
             /// assert_eq!(atomic_cell.take(), None);
             /// ```
             #[inline]
+            #[must_use]
             pub fn take(&self) -> Option<T> {
                 self.take_boxed().map(|x| *x)
             }
@@ -393,11 +670,34 @@ cfg_if::cfg_if! {
             /// ```
             #[inline]
             pub fn get_mut (&mut self) -> Option<&mut T> {
-                let ptr = *self.inner.get_mut();
+                let ptr = get_mut_ptr(&mut self.inner);
                 if ptr.is_null() { return None }
                 return unsafe { Some(&mut *ptr) }
             }
 
+            /// Returns a mutable reference to the value inside the `AtomicCell`, inserting `v` first if it's empty.
+            /// If the `AtomicCell` already contains a value, `v` is dropped.
+            ///
+            /// This is a non-atomic operation, made safe by the mutable reference.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use utils_atomics::AtomicCell;
+            ///
+            /// let mut atomic_cell = AtomicCell::new(None);
+            /// *atomic_cell.get_mut_or_insert(1) += 1;
+            /// assert_eq!(atomic_cell.get_mut_or_insert(5), &mut 2);
+            /// ```
+            pub fn get_mut_or_insert (&mut self, v: T) -> &mut T {
+                let mut ptr = get_mut_ptr(&mut self.inner);
+                if ptr.is_null() {
+                    ptr = Box::into_raw(Box::new(v));
+                    self.inner.store(ptr, Ordering::Relaxed);
+                }
+                return unsafe { &mut *ptr }
+            }
+
             /// Returns `true` if the `AtomicCell` contains a value.
             ///
             /// # Examples
@@ -432,7 +732,7 @@ cfg_if::cfg_if! {
         impl<T> Drop for AtomicCell<T> {
             fn drop(&mut self) {
                 unsafe {
-                    let ptr = *self.inner.get_mut();
+                    let ptr = get_mut_ptr(&mut self.inner);
                     if !ptr.is_null() {
                         let _: Box<T> = Box::from_raw(ptr);
                     }
@@ -445,10 +745,404 @@ cfg_if::cfg_if! {
     }
 }
 
+/// Guard returned by [`AtomicCell::replace_guarded`], that restores the replaced value when dropped without being committed.
+#[must_use = "dropping the guard immediately restores the replaced value"]
+#[derive(Debug)]
+pub struct RestoreGuard<'a, T> {
+    cell: &'a AtomicCell<T>,
+    old: Option<Box<T>>,
+}
+
+impl<T> RestoreGuard<'_, T> {
+    /// Returns a reference to the replaced value, if any.
+    #[inline]
+    pub fn old(&self) -> Option<&T> {
+        return self.old.as_deref();
+    }
+
+    /// Keeps the replacement, returning the replaced value.
+    #[inline]
+    pub fn commit(self) -> Option<T> {
+        let mut this = core::mem::ManuallyDrop::new(self);
+        return this.old.take().map(|x| *x);
+    }
+}
+
+impl<T> Drop for RestoreGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        let _: Option<Box<T>> = self.cell.replace_boxed(self.old.take());
+    }
+}
+
+/// A reference-counted [`AtomicCell`], that can be cloned to share the same cell between threads.
+///
+/// Cloning a `SharedCell` only increments it's reference count, so all clones observe and modify the same value.
+///
+/// # Example
+///
+/// ```rust
+/// use utils_atomics::SharedCell;
+///
+/// let cell = SharedCell::new(Some(1));
+/// let other = cell.clone();
+///
+/// std::thread::spawn(move || {
+///     other.replace(Some(2));
+/// })
+/// .join()
+/// .unwrap();
+///
+/// assert_eq!(cell.take(), Some(2));
+/// ```
+#[derive(Debug)]
+pub struct SharedCell<T> {
+    inner: Arc<AtomicCell<T>>,
+}
+
+impl<T> SharedCell<T> {
+    /// Constructs a new `SharedCell` containing an optional value `t`.
+    #[inline]
+    pub fn new(t: impl Into<Option<T>>) -> Self {
+        Self::from(AtomicCell::new(t))
+    }
+
+    /// Constructs a new `SharedCell` from an optional boxed value `t`.
+    #[inline]
+    pub fn new_boxed(t: impl Into<Option<Box<T>>>) -> Self {
+        Self::from(AtomicCell::new_boxed(t))
+    }
+
+    /// Replaces the value inside the cell with a new optional value `new`.
+    /// Returns the old value as an optional value. If the cell was empty, returns `None`.
+    #[inline]
+    #[must_use]
+    pub fn replace(&self, new: impl Into<Option<T>>) -> Option<T> {
+        self.inner.replace(new)
+    }
+
+    /// Replaces the value inside the cell with a new optional boxed value `new`.
+    /// Returns the old value as an optional boxed value. If the cell was empty, returns `None`.
+    #[inline]
+    pub fn replace_boxed(&self, new: impl Into<Option<Box<T>>>) -> Option<Box<T>> {
+        self.inner.replace_boxed(new)
+    }
+
+    /// Takes the value out of the cell, leaving it empty.
+    /// Returns an optional value. If the cell is empty, returns `None`.
+    #[inline]
+    #[must_use]
+    pub fn take(&self) -> Option<T> {
+        self.inner.take()
+    }
+
+    /// Takes the value out of the cell, leaving it empty.
+    /// Returns an optional boxed value. If the cell is empty, returns `None`.
+    #[inline]
+    #[must_use]
+    pub fn take_boxed(&self) -> Option<Box<T>> {
+        self.inner.take_boxed()
+    }
+
+    /// Returns `true` if the cell contains a value.
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Returns `true` if the cell is empty.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    /// Returns `true` if both `SharedCell`s point to the same cell
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.inner, &other.inner)
+    }
+
+    /// Returns the inner [`AtomicCell`] if this is it's only reference.
+    ///
+    /// # Errors
+    /// If other clones of this `SharedCell` exist, the `SharedCell` is returned back.
+    #[inline]
+    pub fn try_unwrap(this: Self) -> Result<AtomicCell<T>, Self> {
+        Arc::try_unwrap(this.inner).map_err(|inner| Self { inner })
+    }
+}
+
+impl<T> Clone for SharedCell<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Default for SharedCell<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl<T> From<AtomicCell<T>> for SharedCell<T> {
+    #[inline]
+    fn from(cell: AtomicCell<T>) -> Self {
+        Self {
+            inner: Arc::new(cell),
+        }
+    }
+}
+
+impl<T> AsRef<AtomicCell<T>> for SharedCell<T> {
+    #[inline]
+    fn as_ref(&self) -> &AtomicCell<T> {
+        &self.inner
+    }
+}
+
+/// Takes the values of the cells of `cells` inside `range`, yielding them alongside their index in `cells`.
+///
+/// Every cell is taken atomically, so threads may call this function on the same slice concurrently, and no value is ever taken twice.
+/// Splitting the slice into disjoint ranges (one per thread) avoids the threads contending over the same cells,
+/// which makes this a simple building block for parallel scatter-gather collection.
+///
+/// # Panics
+/// This function panics if `range` is out of the bounds of `cells`.
+///
+/// # Example
+///
+/// ```rust
+/// use utils_atomics::{take_all, AtomicCell};
+///
+/// let cells = (0..8).map(|i| AtomicCell::<i32>::new(Some(i))).collect::<Vec<_>>();
+///
+/// let taken = std::thread::scope(|s| {
+///     let low = s.spawn(|| take_all(&cells, ..4).map(|(_, v)| v).sum::<i32>());
+///     let high = s.spawn(|| take_all(&cells, 4..).map(|(_, v)| v).sum::<i32>());
+///     low.join().unwrap() + high.join().unwrap()
+/// });
+///
+/// assert_eq!(taken, (0..8).sum());
+/// assert!(cells.iter().all(AtomicCell::is_none));
+/// ```
+pub fn take_all<T, R: RangeBounds<usize>>(cells: &[AtomicCell<T>], range: R) -> impl '_ + Iterator<Item = (usize, T)> {
+    let start = match range.start_bound() {
+        Bound::Included(&x) => x,
+        Bound::Excluded(&x) => x + 1,
+        Bound::Unbounded => 0,
+    };
+    let cells = &cells[(range.start_bound().cloned(), range.end_bound().cloned())];
+
+    return cells
+        .iter()
+        .enumerate()
+        .filter_map(move |(i, cell)| Some((start + i, cell.take()?)));
+}
+
+/// A fixed-size table of `N` optional values, each of them stored in it's own atomic slot.
+///
+/// Every slot behaves like an [`AtomicCell`], but the slots are stored inline, so the table itself needs no extra allocation.
+///
+/// # Example
+///
+/// ```rust
+/// use utils_atomics::AtomicCellArray;
+///
+/// let slots = AtomicCellArray::<usize, 4>::new();
+///
+/// std::thread::scope(|s| {
+///     for i in 0..4 {
+///         let slots = &slots;
+///         s.spawn(move || slots.replace(i, Some(i)));
+///     }
+/// });
+///
+/// let values = slots.drain().collect::<Vec<_>>();
+/// assert_eq!(values, [(0, 0), (1, 1), (2, 2), (3, 3)]);
+/// ```
+#[derive(Debug)]
+pub struct AtomicCellArray<T, const N: usize> {
+    inner: [AtomicPtr<T>; N],
+}
+
+impl<T, const N: usize> AtomicCellArray<T, N> {
+    /// Constructs a new `AtomicCellArray` with every slot empty.
+    #[inline]
+    pub fn new() -> Self {
+        return Self {
+            inner: core::array::from_fn(|_| AtomicPtr::new(core::ptr::null_mut())),
+        };
+    }
+
+    /// Constructs a new `AtomicCellArray` with the specified optional values.
+    #[inline]
+    pub fn from_array(values: [Option<T>; N]) -> Self {
+        let mut values = values.into_iter();
+        return Self {
+            inner: core::array::from_fn(|_| {
+                AtomicPtr::new(match values.next().flatten() {
+                    Some(x) => Box::into_raw(Box::new(x)),
+                    None => core::ptr::null_mut(),
+                })
+            }),
+        };
+    }
+
+    /// Returns the number of slots of the table.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the table has no slots.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Replaces the value of the `i`-th slot with a new optional value `new`.
+    /// Returns the old value as an optional value. If the slot was empty, returns `None`.
+    ///
+    /// # Panics
+    /// This method panics if `i` is out of bounds.
+    #[inline]
+    #[must_use]
+    pub fn replace(&self, i: usize, new: impl Into<Option<T>>) -> Option<T> {
+        self.replace_boxed(i, new.into().map(Box::new)).map(|x| *x)
+    }
+
+    /// Replaces the value of the `i`-th slot with a new optional boxed value `new`.
+    /// Returns the old value as an optional boxed value. If the slot was empty, returns `None`.
+    ///
+    /// # Panics
+    /// This method panics if `i` is out of bounds.
+    #[inline]
+    pub fn replace_boxed(&self, i: usize, new: impl Into<Option<Box<T>>>) -> Option<Box<T>> {
+        let new = match new.into() {
+            Some(new) => Box::into_raw(new),
+            None => core::ptr::null_mut(),
+        };
+
+        let prev = self.inner[i].swap(new, Ordering::AcqRel);
+        if prev.is_null() {
+            return None;
+        }
+        return unsafe { Some(Box::from_raw(prev)) };
+    }
+
+    /// Takes the value out of the `i`-th slot, leaving it empty.
+    /// Returns an optional value. If the slot is empty, returns `None`.
+    ///
+    /// # Panics
+    /// This method panics if `i` is out of bounds.
+    #[inline]
+    #[must_use]
+    pub fn take(&self, i: usize) -> Option<T> {
+        self.replace(i, None)
+    }
+
+    /// Takes the value out of the `i`-th slot, leaving it empty.
+    /// Returns an optional boxed value. If the slot is empty, returns `None`.
+    ///
+    /// # Panics
+    /// This method panics if `i` is out of bounds.
+    #[inline]
+    #[must_use]
+    pub fn take_boxed(&self, i: usize) -> Option<Box<T>> {
+        self.replace_boxed(i, None)
+    }
+
+    /// Returns `true` if the `i`-th slot contains a value.
+    ///
+    /// # Panics
+    /// This method panics if `i` is out of bounds.
+    #[inline]
+    pub fn is_some(&self, i: usize) -> bool {
+        !self.is_none(i)
+    }
+
+    /// Returns `true` if the `i`-th slot is empty.
+    ///
+    /// # Panics
+    /// This method panics if `i` is out of bounds.
+    #[inline]
+    pub fn is_none(&self, i: usize) -> bool {
+        self.inner[i].load(Ordering::Relaxed).is_null()
+    }
+
+    /// Returns a mutable reference to the value of the `i`-th slot, if any.
+    ///
+    /// # Panics
+    /// This method panics if `i` is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        let ptr = get_mut_ptr(&mut self.inner[i]);
+        if ptr.is_null() {
+            return None;
+        }
+        return unsafe { Some(&mut *ptr) };
+    }
+
+    /// Returns an iterator over mutable references to the values of every slot.
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = Option<&mut T>> {
+        self.inner.iter_mut().map(|x| {
+            let ptr = get_mut_ptr(x);
+            return match ptr.is_null() {
+                true => None,
+                false => unsafe { Some(&mut *ptr) },
+            };
+        })
+    }
+
+    /// Returns an iterator that takes the values out of the occupied slots, alongside their indices.
+    ///
+    /// Each slot is taken atomically as the iterator reaches it, so values stored in a slot that was already visited won't be yielded.
+    #[inline]
+    pub fn drain(&self) -> impl '_ + Iterator<Item = (usize, T)> {
+        (0..N).filter_map(|i| Some((i, self.take(i)?)))
+    }
+}
+
+impl<T, const N: usize> Default for AtomicCellArray<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> From<[Option<T>; N]> for AtomicCellArray<T, N> {
+    #[inline]
+    fn from(values: [Option<T>; N]) -> Self {
+        Self::from_array(values)
+    }
+}
+
+impl<T, const N: usize> Drop for AtomicCellArray<T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        for slot in &mut self.inner {
+            let ptr = get_mut_ptr(slot);
+            if !ptr.is_null() {
+                unsafe { drop(Box::from_raw(ptr)) }
+            }
+        }
+    }
+}
+
+unsafe impl<T: Send, const N: usize> Send for AtomicCellArray<T, N> {}
+unsafe impl<T: Send, const N: usize> Sync for AtomicCellArray<T, N> {}
+
 // Thanks ChatGPT!
 #[cfg(test)]
 mod tests {
     use super::AtomicCell;
+    use super::AtomicCellArray;
+    use super::SharedCell;
 
     #[test]
     fn create_and_take() {
@@ -464,6 +1158,65 @@ mod tests {
         assert_eq!(cell.take(), None);
     }
 
+    #[test]
+    fn unsized_values() {
+        use alloc::boxed::Box;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        let cell = AtomicCell::<Box<dyn Fn() -> usize + Send + Sync>>::new(None);
+        assert!(cell.replace(Some(Box::new(|| CALLS.fetch_add(1, Ordering::Relaxed)) as Box<_>)).is_none());
+
+        let f = cell.take().unwrap();
+        assert_eq!(f(), 0);
+        assert_eq!(f(), 1);
+        assert!(cell.is_none());
+
+        let cell = AtomicCell::<Box<[u8]>>::new(Some(Box::from(&[1, 2, 3][..])));
+        assert_eq!(cell.take().as_deref(), Some(&[1, 2, 3][..]));
+    }
+
+    #[test]
+    fn replace_guarded_commit() {
+        let cell = AtomicCell::<i32>::new(Some(1));
+        let guard = cell.replace_guarded(Some(2));
+        assert_eq!(guard.old(), Some(&1));
+        assert_eq!(guard.commit(), Some(1));
+        assert_eq!(cell.take(), Some(2));
+
+        let guard = cell.replace_guarded(Some(3));
+        assert_eq!(guard.old(), None);
+        assert_eq!(guard.commit(), None);
+        assert_eq!(cell.take(), Some(3));
+    }
+
+    #[test]
+    fn replace_guarded_rollback() {
+        let cell = AtomicCell::<i32>::new(Some(1));
+        drop(cell.replace_guarded(Some(2)));
+        assert_eq!(cell.take(), Some(1));
+
+        // Values stored concurrently are overwritten by the restore
+        let guard = cell.replace_guarded(Some(2));
+        let _ = cell.replace(Some(3));
+        drop(guard);
+        assert_eq!(cell.take(), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn replace_guarded_panic() {
+        let cell = AtomicCell::<i32>::new(Some(1));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = cell.replace_guarded(Some(2));
+            panic!("update failed");
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(cell.take(), Some(1));
+    }
+
     #[test]
     fn replace() {
         let cell = AtomicCell::<i32>::new(Some(42));
@@ -480,23 +1233,267 @@ mod tests {
         assert!(cell.is_none());
     }
 
+    #[test]
+    fn replace_map() {
+        let cell = AtomicCell::<alloc::vec::Vec<i32>>::new(alloc::vec![1, 2]);
+        cell.replace_map(|prev| {
+            let mut prev = prev.unwrap();
+            prev.push(3);
+            Some(prev)
+        });
+        assert_eq!(cell.take(), Some(alloc::vec![1, 2, 3]));
+
+        cell.replace_map(|prev| {
+            assert!(prev.is_none());
+            None
+        });
+        assert!(cell.is_none());
+    }
+
+    #[test]
+    fn cell_array_fill_and_drain() {
+        let mut cells = AtomicCellArray::<usize, 8>::new();
+        assert_eq!(cells.len(), 8);
+        assert!((0..8).all(|i| cells.is_none(i)));
+
+        for i in (0..8).step_by(2) {
+            assert_eq!(cells.replace(i, Some(i)), None);
+        }
+        assert_eq!(cells.replace(2, Some(20)), Some(2));
+        assert_eq!(cells.take(1), None);
+        assert_eq!(cells.take(4), Some(4));
+
+        if let Some(x) = cells.get_mut(6) {
+            *x += 1;
+        }
+        assert_eq!(cells.iter_mut().flatten().map(|x| *x).collect::<alloc::vec::Vec<_>>(), [0, 20, 7]);
+
+        let drained = cells.drain().collect::<alloc::vec::Vec<_>>();
+        assert_eq!(drained, [(0, 0), (2, 20), (6, 7)]);
+        assert!((0..8).all(|i| cells.is_none(i)));
+    }
+
+    #[test]
+    fn cell_array_drops_occupied() {
+        use alloc::sync::Arc;
+
+        let value = Arc::new(());
+        let cells = AtomicCellArray::from([Some(value.clone()), None, Some(value.clone())]);
+        let _ = cells.replace(1, value.clone());
+        assert_eq!(Arc::strong_count(&value), 4);
+
+        drop(cells.take(0));
+        assert_eq!(Arc::strong_count(&value), 3);
+        drop(cells);
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn get_mut_or_insert() {
+        let mut cell = AtomicCell::new(None);
+        assert_eq!(cell.get_mut_or_insert(1), &mut 1);
+        assert!(cell.is_some());
+
+        // The existing value is kept, and the new one is discarded
+        *cell.get_mut_or_insert(2) += 10;
+        assert_eq!(cell.get_mut_or_insert(3), &mut 11);
+        assert_eq!(cell.take(), Some(11));
+
+        let mut cell = AtomicCell::<alloc::string::String>::new(None);
+        cell.get_mut_or_insert(alloc::string::String::new()).push_str("hello");
+        assert_eq!(cell.take().as_deref(), Some("hello"));
+    }
+
+    #[test]
+    fn compare_exchange() {
+        use alloc::boxed::Box;
+
+        let cell = AtomicCell::<i32>::new(None);
+        assert!(cell.as_ptr().is_null());
+        assert_eq!(cell.compare_exchange(core::ptr::null(), Some(Box::new(1))), Ok(None));
+
+        let current = cell.as_ptr();
+        let rejected = cell.compare_exchange(core::ptr::null(), Some(Box::new(2))).unwrap_err();
+        assert_eq!(rejected.as_deref(), Some(&2));
+        assert_eq!(cell.compare_exchange(current, None), Ok(Some(Box::new(1))));
+        assert!(cell.is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn compare_exchange_weak_contended() {
+        use alloc::boxed::Box;
+
+        const THREADS: u64 = 8;
+        const SWAPS: u64 = 1000;
+
+        let cell = AtomicCell::<u64>::new(Some(0));
+        let replaced = std::thread::scope(|s| {
+            let handles = (1..=THREADS)
+                .map(|t| {
+                    let cell = &cell;
+                    s.spawn(move || {
+                        let mut replaced = 0;
+                        for i in 0..SWAPS {
+                            let mut new = Some(Box::new(t * SWAPS + i));
+                            // Only the address is compared, so the value is never read without owning it
+                            let prev = loop {
+                                match cell.compare_exchange_weak(cell.as_ptr(), new) {
+                                    Ok(prev) => break prev,
+                                    Err(back) => new = back,
+                                }
+                            };
+                            replaced += *prev.unwrap();
+                        }
+                        replaced
+                    })
+                })
+                .collect::<alloc::vec::Vec<_>>();
+
+            handles.into_iter().map(|x| x.join().unwrap()).sum::<u64>()
+        });
+
+        // Every stored value was replaced exactly once, except for the last one
+        let stored = (1..=THREADS).flat_map(|t| (0..SWAPS).map(move |i| t * SWAPS + i)).sum::<u64>();
+        assert_eq!(replaced + cell.take().unwrap(), stored);
+    }
+
+    #[test]
+    fn replace_if_none() {
+        use alloc::sync::Arc;
+
+        let value = Arc::new(1);
+        let cell = AtomicCell::new(None);
+        assert!(cell.replace_if_none(value.clone()).is_ok());
+        assert_eq!(Arc::strong_count(&value), 2);
+
+        // The rejected value is handed back, without being leaked or dropped
+        let other = Arc::new(2);
+        let rejected = cell.replace_if_none(other.clone()).unwrap_err();
+        assert!(Arc::ptr_eq(&rejected, &other));
+        assert_eq!(Arc::strong_count(&other), 2);
+        drop(rejected);
+        assert_eq!(Arc::strong_count(&other), 1);
+
+        assert!(Arc::ptr_eq(&cell.take().unwrap(), &value));
+        assert_eq!(Arc::strong_count(&value), 1);
+        assert!(cell.replace_if_none(other).is_ok());
+        assert!(cell.is_some());
+    }
+
+    #[test]
+    fn take_or_else() {
+        let cell = AtomicCell::<i32>::new(Some(42));
+        assert_eq!(cell.take_or_else(|| unreachable!()), 42);
+        assert_eq!(cell.take_or_else(|| 13), 13);
+        assert!(cell.is_none());
+
+        let _ = cell.replace(7);
+        assert_eq!(cell.take_or_default(), 7);
+        assert_eq!(cell.take_or_default(), 0);
+        assert!(cell.is_none());
+    }
+
+    #[cfg(loom)]
+    #[test]
+    fn loom_replace_and_take() {
+        loom::model(|| {
+            let cell = loom::sync::Arc::new(AtomicCell::<i32>::new(Some(1)));
+            let other = cell.clone();
+            let handle = loom::thread::spawn(move || other.replace(Some(2)));
+
+            let taken = cell.take();
+            let replaced = handle.join().unwrap();
+            let remaining = cell.take();
+
+            // Every value is observed exactly once, regardless of the interleaving
+            let mut values = [taken, replaced, remaining]
+                .into_iter()
+                .flatten()
+                .collect::<alloc::vec::Vec<_>>();
+            values.sort_unstable();
+            assert_eq!(values, [1, 2]);
+        });
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn take_all_disjoint_ranges() {
+        use super::take_all;
+        use alloc::vec::Vec;
+
+        const THREADS: usize = 8;
+        const CELLS: usize = 1003;
+
+        let cells = (0..CELLS).map(|i| AtomicCell::new(Some(i))).collect::<Vec<_>>();
+        let chunk = CELLS.div_ceil(THREADS);
+
+        let mut taken = std::thread::scope(|s| {
+            let handles = (0..THREADS)
+                .map(|t| {
+                    let cells = &cells;
+                    s.spawn(move || {
+                        let range = (t * chunk)..((t + 1) * chunk).min(CELLS);
+                        take_all(cells, range.clone())
+                            .inspect(|&(i, v)| {
+                                assert_eq!(i, v);
+                                assert!(range.contains(&i));
+                            })
+                            .map(|(_, v)| v)
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        // Every value was taken exactly once
+        taken.sort_unstable();
+        assert_eq!(taken, (0..CELLS).collect::<Vec<_>>());
+        assert_eq!(take_all(&cells, ..).count(), 0);
+    }
+
     #[test]
     fn is_some_and_is_none() {
         let cell = AtomicCell::<i32>::new(Some(42));
         assert!(cell.is_some());
         assert!(!cell.is_none());
-        cell.take();
+        let _ = cell.take();
         assert!(!cell.is_some());
         assert!(cell.is_none());
     }
 
+    #[cfg(not(any(loom, feature = "alloc_api")))]
+    #[test]
+    fn transparent_layout() {
+        use alloc::boxed::Box;
+        use core::{mem::transmute, sync::atomic::AtomicPtr};
+
+        assert_eq!(core::mem::size_of::<AtomicCell<[u8; 3]>>(), core::mem::size_of::<*mut [u8; 3]>());
+        assert_eq!(core::mem::align_of::<AtomicCell<[u8; 3]>>(), core::mem::align_of::<*mut [u8; 3]>());
+
+        let cell = unsafe { transmute::<*mut i32, AtomicCell<i32>>(Box::into_raw(Box::new(42))) };
+        assert_eq!(cell.replace(Some(24)), Some(42));
+
+        let ptr = unsafe { transmute::<AtomicCell<i32>, AtomicPtr<i32>>(cell) };
+        let value = unsafe { Box::from_raw(ptr.into_inner()) };
+        assert_eq!(*value, 24);
+
+        let empty = unsafe { transmute::<*mut i32, AtomicCell<i32>>(core::ptr::null_mut()) };
+        assert!(empty.is_none());
+    }
+
     // Tests for custom allocator functionality
     #[cfg(feature = "alloc_api")]
     mod custom_allocator {
         use super::*;
         use alloc::alloc::Global;
         use alloc::alloc::{Allocator, Layout};
-        use core::{alloc::AllocError, ptr::NonNull};
+        use core::{alloc::AllocError, ptr::NonNull, sync::atomic::Ordering};
 
         #[derive(Debug, Clone, Copy)]
         pub struct DummyAllocator;
@@ -540,6 +1537,118 @@ mod tests {
             assert_eq!(old_value, Some(Box::new_in(42, cell.allocator())));
             assert!(cell.is_none());
         }
+
+        #[derive(Debug, Default)]
+        struct Counters {
+            live: core::sync::atomic::AtomicIsize,
+            drops: core::sync::atomic::AtomicUsize,
+        }
+
+        /// Tracks the live allocations made through it, and how many times it has been dropped
+        #[derive(Debug)]
+        struct CountingAllocator<'a>(&'a Counters);
+
+        unsafe impl Allocator for CountingAllocator<'_> {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.0.live.fetch_add(1, Ordering::Relaxed);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                self.0.live.fetch_sub(1, Ordering::Relaxed);
+                Global.deallocate(ptr, layout)
+            }
+        }
+
+        impl Drop for CountingAllocator<'_> {
+            fn drop(&mut self) {
+                self.0.drops.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        // After `take_in` swaps the pointer to null, dropping the cell must only drop the allocator
+        #[test]
+        fn take_then_drop_with_allocator() {
+            let counters = Counters::default();
+            let cell = AtomicCell::<alloc::vec::Vec<i32>, _>::new_in(Some(alloc::vec![1, 2, 3]), CountingAllocator(&counters));
+
+            let taken = cell.take_in().unwrap();
+            assert_eq!(*taken, [1, 2, 3]);
+            drop(taken);
+            assert_eq!(counters.live.load(Ordering::Relaxed), 0);
+            assert!(cell.take_in().is_none());
+
+            drop(cell);
+            assert_eq!(counters.live.load(Ordering::Relaxed), 0);
+            assert_eq!(counters.drops.load(Ordering::Relaxed), 1);
+        }
+
+        // The box returned by `replace_in` and the one left in the cell must each be freed exactly once
+        #[test]
+        fn replace_then_drop_with_allocator() {
+            let counters = Counters::default();
+            let cell = AtomicCell::<alloc::vec::Vec<i32>, _>::new_in(Some(alloc::vec![1]), CountingAllocator(&counters));
+
+            let old = cell.replace_in(Some(alloc::vec![2])).unwrap();
+            assert_eq!(counters.live.load(Ordering::Relaxed), 2);
+            drop(old);
+            assert_eq!(counters.live.load(Ordering::Relaxed), 1);
+
+            drop(cell);
+            assert_eq!(counters.live.load(Ordering::Relaxed), 0);
+            assert_eq!(counters.drops.load(Ordering::Relaxed), 1);
+
+            // Replacing with `None` leaves the cell empty, just like `take_in`
+            let cell = AtomicCell::<alloc::vec::Vec<i32>, _>::new_in(Some(alloc::vec![3]), CountingAllocator(&counters));
+            drop(cell.replace_in(None));
+            drop(cell);
+            assert_eq!(counters.live.load(Ordering::Relaxed), 0);
+            assert_eq!(counters.drops.load(Ordering::Relaxed), 2);
+        }
+    }
+
+    #[test]
+    fn shared_clone() {
+        let cell = SharedCell::new(Some(1));
+        let other = cell.clone();
+        assert!(SharedCell::ptr_eq(&cell, &other));
+
+        assert_eq!(other.replace(Some(2)), Some(1));
+        assert_eq!(cell.take(), Some(2));
+        assert!(other.is_none());
+
+        let other = SharedCell::try_unwrap(other).unwrap_err();
+        drop(cell);
+        assert!(SharedCell::try_unwrap(other).unwrap().is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn shared_across_threads() {
+        const THREADS: usize = 8;
+        const ITERATIONS: usize = 100;
+
+        let cell = SharedCell::new(0usize);
+        let handles = (0..THREADS)
+            .map(|_| {
+                let cell = cell.clone();
+                std::thread::spawn(move || {
+                    let mut done = 0;
+                    while done < ITERATIONS {
+                        // Whoever takes the value is the only one able to put it back
+                        if let Some(v) = cell.take() {
+                            assert_eq!(cell.replace(v + 1), None);
+                            done += 1;
+                        }
+                    }
+                })
+            })
+            .collect::<std::vec::Vec<_>>();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(cell.take(), Some(THREADS * ITERATIONS));
     }
 
     #[cfg(all(feature = "std", miri))]
@@ -1,6 +1,11 @@
 use crate::flag::mpsc::*;
+use crate::locks::{lock, Lock};
 use alloc::sync::{Arc, Weak};
-use core::cell::UnsafeCell;
+use core::{
+    cell::UnsafeCell,
+    fmt::Debug,
+    sync::atomic::{AtomicU8, Ordering},
+};
 use docfg::docfg;
 
 struct Inner<T> {
@@ -40,17 +45,72 @@ impl<T> Sender<T> {
         }
         return Err(t);
     }
+
+    /// Clones `t` and sends it through the channel. If the channel is already closed, the value isn't cloned.
+    #[inline]
+    pub fn send_cloned(self, t: &T)
+    where
+        T: Clone,
+    {
+        if !self.is_closed() {
+            self.send(t.clone());
+        }
+    }
+
+    /// Attempts to send a clone of `t` through the channel, returning `Ok` if successfull, and `Err` with the clone otherwise.
+    ///
+    /// # Errors
+    /// This method returns an error if the channel has already been used or closed.
+    #[inline]
+    pub fn try_send_cloned(self, t: &T) -> Result<(), T>
+    where
+        T: Clone,
+    {
+        return self.try_send(t.clone());
+    }
+
+    /// Returns `true` if the [`Receiver`] has been dropped, in which case sending a value would fail.
+    ///
+    /// This can be used to avoid computing a value that can't be delivered.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        return self.inner.strong_count() == 0;
+    }
 }
 
 impl<T> Receiver<T> {
     /// Blocks the current thread until the value is received.
     /// If [`Sender`] is dropped before it sends the value, this method returns `None`.
+    ///
+    /// The value is moved out of the channel exactly once. To access a large value without moving it, see [`wait_ref`](Receiver::wait_ref).
     #[inline]
     pub fn wait(self) -> Option<T> {
         self.sub.wait();
         return unsafe { &mut *self.inner.v.get() }.take();
     }
 
+    /// Blocks the current thread until the value is received, returning a reference to it instead of moving it out of the channel.
+    /// If [`Sender`] is dropped before it sends the value (or the value has already been received), this method returns `None`.
+    ///
+    /// Once the flag is marked, the sender can no longer write to the channel, so the value can be borrowed in place.
+    /// The mutable reference guarantees the value isn't taken (e.g. by [`wait_timeout`](Receiver::wait_timeout)) while it's borrowed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::channel::once::channel;
+    ///
+    /// let (sender, mut receiver) = channel::<[u64; 1024]>();
+    /// sender.send([7; 1024]);
+    ///
+    /// let value = receiver.wait_ref().unwrap();
+    /// assert!(value.iter().all(|&x| x == 7));
+    /// ```
+    #[inline]
+    pub fn wait_ref(&mut self) -> Option<&T> {
+        self.sub.wait_by_ref();
+        return unsafe { &*self.inner.v.get() }.as_ref();
+    }
+
     /// Blocks the current thread until the value is received.
     /// If [`Sender`] is dropped before it sends the value, this method returns `None`.
     ///
@@ -58,10 +118,90 @@ impl<T> Receiver<T> {
     /// This method returns an error if the wait didn't conclude before the specified duration
     #[docfg(feature = "std")]
     #[inline]
-    pub fn wait_timeout(&self, dur: core::time::Duration) -> Result<Option<T>, crate::Timeout> {
+    pub fn wait_timeout(&self, dur: core::time::Duration) -> Result<Option<T>, crate::TimeoutElapsed> {
         self.sub.wait_timeout(dur)?;
         return Ok(unsafe { &mut *self.inner.v.get() }.take());
     }
+
+    /// Re-arms a completed channel, returning a new [`Sender`] for it. This allows a receiver to be reused across multiple
+    /// exchanges (e.g. when pooling channels) without reallocating the channel's value slot.
+    ///
+    /// The channel's flag is still reallocated on every reset. A flag completes once its last strong reference is dropped,
+    /// and a dropped [`Arc`] can't be revived, so each exchange needs a new [`Flag`].
+    ///
+    /// The channel can only be reset once the previous exchange is fully complete: the [`Sender`] must have sent it's value
+    /// (or been dropped) and every reference to the channel's flag must have been marked. Otherwise, this method returns `None`
+    /// and leaves the channel untouched. A value that was sent but not moved out of the channel is dropped.
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::channel::once::channel;
+    ///
+    /// let (sender, mut receiver) = channel::<i32>();
+    /// assert!(receiver.reset().is_none());
+    ///
+    /// sender.send(1);
+    /// assert_eq!(receiver.wait_ref(), Some(&1));
+    ///
+    /// let sender = receiver.reset().unwrap();
+    /// sender.send(2);
+    /// assert_eq!(receiver.wait(), Some(2));
+    /// ```
+    pub fn reset(&mut self) -> Option<Sender<T>> {
+        if !self.sub.is_marked() {
+            return None;
+        }
+
+        // Fails if the sender is still alive (or is in the middle of sending it's value)
+        let inner = Arc::get_mut(&mut self.inner)?;
+        *inner.v.get_mut() = None;
+
+        // The previous flag's allocation is gone once it completes, so a new one is needed
+        let (flag, sub) = crate::flag::mpsc::flag();
+        self.sub = sub;
+        return Some(Sender {
+            inner: Arc::downgrade(&self.inner),
+            flag,
+        });
+    }
+
+    /// Blocks the current thread until the value is received. This method is an alias of [`wait`](Receiver::wait).
+    ///
+    /// Blocking inside an async context will stall the executor's thread, so this method should only be used
+    /// when the receiver is known to complete promptly. To `.await` the value instead, see [`into_async`](Receiver::into_async).
+    #[inline]
+    pub fn block_in_place(self) -> Option<T> {
+        self.wait()
+    }
+
+    /// Converts this receiver into an [`AsyncReceiver`], re-subscribing to the sender's flag so the value can be awaited.
+    /// The original [`Sender`] can still be used to send the value.
+    #[docfg(feature = "futures")]
+    #[inline]
+    pub fn into_async(self) -> AsyncReceiver<T> {
+        return AsyncReceiver {
+            inner: self.inner,
+            sub: self.sub.into_async(),
+        };
+    }
+}
+
+impl<T> Debug for Sender<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Sender")
+            .field("closed", &self.is_closed())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Debug for Receiver<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Receiver")
+            .field("completed", &self.sub.is_marked())
+            .finish_non_exhaustive()
+    }
 }
 
 unsafe impl<T: Send> Send for Sender<T> {}
@@ -85,8 +225,177 @@ pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
     );
 }
 
+/// Creates a new single-value channel on top of an existing flag and it's subscriber.
+///
+/// The returned [`Sender`] marks `flag` once it sends it's value (or is dropped), and the [`Receiver`] waits on `sub`.
+/// If other references to the flag are kept alive, the receiver won't complete until they are marked or dropped too.
+///
+/// # Panics
+/// This function panics if `sub` isn't a subscriber of `flag`.
+///
+/// # Example
+/// ```rust
+/// use utils_atomics::{channel::once, flag::mpsc::flag};
+///
+/// let (flag, sub) = flag();
+/// let (sender, receiver) = once::from_flag(flag, sub);
+///
+/// sender.send(42);
+/// assert_eq!(receiver.wait(), Some(42));
+/// ```
+pub fn from_flag<T>(flag: Flag, sub: Subscribe) -> (Sender<T>, Receiver<T>) {
+    assert!(
+        flag.is_paired(&sub),
+        "the subscriber must belong to the specified flag"
+    );
+
+    let inner = Arc::new(Inner {
+        v: UnsafeCell::new(None),
+    });
+
+    return (
+        Sender {
+            inner: Arc::downgrade(&inner),
+            flag,
+        },
+        Receiver { inner, sub },
+    );
+}
+
+const SIGNALLED: u8 = 1;
+const CLOSED: u8 = 1 << 1;
+const WAITING: u8 = 1 << 2;
+
+struct SignalInner {
+    state: AtomicU8,
+    waker: UnsafeCell<Option<Lock>>,
+}
+unsafe impl Send for SignalInner where Lock: Send {}
+unsafe impl Sync for SignalInner where Lock: Sync {}
+
+/// The sending half of a [`signal`]. Completes the signal when it's signalled or dropped.
+pub struct Signaller {
+    inner: Arc<SignalInner>,
+}
+
+/// The receiving half of a [`signal`]
+pub struct Waiter {
+    inner: Arc<SignalInner>,
+}
+
+impl Signaller {
+    /// Signals the waiter, consuming the signaller
+    #[inline]
+    pub fn signal(self) {
+        self.close(SIGNALLED);
+    }
+
+    #[inline]
+    fn close(&self, flags: u8) {
+        let prev = self.inner.state.fetch_or(CLOSED | flags, Ordering::AcqRel);
+        if prev & (CLOSED | WAITING) == WAITING {
+            // The waiter has already registered it's lock, and won't touch it again
+            if let Some(lock) = unsafe { &mut *self.inner.waker.get() }.take() {
+                lock.wake();
+            }
+        }
+    }
+}
+
+impl Drop for Signaller {
+    #[inline]
+    fn drop(&mut self) {
+        self.close(0);
+    }
+}
+
+impl Waiter {
+    /// Returns `Some(true)` if the signal has been sent, `Some(false)` if the [`Signaller`] was dropped without sending it,
+    /// and `None` if neither has happened yet.
+    #[inline]
+    pub fn try_wait(&self) -> Option<bool> {
+        let state = self.inner.state.load(Ordering::Acquire);
+        if state & CLOSED == CLOSED {
+            return Some(state & SIGNALLED == SIGNALLED);
+        }
+        return None;
+    }
+
+    /// Blocks the current thread until the signal is received.
+    /// If [`Signaller`] is dropped before it sends the signal, this method returns `false`.
+    pub fn wait(self) -> bool {
+        if let Some(signalled) = self.try_wait() {
+            return signalled;
+        }
+
+        let (lock, sub) = lock();
+        unsafe { *self.inner.waker.get() = Some(lock) };
+
+        let prev = self.inner.state.fetch_or(WAITING, Ordering::AcqRel);
+        if prev & CLOSED == CLOSED {
+            // The signaller closed before seeing our lock, so we must release it ourselves
+            drop(unsafe { &mut *self.inner.waker.get() }.take());
+            sub.wait();
+            return prev & SIGNALLED == SIGNALLED;
+        }
+
+        sub.wait();
+        return self.inner.state.load(Ordering::Acquire) & SIGNALLED == SIGNALLED;
+    }
+}
+
+impl Debug for Signaller {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Signaller").finish_non_exhaustive()
+    }
+}
+
+impl Debug for Waiter {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Waiter")
+            .field("state", &self.try_wait())
+            .finish()
+    }
+}
+
+/// Creates a new single-use signal, a specialization of [`channel::<()>`](channel) that tells whether the
+/// [`Signaller`] signalled the [`Waiter`] or was dropped without doing so.
+///
+/// Both halves share a single allocation, containing the signal's state and the waiter's lock.
+///
+/// # Example
+///
+/// ```rust
+/// use utils_atomics::channel::once::signal;
+///
+/// let (signaller, waiter) = signal();
+/// std::thread::spawn(move || signaller.signal());
+/// assert!(waiter.wait());
+///
+/// let (signaller, waiter) = signal();
+/// drop(signaller);
+/// assert!(!waiter.wait());
+/// ```
+pub fn signal() -> (Signaller, Waiter) {
+    let inner = Arc::new(SignalInner {
+        state: AtomicU8::new(0),
+        waker: UnsafeCell::new(None),
+    });
+
+    return (
+        Signaller {
+            inner: inner.clone(),
+        },
+        Waiter { inner },
+    );
+}
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "futures")] {
+        use core::future::Future;
+
         /// An asynchronous channel sender that can only send a single value
         #[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "futures"))))]
         pub struct AsyncSender<T> {
@@ -123,9 +432,95 @@ cfg_if::cfg_if! {
                 }
                 return Err(t);
             }
+
+            /// Clones `t` and sends it through the channel. If the channel is already closed, the value isn't cloned.
+            #[inline]
+            pub fn send_cloned (self, t: &T)
+            where
+                T: Clone,
+            {
+                if !self.is_closed() {
+                    self.send(t.clone());
+                }
+            }
+
+            /// Attempts to send a clone of `t` through the channel, returning `Ok` if successfull, and `Err` with the clone otherwise.
+            ///
+            /// # Errors
+            /// This method returns an error if the channel has already been used or closed.
+            #[inline]
+            pub fn try_send_cloned(self, t: &T) -> Result<(), T>
+            where
+                T: Clone,
+            {
+                return self.try_send(t.clone());
+            }
+
+            /// Returns `true` if the [`AsyncReceiver`] has been dropped, in which case sending a value would fail.
+            ///
+            /// This can be used to avoid computing a value that can't be delivered.
+            #[inline]
+            pub fn is_closed(&self) -> bool {
+                return self.inner.strong_count() == 0;
+            }
         }
 
-        impl<T> futures::Future for AsyncReceiver<T> {
+        impl<T> AsyncReceiver<T> {
+            /// Returns a future that resolves to the received value, or to [`Timeout`](crate::Timeout) if it isn't received before `dur` elapses.
+            ///
+            /// Since this crate doesn't depend on any specific runtime, the timer is provided by `sleep`, which is called with `dur`
+            /// to create the future that signals the timeout (e.g. `tokio::time::sleep`).
+            ///
+            /// # Example
+            /// ```rust
+            /// use utils_atomics::channel::once::async_channel;
+            /// use core::time::Duration;
+            ///
+            /// # #[tokio::main]
+            /// # async fn main() {
+            /// let (sender, receiver) = async_channel::<i32>();
+            /// let result = receiver.with_timeout(Duration::from_millis(100), tokio::time::sleep).await;
+            /// assert!(result.is_err());
+            /// drop(sender);
+            /// # }
+            /// ```
+            #[inline]
+            pub fn with_timeout<S: Future, F: FnOnce(core::time::Duration) -> S>(self, dur: core::time::Duration, sleep: F) -> WithTimeout<T, S> {
+                return WithTimeout {
+                    receiver: self,
+                    sleep: sleep(dur),
+                };
+            }
+        }
+
+        pin_project_lite::pin_project! {
+            /// Future returned by [`AsyncReceiver::with_timeout`]
+            #[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "futures"))))]
+            pub struct WithTimeout<T, S> {
+                #[pin]
+                receiver: AsyncReceiver<T>,
+                #[pin]
+                sleep: S
+            }
+        }
+
+        impl<T, S: Future> Future for WithTimeout<T, S> {
+            type Output = Result<Option<T>, crate::Timeout>;
+
+            #[inline]
+            fn poll(self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+                let this = self.project();
+                if let core::task::Poll::Ready(v) = this.receiver.poll(cx) {
+                    return core::task::Poll::Ready(Ok(v))
+                }
+                if this.sleep.poll(cx).is_ready() {
+                    return core::task::Poll::Ready(Err(crate::Timeout))
+                }
+                return core::task::Poll::Pending
+            }
+        }
+
+        impl<T> Future for AsyncReceiver<T> {
             type Output = Option<T>;
 
             #[inline]
@@ -145,6 +540,24 @@ cfg_if::cfg_if! {
             }
         }
 
+        impl<T> Debug for AsyncSender<T> {
+            #[inline]
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct("AsyncSender")
+                    .field("closed", &self.is_closed())
+                    .finish_non_exhaustive()
+            }
+        }
+
+        impl<T> Debug for AsyncReceiver<T> {
+            #[inline]
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct("AsyncReceiver")
+                    .field("completed", &self.sub.is_marked())
+                    .finish_non_exhaustive()
+            }
+        }
+
         unsafe impl<T: Send> Send for AsyncSender<T> {}
         unsafe impl<T: Send> Send for AsyncReceiver<T> {}
         unsafe impl<T: Send> Sync for AsyncSender<T> {}
@@ -182,6 +595,24 @@ mod tests {
         assert_eq!(result, Some(42));
     }
 
+    #[test]
+    fn test_send_cloned() {
+        use alloc::string::String;
+
+        let value = String::from("hello");
+        let (sender, receiver) = channel::<String>();
+        sender.send_cloned(&value);
+        assert_eq!(receiver.wait().as_ref(), Some(&value));
+
+        let (sender, receiver) = channel::<String>();
+        assert_eq!(sender.try_send_cloned(&value), Ok(()));
+        assert_eq!(receiver.wait(), Some(value.clone()));
+
+        let (sender, receiver) = channel::<String>();
+        drop(receiver);
+        assert_eq!(sender.try_send_cloned(&value), Err(value));
+    }
+
     #[test]
     fn test_sender_dropped() {
         let (sender, receiver) = channel::<i32>();
@@ -192,6 +623,93 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_wait_ref() {
+        struct Large {
+            id: usize,
+            data: [u64; 1024],
+        }
+
+        let (sender, mut receiver) = channel::<Large>();
+        std::thread::scope(|s| {
+            s.spawn(move || sender.send(Large { id: 1, data: [3; 1024] }));
+
+            let value = receiver.wait_ref().unwrap();
+            assert_eq!(value.id, 1);
+            assert!(value.data.iter().all(|&x| x == 3));
+        });
+
+        // The value is still in the channel, and can be borrowed again or moved out
+        assert_eq!(receiver.wait_ref().map(|x| x.id), Some(1));
+        assert_eq!(receiver.wait().map(|x| x.id), Some(1));
+
+        let (sender, mut receiver) = channel::<Large>();
+        drop(sender);
+        assert!(receiver.wait_ref().is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_reset() {
+        let (mut sender, mut receiver) = channel::<usize>();
+        let slot = Arc::as_ptr(&receiver.inner);
+
+        for i in 0..5 {
+            std::thread::scope(|s| {
+                s.spawn(move || sender.send(i));
+                assert_eq!(receiver.wait_ref(), Some(&i));
+            });
+
+            sender = receiver.reset().unwrap();
+            // The value slot is reused, and cleared between exchanges
+            assert!(core::ptr::eq(Arc::as_ptr(&receiver.inner), slot));
+            assert!(unsafe { &*receiver.inner.v.get() }.is_none());
+        }
+
+        // Incomplete exchanges can't be reset
+        assert!(receiver.reset().is_none());
+        drop(sender);
+        let sender = receiver.reset().unwrap();
+        assert!(!sender.is_closed());
+        sender.send(42);
+        assert_eq!(receiver.wait(), Some(42));
+
+        let (flag, sub) = crate::flag::mpsc::flag();
+        let extra = flag.clone();
+        let (sender, mut receiver) = from_flag::<i32>(flag, sub);
+        sender.send(1);
+        assert!(receiver.reset().is_none());
+        extra.mark();
+        assert!(receiver.reset().is_some());
+    }
+
+    #[test]
+    fn test_from_flag() {
+        let (flag, sub) = crate::flag::mpsc::flag();
+        let extra = flag.clone();
+        let (sender, receiver) = from_flag::<i32>(flag, sub);
+
+        sender.send(42);
+        // The channel completes only once every reference to the flag is marked
+        assert!(!receiver.sub.is_marked());
+        extra.mark();
+        assert_eq!(receiver.wait(), Some(42));
+
+        let (flag, sub) = crate::flag::mpsc::flag();
+        let (sender, receiver) = from_flag::<i32>(flag, sub);
+        drop(sender);
+        assert_eq!(receiver.wait(), None);
+    }
+
+    #[test]
+    #[should_panic = "the subscriber must belong to the specified flag"]
+    fn test_from_unpaired_flag() {
+        let (flag, _) = crate::flag::mpsc::flag();
+        let (_other, sub) = crate::flag::mpsc::flag();
+        let _ = from_flag::<i32>(flag, sub);
+    }
+
     #[test]
     fn test_try_send() {
         let (sender, receiver) = channel::<i32>();
@@ -213,6 +731,14 @@ mod tests {
         assert_eq!(result.unwrap_err(), 43);
     }
 
+    #[test]
+    fn test_is_closed() {
+        let (sender, receiver) = channel::<i32>();
+        assert!(!sender.is_closed());
+        drop(receiver);
+        assert!(sender.is_closed());
+    }
+
     #[docfg(feature = "std")]
     #[test]
     fn test_try_receive_timeout() {
@@ -224,12 +750,68 @@ mod tests {
         std::thread::sleep(core::time::Duration::from_millis(200));
         sender.send(2);
 
-        assert!(wait.join().unwrap().is_err())
+        let err = wait.join().unwrap().unwrap_err();
+        assert_eq!(err.requested, core::time::Duration::from_millis(100));
+        assert!(err.elapsed > core::time::Duration::ZERO);
+        assert!(err.elapsed < core::time::Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_debug() {
+        use alloc::format;
+
+        let (sender, receiver) = channel::<i32>();
+        assert_eq!(format!("{sender:?}"), "Sender { closed: false, .. }");
+        assert_eq!(format!("{receiver:?}"), "Receiver { completed: false, .. }");
+
+        sender.send(1);
+        assert_eq!(format!("{receiver:?}"), "Receiver { completed: true, .. }");
+
+        let (sender, receiver) = channel::<i32>();
+        drop(receiver);
+        assert_eq!(format!("{sender:?}"), "Sender { closed: true, .. }");
+    }
+
+    #[test]
+    fn test_signal_then_wait() {
+        let (signaller, waiter) = signal();
+        assert_eq!(waiter.try_wait(), None);
+
+        signaller.signal();
+        assert_eq!(waiter.try_wait(), Some(true));
+        assert!(waiter.wait());
+    }
+
+    #[test]
+    fn test_signaller_dropped() {
+        let (signaller, waiter) = signal();
+        drop(signaller);
+        assert_eq!(waiter.try_wait(), Some(false));
+        assert!(!waiter.wait());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_signal_across_threads() {
+        for dropped in [false, true] {
+            let (signaller, waiter) = signal();
+            let handle = std::thread::spawn(move || {
+                std::thread::sleep(core::time::Duration::from_millis(50));
+                match dropped {
+                    true => drop(signaller),
+                    false => signaller.signal(),
+                }
+            });
+
+            assert_eq!(waiter.wait(), !dropped);
+            handle.join().unwrap();
+        }
     }
 
     #[cfg(feature = "futures")]
     mod async_tests {
         use super::*;
+        use core::time::Duration;
         use tokio::runtime::Runtime;
 
         #[test]
@@ -243,6 +825,20 @@ mod tests {
             assert_eq!(result, Some(42));
         }
 
+        #[test]
+        fn test_async_send_cloned() {
+            let rt = Runtime::new().unwrap();
+            let value = vec![1, 2, 3];
+
+            let (async_sender, async_receiver) = async_channel::<Vec<i32>>();
+            async_sender.send_cloned(&value);
+            assert_eq!(rt.block_on(async_receiver), Some(value.clone()));
+
+            let (async_sender, async_receiver) = async_channel::<Vec<i32>>();
+            drop(async_receiver);
+            assert_eq!(async_sender.try_send_cloned(&value), Err(value));
+        }
+
         #[test]
         fn test_async_sender_dropped() {
             let rt = Runtime::new().unwrap();
@@ -275,5 +871,75 @@ mod tests {
             let value = rt.block_on(async_receiver);
             assert_eq!(value, Some(42));
         }
+
+        #[test]
+        fn test_async_is_closed() {
+            let (async_sender, async_receiver) = async_channel::<i32>();
+            assert!(!async_sender.is_closed());
+            drop(async_receiver);
+            assert!(async_sender.is_closed());
+        }
+
+        #[tokio::test]
+        async fn test_with_timeout() {
+            let (sender, receiver) = async_channel::<i32>();
+            let handle = tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                sender.send(42);
+            });
+
+            let result = receiver.with_timeout(Duration::from_secs(10), tokio::time::sleep).await;
+            assert_eq!(result, Ok(Some(42)));
+            handle.await.unwrap();
+
+            let (sender, receiver) = async_channel::<i32>();
+            let result = receiver.with_timeout(Duration::from_millis(50), tokio::time::sleep).await;
+            assert_eq!(result, Err(crate::Timeout));
+            drop(sender);
+
+            let (sender, receiver) = async_channel::<i32>();
+            drop(sender);
+            let result = receiver.with_timeout(Duration::from_secs(10), tokio::time::sleep).await;
+            assert_eq!(result, Ok(None));
+        }
+
+        #[test]
+        fn test_async_debug() {
+            use alloc::format;
+
+            let (sender, receiver) = async_channel::<i32>();
+            assert_eq!(format!("{sender:?}"), "AsyncSender { closed: false, .. }");
+            assert_eq!(format!("{receiver:?}"), "AsyncReceiver { completed: false, .. }");
+
+            sender.send(1);
+            assert_eq!(format!("{receiver:?}"), "AsyncReceiver { completed: true, .. }");
+
+            let (sender, receiver) = async_channel::<i32>();
+            drop(receiver);
+            assert_eq!(format!("{sender:?}"), "AsyncSender { closed: true, .. }");
+        }
+
+        #[test]
+        fn test_into_async() {
+            let rt = Runtime::new().unwrap();
+
+            let (sender, receiver) = channel::<i32>();
+            sender.send(42);
+            assert_eq!(rt.block_on(receiver.into_async()), Some(42));
+
+            let (sender, receiver) = channel::<i32>();
+            let receiver = receiver.into_async();
+            let handle = std::thread::spawn(move || {
+                std::thread::sleep(core::time::Duration::from_millis(100));
+                sender.send(42);
+            });
+            assert_eq!(rt.block_on(receiver), Some(42));
+            handle.join().unwrap();
+
+            let (sender, receiver) = channel::<i32>();
+            let receiver = receiver.into_async();
+            drop(sender);
+            assert_eq!(rt.block_on(receiver), None);
+        }
     }
 }
@@ -0,0 +1,258 @@
+use crate::locks::{waiter, WaitQueue, Waiter};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A condition variable, without an associated mutex.
+///
+/// Threads block on the condition with [`wait`](Condition::wait) or [`wait_while`](Condition::wait_while), and are awaken by
+/// [`notify_one`](Condition::notify_one) or [`notify_all`](Condition::notify_all). Like with [`Condvar`](std::sync::Condvar),
+/// waits may end spuriously, so the awaited state should be checked in a loop (which is what [`wait_while`](Condition::wait_while) does).
+///
+/// Every notification increments a generation counter, which waiters check after registering themselves and before blocking,
+/// so a notification sent after the awaited state was checked is never lost.
+///
+/// # Example
+///
+/// ```rust
+/// use utils_atomics::Condition;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// let cond = Condition::new();
+/// let ready = AtomicBool::new(false);
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         ready.store(true, Ordering::Release);
+///         cond.notify_all();
+///     });
+///
+///     cond.wait_while(|| !ready.load(Ordering::Acquire));
+/// });
+/// ```
+#[derive(Debug)]
+pub struct Condition {
+    wakers: WaitQueue<Waiter>,
+    generation: AtomicUsize,
+}
+
+impl Condition {
+    /// Creates a new [`Condition`]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            wakers: WaitQueue::new(),
+            generation: AtomicUsize::new(0),
+        }
+    }
+
+    /// Blocks the current thread until it's notified.
+    ///
+    /// This method may return spuriously, and notifications sent before it's called are ignored.
+    /// To wait until some state is reached without missing the notifications, see [`wait_while`](Condition::wait_while).
+    #[inline]
+    pub fn wait(&self) {
+        let generation = self.generation.load(Ordering::Acquire);
+        self.wait_generation(generation);
+    }
+
+    /// Blocks the current thread for as long as `condition` returns `true`.
+    ///
+    /// `condition` is checked before blocking and after every wake up. Since the generation counter is read before `condition`
+    /// is checked, a notification sent after the check makes the thread check it again, instead of blocking.
+    pub fn wait_while<F: FnMut() -> bool>(&self, mut condition: F) {
+        loop {
+            let generation = self.generation.load(Ordering::Acquire);
+            if !condition() {
+                return;
+            }
+            self.wait_generation(generation);
+        }
+    }
+
+    /// Wakes one of the waiting threads, returning `true` if there was one to be woken.
+    ///
+    /// Waiters are woken in LIFO order. Threads that already stopped waiting are skipped (and unregistered), so the notification
+    /// always goes to a thread that's still blocked, if there's one. The waiters that aren't woken never leave the queue, so a concurrent
+    /// [`notify_all`](Condition::notify_all) still wakes them.
+    #[inline]
+    pub fn notify_one(&self) -> bool {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        return Waiter::wake_one(&self.wakers);
+    }
+
+    /// Wakes all the waiting threads.
+    #[inline]
+    pub fn notify_all(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        Waiter::wake_all(&self.wakers);
+    }
+
+    fn wait_generation(&self, generation: usize) {
+        // The waiter is unregistered when the guard is dropped, so that notifications aren't spent on it once we return
+        let (waiter, sub, _guard) = waiter();
+        self.wakers.push(waiter);
+
+        // A notification may have been sent before our waker was registered
+        if self.generation.load(Ordering::Acquire) != generation {
+            return;
+        }
+
+        sub.wait();
+    }
+}
+
+impl Default for Condition {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(feature = "std", test))]
+mod tests {
+    use super::Condition;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    #[test]
+    fn flag_checked_in_loop() {
+        const RUNS: usize = if cfg!(miri) { 10 } else { 200 };
+
+        for _ in 0..RUNS {
+            let cond = Condition::new();
+            let ready = AtomicBool::new(false);
+
+            std::thread::scope(|s| {
+                s.spawn(|| cond.wait_while(|| !ready.load(Ordering::Acquire)));
+                s.spawn(|| {
+                    ready.store(true, Ordering::Release);
+                    cond.notify_all();
+                });
+            });
+        }
+    }
+
+    #[test]
+    fn producer_consumer() {
+        const CONSUMERS: usize = 4;
+        const ITEMS: usize = if cfg!(miri) { 10 } else { 1000 };
+
+        let cond = Condition::new();
+        let items = AtomicUsize::new(0);
+        let taken = AtomicUsize::new(0);
+
+        std::thread::scope(|s| {
+            for _ in 0..CONSUMERS {
+                s.spawn(|| {
+                    for _ in 0..ITEMS {
+                        loop {
+                            cond.wait_while(|| items.load(Ordering::Acquire) == 0);
+                            if items
+                                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |x| x.checked_sub(1))
+                                .is_ok()
+                            {
+                                break;
+                            }
+                        }
+                        taken.fetch_add(1, Ordering::Relaxed);
+                    }
+                });
+            }
+
+            for _ in 0..CONSUMERS * ITEMS {
+                items.fetch_add(1, Ordering::Release);
+                cond.notify_one();
+            }
+        });
+
+        assert_eq!(items.into_inner(), 0);
+        assert_eq!(taken.into_inner(), CONSUMERS * ITEMS);
+    }
+
+    #[test]
+    fn notify_one_skips_returned_waiters() {
+        let cond = Condition::new();
+        let ready = AtomicBool::new(false);
+
+        std::thread::scope(|s| {
+            let waiter = s.spawn(|| cond.wait_while(|| !ready.load(Ordering::Acquire)));
+            while cond.wakers.is_empty() {
+                std::thread::yield_now();
+            }
+
+            // Registers a waiter that returns right away, on top of the blocked one
+            let generation = cond.generation.load(Ordering::Acquire);
+            cond.wait_generation(generation.wrapping_sub(1));
+
+            ready.store(true, Ordering::Release);
+            assert!(cond.notify_one());
+
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+            while !waiter.is_finished() && std::time::Instant::now() < deadline {
+                std::thread::yield_now();
+            }
+
+            let lost = !waiter.is_finished();
+            // Releases the waiter either way, so that a failure doesn't hang the test
+            cond.notify_all();
+            assert!(!lost, "the notification was lost");
+        });
+    }
+
+    #[test]
+    fn notify_one_and_notify_all() {
+        use std::{sync::Barrier, time::Instant};
+
+        const THREADS: usize = 16;
+        const RUNS: usize = if cfg!(miri) { 10 } else { 200 };
+
+        for _ in 0..RUNS {
+            let cond = Condition::new();
+            let ready = AtomicBool::new(false);
+            let woken = AtomicUsize::new(0);
+            let start = Barrier::new(2);
+
+            std::thread::scope(|s| {
+                for _ in 0..THREADS {
+                    s.spawn(|| {
+                        cond.wait_while(|| !ready.load(Ordering::Acquire));
+                        woken.fetch_add(1, Ordering::AcqRel);
+                    });
+                }
+
+                while cond.wakers.len() < THREADS {
+                    std::thread::yield_now();
+                }
+                ready.store(true, Ordering::Release);
+
+                // Every waiter registered before `notify_all`, so none of them may be missed by it
+                s.spawn(|| {
+                    start.wait();
+                    cond.notify_one()
+                });
+                s.spawn(|| {
+                    start.wait();
+                    cond.notify_all();
+                });
+
+                let deadline = Instant::now() + std::time::Duration::from_secs(10);
+                while woken.load(Ordering::Acquire) < THREADS && Instant::now() < deadline {
+                    std::thread::yield_now();
+                }
+
+                let lost = woken.load(Ordering::Acquire) < THREADS;
+                // Releases the waiters either way, so that a failure doesn't hang the test
+                while woken.load(Ordering::Acquire) < THREADS {
+                    cond.notify_all();
+                    std::thread::yield_now();
+                }
+                assert!(!lost, "a waiter was missed by notify_all");
+            });
+        }
+    }
+
+    #[test]
+    fn notify_without_waiters() {
+        let cond = Condition::default();
+        assert!(!cond.notify_one());
+        cond.notify_all();
+    }
+}
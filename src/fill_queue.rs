@@ -3,23 +3,27 @@ use core::fmt::Debug;
 use core::{
     alloc::Layout,
     iter::FusedIterator,
+    mem::ManuallyDrop,
     ptr::NonNull,
-    sync::atomic::{AtomicPtr, Ordering},
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
 };
+use alloc::vec::Vec;
+#[cfg(feature = "observer")]
+use alloc::boxed::Box;
 #[cfg(feature = "alloc_api")]
 use {alloc::alloc::Global, core::alloc::*};
 
 macro_rules! impl_all {
-    (impl $(@$tr:path =>)? $target:ident {
+    (impl $(<$lt:lifetime>)? $(@$tr:path =>)? $target:ident {
         $($t:tt)*
     }) => {
         cfg_if::cfg_if! {
             if #[cfg(feature = "alloc_api")] {
-                impl<T, A: Allocator> $($tr for)? $target <T, A> {
+                impl<$($lt,)? T, A: Allocator> $($tr for)? $target <$($lt,)? T, A> {
                     $($t)*
                 }
             } else {
-                impl<T> $($tr for)? $target <T> {
+                impl<$($lt,)? T> $($tr for)? $target <$($lt,)? T> {
                     $($t)*
                 }
             }
@@ -69,11 +73,145 @@ impl<T> PrevCell<T> {
         }
         return self.prev.swap(core::ptr::null_mut(), Ordering::Acquire);
     }
+
+    /// Like [`get`](PrevCell::get), but leaves the cell untouched.
+    pub fn peek(&self) -> *mut FillQueueNode<T> {
+        while self.init.load(Ordering::Acquire) == FALSE {
+            core::hint::spin_loop()
+        }
+        return self.prev.load(Ordering::Acquire);
+    }
+
+    /// Links an already initialized cell to another node.
+    #[inline]
+    pub fn relink(&self, prev: *mut FillQueueNode<T>) {
+        debug_assert_eq!(self.init.load(Ordering::Acquire), TRUE);
+        self.prev.store(prev, Ordering::Release);
+    }
 }
 
 struct FillQueueNode<T> {
     prev: PrevCell<T>,
-    v: T,
+    // Ownership of the value is moved out explicitly by `take_value`, never through the node's drop glue
+    v: ManuallyDrop<T>,
+}
+
+impl<T> FillQueueNode<T> {
+    #[inline]
+    pub const fn new(v: T) -> Self {
+        return Self {
+            prev: PrevCell::new(),
+            v: ManuallyDrop::new(v),
+        };
+    }
+
+    /// Moves the value out of the node, returning it alongside the previous node in the list.
+    /// # Safety
+    /// `ptr` must point to a valid node owned by the caller, and this method must be called at most once per node.
+    /// After this call, the node may only be deallocated.
+    #[inline]
+    pub unsafe fn take_value(ptr: NonNull<Self>) -> (T, *mut Self) {
+        let node = &mut *ptr.as_ptr();
+        let value = ManuallyDrop::take(&mut node.v);
+        return (value, node.prev.get());
+    }
+
+    /// Reverses the chain ending at `last` by re-linking it's nodes in place, returning the new last node (the previous first one).
+    /// # Safety
+    /// The chain must be uniquely owned by the caller, and none of it's values may have been taken.
+    unsafe fn reverse_chain(last: Option<NonNull<Self>>) -> Option<NonNull<Self>> {
+        let mut reversed = core::ptr::null_mut();
+        let mut current = last;
+        while let Some(node) = current {
+            let node = &mut *node.as_ptr();
+            // Waits for the pusher of the node to link it, if it hasn't yet
+            let prev = node.prev.get();
+            node.prev.set_mut(reversed);
+            reversed = node;
+            current = NonNull::new(prev);
+        }
+        return NonNull::new(reversed);
+    }
+}
+
+/// An operation performed on a [`FillQueue`], reported to the callback registered with [`set_observer`](FillQueue::set_observer).
+///
+/// Observing queues is opt-in, so that queues that aren't observed don't pay for it in size nor in pushes.
+#[cfg_attr(docsrs, doc(cfg(feature = "observer")))]
+#[cfg(feature = "observer")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueueEvent {
+    /// An element was pushed to the queue. Extending the queue reports one event per element.
+    Pushed,
+    /// The queue was chopped, taking the specified amount of elements. It's reported once the [`ChopIter`] is dropped.
+    Chopped(usize),
+}
+
+#[cfg(feature = "observer")]
+type Observer = Box<dyn Fn(QueueEvent) + Send + Sync>;
+
+/// Lock-free slot for the observer of a [`FillQueue`].
+///
+/// Readers pin the current thread's epoch before loading the observer, so a replaced observer is only dropped
+/// once every thread that could be calling it has unpinned.
+#[cfg(feature = "observer")]
+struct ObserverSlot {
+    inner: AtomicPtr<Observer>,
+}
+
+#[cfg(feature = "observer")]
+impl ObserverSlot {
+    #[inline]
+    const fn new() -> Self {
+        return Self {
+            inner: AtomicPtr::new(core::ptr::null_mut()),
+        };
+    }
+
+    fn replace(&self, observer: Option<Observer>) {
+        let new = observer.map_or(core::ptr::null_mut(), |x| Box::into_raw(Box::new(x)));
+        let guard = crossbeam::epoch::pin();
+        let prev = self.inner.swap(new, Ordering::AcqRel);
+        if !prev.is_null() {
+            unsafe { guard.defer_unchecked(move || drop(Box::from_raw(prev))) }
+        }
+    }
+
+    #[inline]
+    fn notify(&self, event: QueueEvent) {
+        self.notify_n(event, 1)
+    }
+
+    /// Reports `event` `n` times, pinning the epoch only once
+    fn notify_n(&self, event: QueueEvent, n: usize) {
+        // Queues without an observer don't need to pin
+        if self.inner.load(Ordering::Relaxed).is_null() {
+            return;
+        }
+
+        let _guard = crossbeam::epoch::pin();
+        if let Some(f) = unsafe { self.inner.load(Ordering::Acquire).as_ref() } {
+            (0..n).for_each(|_| f(event))
+        }
+    }
+
+    #[inline]
+    fn notify_mut(&mut self, event: QueueEvent) {
+        if let Some(f) = unsafe { self.inner.get_mut().as_ref() } {
+            f(event)
+        }
+    }
+}
+
+#[cfg(feature = "observer")]
+impl Drop for ObserverSlot {
+    #[inline]
+    fn drop(&mut self) {
+        let ptr = *self.inner.get_mut();
+        if !ptr.is_null() {
+            unsafe { drop(Box::from_raw(ptr)) }
+        }
+    }
 }
 
 /// An atomic queue intended for use cases where taking the full contents of the queue is needed.
@@ -101,6 +239,9 @@ struct FillQueueNode<T> {
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub struct FillQueue<T, #[cfg(feature = "alloc_api")] A: Allocator = Global> {
     head: AtomicPtr<FillQueueNode<T>>,
+    len: AtomicUsize,
+    #[cfg(feature = "observer")]
+    observer: ObserverSlot,
     #[cfg(feature = "alloc_api")]
     alloc: A,
 }
@@ -117,12 +258,22 @@ impl<T> FillQueue<T> {
     pub const fn new() -> Self {
         Self {
             head: AtomicPtr::new(core::ptr::null_mut()),
+            len: AtomicUsize::new(0),
+            #[cfg(feature = "observer")]
+            observer: ObserverSlot::new(),
             #[cfg(feature = "alloc_api")]
             alloc: Global,
         }
     }
 }
 
+impl<T> Default for FillQueue<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[docfg::docfg(feature = "alloc_api")]
 impl<T, A: Allocator> FillQueue<T, A> {
     /// Creates a new [`FillQueue`] with the given allocator.
@@ -139,6 +290,9 @@ impl<T, A: Allocator> FillQueue<T, A> {
     pub const fn new_in(alloc: A) -> Self {
         Self {
             head: AtomicPtr::new(core::ptr::null_mut()),
+            len: AtomicUsize::new(0),
+            #[cfg(feature = "observer")]
+            observer: ObserverSlot::new(),
             alloc,
         }
     }
@@ -177,6 +331,71 @@ impl_all! {
             self.head.load(Ordering::Relaxed).is_null()
         }
 
+        /// Returns the approximate number of elements currently in the queue.
+        ///
+        /// Elements are counted right before they're pushed, and uncounted as the [`ChopIter`] that chopped them
+        /// consumes (or drops) them, so the counter never misses an element that's in the queue, even if pushes interleave with chops.
+        /// Since the counter is updated separately from the list itself, it may still include elements that are being pushed,
+        /// or that were chopped but not yet consumed, so it's result should be considered immediately stale.
+        /// Every counted element lives in it's own node allocation, so the counter can't overflow before the allocator runs out of memory.
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::prelude::*;
+        ///
+        /// let queue = FillQueue::<i32>::new();
+        /// queue.push(1);
+        /// queue.push(2);
+        /// assert_eq!(queue.len(), 2);
+        ///
+        /// let mut iter = queue.chop();
+        /// assert_eq!(queue.len(), 2);
+        /// assert_eq!(iter.next(), Some(2));
+        /// assert_eq!(queue.len(), 1);
+        ///
+        /// drop(iter);
+        /// assert_eq!(queue.len(), 0);
+        /// ```
+        #[inline]
+        pub fn len (&self) -> usize {
+            self.len.load(Ordering::Relaxed)
+        }
+
+        /// Registers a callback that's invoked with every [`QueueEvent`] of the queue, replacing the previous one.
+        ///
+        /// The callback is called from the thread that performed the operation, right after it completes.
+        /// Reading the observer is lock-free, so it's safe to push to (or chop) the queue from inside the callback,
+        /// and threads that are invoking the previous observer will keep it alive until they finish.
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::fill_queue::{FillQueue, QueueEvent};
+        /// use std::sync::{Arc, Mutex};
+        ///
+        /// let events = Arc::new(Mutex::new(Vec::new()));
+        /// let queue = FillQueue::<i32>::new();
+        /// queue.set_observer({
+        ///     let events = events.clone();
+        ///     move |event| events.lock().unwrap().push(event)
+        /// });
+        ///
+        /// queue.push(1);
+        /// let _ = queue.chop();
+        /// assert_eq!(*events.lock().unwrap(), [QueueEvent::Pushed, QueueEvent::Chopped(1)]);
+        /// ```
+        #[cfg_attr(docsrs, doc(cfg(feature = "observer")))]
+        #[cfg(feature = "observer")]
+        #[inline]
+        pub fn set_observer<F: 'static + Fn(QueueEvent) + Send + Sync> (&self, f: F) {
+            self.observer.replace(Some(Box::new(f)));
+        }
+
+        /// Removes the observer registered with [`set_observer`](FillQueue::set_observer), if any.
+        #[cfg_attr(docsrs, doc(cfg(feature = "observer")))]
+        #[cfg(feature = "observer")]
+        #[inline]
+        pub fn clear_observer (&self) {
+            self.observer.replace(None);
+        }
+
         /// Uses atomic operations to push an element to the queue.
         /// # Panics
         /// This method panics if `alloc` fails to allocate the memory needed for the node.
@@ -224,30 +443,17 @@ impl_all! {
         /// assert_eq!(queue.chop().next(), Some(1));
         /// ```
         pub fn try_push (&self, v: T) -> Result<(), AllocError> {
-            let node = FillQueueNode {
-                prev: PrevCell::new(),
-                v
-            };
-
-            let layout = Layout::new::<FillQueueNode<T>>();
-            #[cfg(feature = "alloc_api")]
-            let ptr = self.alloc.allocate(layout)?.cast::<FillQueueNode<T>>();
-            #[cfg(not(feature = "alloc_api"))]
-            let ptr = match unsafe { NonNull::new(alloc::alloc::alloc(layout)) } {
-                Some(x) => x.cast::<FillQueueNode<T>>(),
-                None => return Err(AllocError)
-            };
-
-            unsafe {
-                ptr.as_ptr().write(node)
-            }
+            let ptr = self.allocate_node(v)?;
+            // The node is counted before it's published, so the chop that takes it can't uncount it first
+            self.len.fetch_add(1, Ordering::Relaxed);
 
             let prev = self.head.swap(ptr.as_ptr(), Ordering::AcqRel);
             unsafe {
                 let rf = &*ptr.as_ptr();
                 rf.prev.set(prev);
             }
-
+            #[cfg(feature = "observer")]
+            self.observer.notify(QueueEvent::Pushed);
             Ok(())
         }
 
@@ -271,25 +477,190 @@ impl_all! {
         /// assert_eq!(queue.chop_mut().next(), Some(1));
         /// ```
         pub fn try_push_mut (&mut self, v: T) -> Result<(), AllocError> {
-            let node = FillQueueNode {
-                prev: PrevCell::new(),
-                v
-            };
+            let mut ptr = self.allocate_node(v)?;
+            unsafe {
+                let prev = core::ptr::replace(self.head.get_mut(), ptr.as_ptr());
+                ptr.as_mut().prev.set_mut(prev);
+                *self.len.get_mut() += 1;
+            }
+            #[cfg(feature = "observer")]
+            self.observer.notify_mut(QueueEvent::Pushed);
+            Ok(())
+        }
+
+        /// Uses atomic operations to push every element of `iter` to the queue, returning the number of elements pushed.
+        /// # Panics
+        /// This method panics if `alloc` fails to allocate the memory needed for the nodes.
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::prelude::*;
+        ///
+        /// let queue = FillQueue::<i32>::new();
+        /// assert_eq!(queue.extend([1, 2, 3]), 3);
+        /// assert_eq!(queue.chop().collect::<Vec<_>>(), [3, 2, 1]);
+        /// ```
+        #[inline]
+        pub fn extend<I: IntoIterator<Item = T>> (&self, iter: I) -> usize {
+            self.try_extend(iter).unwrap()
+        }
 
+        /// Uses atomic operations to push every element of `iter` to the queue, returning the number of elements pushed.
+        ///
+        /// The elements are first linked into a local chain, which is then appended to the queue with a single atomic operation,
+        /// so they'll be chopped together, in LIFO order, as if they had been pushed one by one.
+        ///
+        /// # Errors
+        ///
+        /// This method returns an error if `alloc` fails to allocate the memory needed for any of the nodes.
+        /// In that case, no element is pushed: the nodes that were already allocated are freed, and every element of `iter` is dropped.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::prelude::*;
+        ///
+        /// let queue = FillQueue::<i32>::new();
+        /// assert_eq!(queue.try_extend([1, 2, 3]), Ok(3));
+        /// assert_eq!(queue.chop().collect::<Vec<_>>(), [3, 2, 1]);
+        /// ```
+        pub fn try_extend<I: IntoIterator<Item = T>> (&self, iter: I) -> Result<usize, AllocError> {
+            let mut iter = iter.into_iter();
+            let Some(first) = iter.next() else { return Ok(0) };
+
+            let first = self.allocate_node(first)?;
+            let mut last = first;
+            let mut count = 1;
+
+            for v in iter {
+                match self.allocate_node(v) {
+                    Ok(node) => unsafe {
+                        // The chain is still local, so it can be linked with non-atomic operations
+                        (*node.as_ptr()).prev.set_mut(last.as_ptr());
+                        last = node;
+                        count += 1;
+                    },
+                    Err(e) => unsafe {
+                        (*first.as_ptr()).prev.set_mut(core::ptr::null_mut());
+                        self.free_chain(last);
+                        return Err(e)
+                    }
+                }
+            }
+
+            self.len.fetch_add(count, Ordering::Relaxed);
+            let prev = self.head.swap(last.as_ptr(), Ordering::AcqRel);
+            unsafe {
+                (*first.as_ptr()).prev.set(prev);
+            }
+            #[cfg(feature = "observer")]
+            self.observer.notify_n(QueueEvent::Pushed, count);
+            Ok(count)
+        }
+
+        /// Allocates a new, unlinked node holding `v`.
+        #[cfg_attr(not(feature = "alloc_api"), allow(clippy::unused_self))]
+        fn allocate_node (&self, v: T) -> Result<NonNull<FillQueueNode<T>>, AllocError> {
             let layout = Layout::new::<FillQueueNode<T>>();
             #[cfg(feature = "alloc_api")]
-            let mut ptr = self.alloc.allocate(layout)?.cast::<FillQueueNode<T>>();
+            let ptr = self.alloc.allocate(layout)?.cast::<FillQueueNode<T>>();
             #[cfg(not(feature = "alloc_api"))]
-            let mut ptr = match unsafe { NonNull::new(alloc::alloc::alloc(layout)) } {
+            let ptr = match unsafe { NonNull::new(alloc::alloc::alloc(layout)) } {
                 Some(x) => x.cast::<FillQueueNode<T>>(),
                 None => return Err(AllocError)
             };
 
             unsafe {
-                ptr.as_ptr().write(node);
-                let prev = core::ptr::replace(self.head.get_mut(), ptr.as_ptr());
-                ptr.as_mut().prev.set_mut(prev);
-                Ok(())
+                ptr.as_ptr().write(FillQueueNode::new(v))
+            }
+
+            Ok(ptr)
+        }
+
+        /// Returns a LIFO iterator over the elements of the queue, without removing them from it.
+        /// # Safety
+        /// The queue mustn't be chopped, nor have elements removed by [`retain_unchecked`](FillQueue::retain_unchecked), while the iterator or the
+        /// references it yields are alive. Likewise, only one of these iterators may be alive at once.
+        #[allow(clippy::mut_from_ref)]
+        pub(crate) unsafe fn iter_mut_unchecked (&self) -> impl Iterator<Item = &mut T> {
+            let mut ptr = self.head.load(Ordering::Acquire);
+            return core::iter::from_fn(move || {
+                let node = ptr.as_mut()?;
+                // Waits for the pusher of the node to link it, if it hasn't yet
+                ptr = node.prev.peek();
+                return Some(&mut *node.v)
+            })
+        }
+
+        /// Removes, in place, every element for which `f` returns `false`, visiting them in LIFO order.
+        ///
+        /// Unlike chopping the queue and pushing the retained elements back, the retained elements never leave the queue,
+        /// so concurrent chops can't miss them. Elements pushed concurrently may or may not be visited.
+        /// # Safety
+        /// The queue mustn't be chopped, iterated by [`iter_mut_unchecked`](FillQueue::iter_mut_unchecked) or retained concurrently,
+        /// since the removed nodes are freed right away. Concurrent pushes are fine.
+        pub(crate) unsafe fn retain_unchecked<F: FnMut(&mut T) -> bool> (&self, mut f: F) {
+            // The newer neighbour of the current node, whose `prev` points to it
+            let mut next = None::<&FillQueueNode<T>>;
+            let mut ptr = self.head.load(Ordering::Acquire);
+            #[cfg(feature = "observer")]
+            let mut removed = 0;
+
+            while let Some(node) = NonNull::new(ptr) {
+                if f(&mut *(*node.as_ptr()).v) {
+                    let node = &*node.as_ptr();
+                    ptr = node.prev.peek();
+                    next = Some(node);
+                    continue
+                }
+
+                ptr = (*node.as_ptr()).prev.get();
+                match next {
+                    Some(next) => next.prev.relink(ptr),
+                    None => if let Err(mut newer) = self.head.compare_exchange(node.as_ptr(), ptr, Ordering::AcqRel, Ordering::Acquire) {
+                        // Nodes were pushed on top of the removed one, so it's newer neighbour must be found first
+                        loop {
+                            let prev = (*newer).prev.peek();
+                            if prev == node.as_ptr() {
+                                break
+                            }
+                            newer = prev;
+                        }
+
+                        let newer = &*newer;
+                        newer.prev.relink(ptr);
+                        next = Some(newer);
+                    }
+                }
+
+                self.len.fetch_sub(1, Ordering::Relaxed);
+                #[cfg(feature = "observer")]
+                {
+                    removed += 1;
+                }
+                // The node has been unlinked (and it's `prev` cleared), so it's the only one freed
+                self.free_chain(node);
+            }
+
+            #[cfg(feature = "observer")]
+            if removed > 0 {
+                self.observer.notify(QueueEvent::Chopped(removed));
+            }
+        }
+
+        /// Drops the values and frees the nodes of a chain that was never shared, starting at `last`.
+        /// # Safety
+        /// The chain must be uniquely owned by the caller, and it's first node must have a null `prev`.
+        #[cfg_attr(not(feature = "alloc_api"), allow(clippy::unused_self))]
+        unsafe fn free_chain (&self, last: NonNull<FillQueueNode<T>>) {
+            let mut ptr = Some(last);
+            while let Some(node) = ptr {
+                let (value, prev) = FillQueueNode::take_value(node);
+                drop(value);
+                ptr = NonNull::new(prev);
+
+                #[cfg(feature = "alloc_api")]
+                self.alloc.deallocate(node.cast(), Layout::new::<FillQueueNode<T>>());
+                #[cfg(not(feature = "alloc_api"))]
+                alloc::alloc::dealloc(node.as_ptr().cast(), Layout::new::<FillQueueNode<T>>());
             }
         }
     }
@@ -316,13 +687,19 @@ impl<T, A: Allocator> FillQueue<T, A> {
     /// assert_eq!(iter.next(), None)
     /// ```
     #[inline]
-    pub fn chop(&self) -> ChopIter<T, A>
+    #[must_use = "the chopped elements are removed from the queue, and dropped alongside the iterator"]
+    pub fn chop(&self) -> ChopIter<'_, T, A>
     where
         A: Clone,
     {
         let ptr = self.head.swap(core::ptr::null_mut(), Ordering::AcqRel);
         ChopIter {
             ptr: NonNull::new(ptr),
+            len: Some(&self.len),
+            #[cfg(feature = "observer")]
+            observer: Some(&self.observer),
+            #[cfg(feature = "observer")]
+            taken: 0,
             alloc: self.alloc.clone(),
         }
     }
@@ -347,17 +724,112 @@ impl<T, A: Allocator> FillQueue<T, A> {
     /// assert_eq!(iter.next(), None)
     /// ```
     #[inline]
-    pub fn chop_mut(&mut self) -> ChopIter<T, A>
+    #[must_use = "the chopped elements are removed from the queue, and dropped alongside the iterator"]
+    pub fn chop_mut(&mut self) -> ChopIter<'static, T, A>
     where
         A: Clone,
     {
         let ptr = unsafe { core::ptr::replace(self.head.get_mut(), core::ptr::null_mut()) };
+        #[cfg(feature = "observer")]
+        self.observer.notify_mut(QueueEvent::Chopped(*self.len.get_mut()));
+        *self.len.get_mut() = 0;
 
         ChopIter {
             ptr: NonNull::new(ptr),
+            len: None,
+            #[cfg(feature = "observer")]
+            observer: None,
+            #[cfg(feature = "observer")]
+            taken: 0,
             alloc: self.alloc.clone(),
         }
     }
+
+    /// Returns a FIFO (First In First Out) iterator over a chopped chunk of a [`FillQueue`], yielding the oldest elements first.
+    ///
+    /// The chopped chain is reversed eagerly, before this method returns, by re-linking it's nodes in place. This doesn't allocate,
+    /// but it makes creating the iterator O(n) on the number of chopped elements, which makes it slightly more expensive than
+    /// the default LIFO [`chop`](FillQueue::chop). The nodes are still freed one by one as the iterator advances (or when it's dropped).
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::prelude::*;
+    ///
+    /// let queue = FillQueue::<i32>::new();
+    ///
+    /// queue.push(1);
+    /// queue.push(2);
+    /// queue.push(3);
+    ///
+    /// assert_eq!(queue.chop_fifo().collect::<Vec<_>>(), [1, 2, 3]);
+    /// ```
+    #[inline]
+    #[must_use = "the chopped elements are removed from the queue, and dropped alongside the iterator"]
+    pub fn chop_fifo(&self) -> ChopIter<'_, T, A>
+    where
+        A: Clone,
+    {
+        let mut iter = self.chop();
+        // SAFETY: The chopped chain is uniquely owned by the iterator, which hasn't yielded anything yet
+        iter.ptr = unsafe { FillQueueNode::reverse_chain(iter.ptr) };
+        return iter;
+    }
+
+    /// Returns a FIFO (First In First Out) iterator over a chopped chunk of a [`FillQueue`], like [`chop_fifo`](FillQueue::chop_fifo).
+    /// The chopping is done with non-atomic operations.
+    /// # Safety
+    /// This method is safe because the mutable reference guarantees we are the only thread that can access this queue.
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::prelude::*;
+    ///
+    /// let mut queue = FillQueue::<i32>::new();
+    ///
+    /// queue.push_mut(1);
+    /// queue.push_mut(2);
+    /// queue.push_mut(3);
+    ///
+    /// assert_eq!(queue.chop_fifo_mut().collect::<Vec<_>>(), [1, 2, 3]);
+    /// ```
+    #[inline]
+    #[must_use = "the chopped elements are removed from the queue, and dropped alongside the iterator"]
+    pub fn chop_fifo_mut(&mut self) -> ChopIter<'static, T, A>
+    where
+        A: Clone,
+    {
+        let mut iter = self.chop_mut();
+        // SAFETY: The chopped chain is uniquely owned by the iterator, which hasn't yielded anything yet
+        iter.ptr = unsafe { FillQueueNode::reverse_chain(iter.ptr) };
+        return iter;
+    }
+
+    /// Chops the queue, collecting it's elements into a [`Vec`] pre-sized with the queue's approximate [`len`](FillQueue::len).
+    ///
+    /// The elements are returned in LIFO (Last In First Out) order, like with [`chop`](FillQueue::chop).
+    /// If FIFO order is needed, the result can be [`reverse`](slice::reverse)d in place.
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::prelude::*;
+    ///
+    /// let queue = FillQueue::<i32>::new();
+    ///
+    /// queue.push(1);
+    /// queue.push(2);
+    /// queue.push(3);
+    ///
+    /// let mut chopped = queue.chop_into_vec();
+    /// assert_eq!(chopped, [3, 2, 1]);
+    ///
+    /// chopped.reverse();
+    /// assert_eq!(chopped, [1, 2, 3]);
+    /// ```
+    pub fn chop_into_vec(&self) -> Vec<T>
+    where
+        A: Clone,
+    {
+        let mut result = Vec::with_capacity(self.len());
+        result.extend(self.chop());
+        return result;
+    }
 }
 
 #[cfg(not(feature = "alloc_api"))]
@@ -381,10 +853,16 @@ impl<T> FillQueue<T> {
     /// assert_eq!(iter.next(), None)
     /// ```
     #[inline]
-    pub fn chop(&self) -> ChopIter<T> {
+    #[must_use = "the chopped elements are removed from the queue, and dropped alongside the iterator"]
+    pub fn chop(&self) -> ChopIter<'_, T> {
         let ptr = self.head.swap(core::ptr::null_mut(), Ordering::AcqRel);
         ChopIter {
             ptr: NonNull::new(ptr),
+            len: Some(&self.len),
+            #[cfg(feature = "observer")]
+            observer: Some(&self.observer),
+            #[cfg(feature = "observer")]
+            taken: 0,
         }
     }
 
@@ -408,47 +886,149 @@ impl<T> FillQueue<T> {
     /// assert_eq!(iter.next(), None)
     /// ```
     #[inline]
-    pub fn chop_mut(&mut self) -> ChopIter<T> {
+    #[must_use = "the chopped elements are removed from the queue, and dropped alongside the iterator"]
+    pub fn chop_mut(&mut self) -> ChopIter<'static, T> {
         let ptr = unsafe { core::ptr::replace(self.head.get_mut(), core::ptr::null_mut()) };
+        #[cfg(feature = "observer")]
+        self.observer.notify_mut(QueueEvent::Chopped(*self.len.get_mut()));
+        *self.len.get_mut() = 0;
 
         ChopIter {
             ptr: NonNull::new(ptr),
+            len: None,
+            #[cfg(feature = "observer")]
+            observer: None,
+            #[cfg(feature = "observer")]
+            taken: 0,
         }
     }
+
+    /// Returns a FIFO (First In First Out) iterator over a chopped chunk of a [`FillQueue`], yielding the oldest elements first.
+    ///
+    /// The chopped chain is reversed eagerly, before this method returns, by re-linking it's nodes in place. This doesn't allocate,
+    /// but it makes creating the iterator O(n) on the number of chopped elements, which makes it slightly more expensive than
+    /// the default LIFO [`chop`](FillQueue::chop). The nodes are still freed one by one as the iterator advances (or when it's dropped).
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::prelude::*;
+    ///
+    /// let queue = FillQueue::<i32>::new();
+    ///
+    /// queue.push(1);
+    /// queue.push(2);
+    /// queue.push(3);
+    ///
+    /// assert_eq!(queue.chop_fifo().collect::<Vec<_>>(), [1, 2, 3]);
+    /// ```
+    #[inline]
+    #[must_use = "the chopped elements are removed from the queue, and dropped alongside the iterator"]
+    pub fn chop_fifo(&self) -> ChopIter<'_, T> {
+        let mut iter = self.chop();
+        // SAFETY: The chopped chain is uniquely owned by the iterator, which hasn't yielded anything yet
+        iter.ptr = unsafe { FillQueueNode::reverse_chain(iter.ptr) };
+        return iter;
+    }
+
+    /// Returns a FIFO (First In First Out) iterator over a chopped chunk of a [`FillQueue`], like [`chop_fifo`](FillQueue::chop_fifo).
+    /// The chopping is done with non-atomic operations.
+    /// # Safety
+    /// This method is safe because the mutable reference guarantees we are the only thread that can access this queue.
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::prelude::*;
+    ///
+    /// let mut queue = FillQueue::<i32>::new();
+    ///
+    /// queue.push_mut(1);
+    /// queue.push_mut(2);
+    /// queue.push_mut(3);
+    ///
+    /// assert_eq!(queue.chop_fifo_mut().collect::<Vec<_>>(), [1, 2, 3]);
+    /// ```
+    #[inline]
+    #[must_use = "the chopped elements are removed from the queue, and dropped alongside the iterator"]
+    pub fn chop_fifo_mut(&mut self) -> ChopIter<'static, T> {
+        let mut iter = self.chop_mut();
+        // SAFETY: The chopped chain is uniquely owned by the iterator, which hasn't yielded anything yet
+        iter.ptr = unsafe { FillQueueNode::reverse_chain(iter.ptr) };
+        return iter;
+    }
+
+    /// Chops the queue, collecting it's elements into a [`Vec`] pre-sized with the queue's approximate [`len`](FillQueue::len).
+    ///
+    /// The elements are returned in LIFO (Last In First Out) order, like with [`chop`](FillQueue::chop).
+    /// If FIFO order is needed, the result can be [`reverse`](slice::reverse)d in place.
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::prelude::*;
+    ///
+    /// let queue = FillQueue::<i32>::new();
+    ///
+    /// queue.push(1);
+    /// queue.push(2);
+    /// queue.push(3);
+    ///
+    /// let mut chopped = queue.chop_into_vec();
+    /// assert_eq!(chopped, [3, 2, 1]);
+    ///
+    /// chopped.reverse();
+    /// assert_eq!(chopped, [1, 2, 3]);
+    /// ```
+    pub fn chop_into_vec(&self) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.len());
+        result.extend(self.chop());
+        return result;
+    }
 }
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "alloc_api")] {
         unsafe impl<T: Send, A: Send + Allocator> Send for FillQueue<T, A> {}
         unsafe impl<T: Sync, A: Sync + Allocator> Sync for FillQueue<T, A> {}
-        unsafe impl<T: Send, A: Send + Allocator> Send for ChopIter<T, A> {}
-        unsafe impl<T: Sync, A: Sync + Allocator> Sync for ChopIter<T, A> {}
+        unsafe impl<T: Send, A: Send + Allocator> Send for ChopIter<'_, T, A> {}
+        unsafe impl<T: Sync, A: Sync + Allocator> Sync for ChopIter<'_, T, A> {}
     } else {
         unsafe impl<T: Send> Send for FillQueue<T> {}
         unsafe impl<T: Sync> Sync for FillQueue<T> {}
-        unsafe impl<T: Send> Send for ChopIter<T> {}
-        unsafe impl<T: Sync> Sync for ChopIter<T> {}
+        unsafe impl<T: Send> Send for ChopIter<'_, T> {}
+        unsafe impl<T: Sync> Sync for ChopIter<'_, T> {}
     }
 }
 
 /// Iterator of [`FillQueue::chop`] and [`FillQueue::chop_mut`]
-pub struct ChopIter<T, #[cfg(feature = "alloc_api")] A: Allocator = Global> {
+///
+/// Iterators returned by [`FillQueue::chop`] uncount every element they consume from the queue's [`len`](FillQueue::len).
+#[must_use = "dropping a `ChopIter` drops every element it hasn't yielded yet"]
+pub struct ChopIter<'a, T, #[cfg(feature = "alloc_api")] A: Allocator = Global> {
     ptr: Option<NonNull<FillQueueNode<T>>>,
+    len: Option<&'a AtomicUsize>,
+    #[cfg(feature = "observer")]
+    observer: Option<&'a ObserverSlot>,
+    #[cfg(feature = "observer")]
+    taken: usize,
     #[cfg(feature = "alloc_api")]
     alloc: A,
 }
 
 impl_all! {
-    impl @Iterator => ChopIter {
+    impl<'a> @Iterator => ChopIter {
         type Item = T;
 
         #[inline]
         fn next(&mut self) -> Option<Self::Item> {
             if let Some(ptr) = self.ptr {
                 unsafe {
-                    let node = &*ptr.as_ptr();
-                    let value = core::ptr::read(&node.v);
-                    self.ptr = NonNull::new(node.prev.get());
+                    // The node is uniquely owned by the iterator, so it's value is taken exactly once, right before freeing it
+                    let (value, prev) = FillQueueNode::take_value(ptr);
+                    self.ptr = NonNull::new(prev);
+
+                    if let Some(len) = self.len {
+                        len.fetch_sub(1, Ordering::Relaxed);
+                    }
+                    #[cfg(feature = "observer")]
+                    {
+                        self.taken += 1;
+                    }
 
                     #[cfg(feature = "alloc_api")]
                     self.alloc.deallocate(ptr.cast(), Layout::new::<FillQueueNode<T>>());
@@ -465,18 +1045,90 @@ impl_all! {
 }
 
 impl_all! {
-    impl @Drop => ChopIter {
+    impl<'a> @Drop => ChopIter {
         #[inline]
         fn drop(&mut self) {
-            self.for_each(core::mem::drop)
+            self.for_each(core::mem::drop);
+            #[cfg(feature = "observer")]
+            if let Some(observer) = self.observer {
+                observer.notify(QueueEvent::Chopped(self.taken));
+            }
         }
     }
 }
 
 impl_all! {
-    impl @FusedIterator => ChopIter {}
+    impl<'a> @FusedIterator => ChopIter {}
+}
+
+impl_all! {
+    impl @Extend<T> => FillQueue {
+        #[inline]
+        fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+            // The mutable reference allows the use of the non-atomic push
+            iter.into_iter().for_each(|v| self.push_mut(v));
+        }
+    }
 }
 
+impl<T> FromIterator<T> for FillQueue<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        Extend::extend(&mut queue, iter);
+        return queue;
+    }
+}
+
+#[cfg(feature = "std")]
+macro_rules! impl_write {
+    ($($t:tt)*) => {
+        /// Pushes the written bytes to the queue as chunks, allowing it to be used as a lock-free byte sink (e.g. for log buffering).
+        ///
+        /// Every call to [`write`](std::io::Write::write) copies it's whole buffer into a single chunk, and pushes it with a single
+        /// atomic operation, so the bytes of concurrent writes are never interleaved. Empty buffers aren't pushed.
+        ///
+        /// Like any other element, chunks are chopped in LIFO order by [`chop`](FillQueue::chop), so use [`chop_fifo`](FillQueue::chop_fifo)
+        /// to flush them in the order they were written.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::FillQueue;
+        /// use std::io::Write;
+        ///
+        /// let queue = FillQueue::<Vec<u8>>::new();
+        /// write!(&queue, "{} + {}", 1, 2).unwrap();
+        ///
+        /// let bytes = queue.chop_fifo().flatten().collect::<Vec<_>>();
+        /// assert_eq!(bytes, b"1 + 2");
+        /// ```
+        #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+        $($t)* {
+            #[inline]
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                if buf.is_empty() {
+                    return Ok(0);
+                }
+
+                match self.try_push(buf.to_vec()) {
+                    Ok(()) => Ok(buf.len()),
+                    Err(_) => Err(std::io::ErrorKind::OutOfMemory.into()),
+                }
+            }
+
+            #[inline]
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(all(feature = "std", feature = "alloc_api"))]
+impl_write!(impl<A: Allocator> std::io::Write for &FillQueue<Vec<u8>, A>);
+#[cfg(all(feature = "std", not(feature = "alloc_api")))]
+impl_write!(impl std::io::Write for &FillQueue<Vec<u8>>);
+
 #[cfg(feature = "alloc_api")]
 impl<T, A: Debug + Allocator> Debug for FillQueue<T, A> {
     #[inline]
@@ -529,6 +1181,339 @@ mod tests {
         assert!(fill_queue.is_empty());
     }
 
+    #[test]
+    fn test_chop_into_vec() {
+        let mut fill_queue = FillQueue::new();
+        assert!(fill_queue.chop_into_vec().is_empty());
+
+        (0..10).for_each(|i| fill_queue.push(i));
+        (10..20).for_each(|i| fill_queue.push_mut(i));
+        assert_eq!(fill_queue.len(), 20);
+
+        let chopped = fill_queue.chop_into_vec();
+        assert_eq!(chopped, (0..20).rev().collect::<alloc::vec::Vec<_>>());
+        assert!(chopped.capacity() >= chopped.len());
+        assert_eq!(fill_queue.len(), 0);
+        assert!(fill_queue.is_empty());
+    }
+
+    #[test]
+    fn test_extend() {
+        let fill_queue = FillQueue::new();
+        assert_eq!(fill_queue.extend(core::iter::empty()), 0);
+        assert!(fill_queue.is_empty());
+
+        fill_queue.push(0);
+        assert_eq!(fill_queue.extend(1..5), 4);
+        fill_queue.push(5);
+        assert_eq!(fill_queue.len(), 6);
+
+        let chopped = fill_queue.chop().collect::<alloc::vec::Vec<_>>();
+        assert_eq!(chopped, [5, 4, 3, 2, 1, 0]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_io_write() {
+        use std::io::Write;
+
+        const THREADS: usize = 4;
+        const LINES: usize = 50;
+
+        let queue = FillQueue::<alloc::vec::Vec<u8>>::new();
+        let mut sink = &queue;
+        writeln!(sink, "header {:03}", 7).unwrap();
+        assert_eq!(sink.write(&[]).unwrap(), 0);
+        sink.flush().unwrap();
+
+        let bytes = queue.chop_fifo().flatten().collect::<alloc::vec::Vec<_>>();
+        assert_eq!(bytes, b"header 007\n");
+
+        std::thread::scope(|s| {
+            for t in 0..THREADS {
+                let queue = &queue;
+                s.spawn(move || {
+                    for i in 0..LINES {
+                        // A single `write` call per line, so lines can't be interleaved
+                        let line = std::format!("thread {t} line {i}\n");
+                        assert_eq!((&*queue).write(line.as_bytes()).unwrap(), line.len());
+                    }
+                });
+            }
+        });
+
+        assert_eq!(queue.len(), THREADS * LINES);
+        let bytes = queue.chop_fifo().flatten().collect::<alloc::vec::Vec<_>>();
+        let text = std::string::String::from_utf8(bytes).unwrap();
+        let lines = text.lines().collect::<std::vec::Vec<_>>();
+        assert_eq!(lines.len(), THREADS * LINES);
+
+        // Each thread's lines are complete, and in the order they were written
+        for t in 0..THREADS {
+            let expected = (0..LINES).map(|i| std::format!("thread {t} line {i}")).collect::<std::vec::Vec<_>>();
+            let prefix = std::format!("thread {t} ");
+            let actual = lines.iter().filter(|l| l.starts_with(&prefix)).copied().collect::<std::vec::Vec<_>>();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[cfg(feature = "observer")]
+    #[test]
+    fn test_observer() {
+        use super::QueueEvent;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        const THREADS: usize = 4;
+        const PUSHES: usize = 100;
+
+        let pushed = Arc::new(AtomicUsize::new(0));
+        let chopped = Arc::new(AtomicUsize::new(0));
+        let mut queue = FillQueue::<usize>::new();
+        queue.push(0);
+
+        queue.set_observer({
+            let pushed = pushed.clone();
+            let chopped = chopped.clone();
+            move |event| match event {
+                QueueEvent::Pushed => drop(pushed.fetch_add(1, Ordering::Relaxed)),
+                QueueEvent::Chopped(count) => drop(chopped.fetch_add(count, Ordering::Relaxed)),
+            }
+        });
+
+        std::thread::scope(|s| {
+            for _ in 0..THREADS {
+                s.spawn(|| (0..PUSHES).for_each(|i| queue.push(i)));
+            }
+        });
+        assert_eq!(queue.extend([1, 2, 3]), 3);
+        assert_eq!(pushed.load(Ordering::Relaxed), THREADS * PUSHES + 3);
+
+        assert_eq!(queue.chop().count(), THREADS * PUSHES + 4);
+        assert_eq!(chopped.load(Ordering::Relaxed), THREADS * PUSHES + 4);
+
+        queue.push_mut(1);
+        drop(queue.chop_mut());
+        assert_eq!(pushed.load(Ordering::Relaxed), THREADS * PUSHES + 4);
+        assert_eq!(chopped.load(Ordering::Relaxed), THREADS * PUSHES + 5);
+
+        // The observer can use the queue without deadlocking
+        let queue = Arc::new(FillQueue::<usize>::new());
+        queue.set_observer({
+            let queue = Arc::downgrade(&queue);
+            move |event| {
+                if let (QueueEvent::Pushed, Some(queue)) = (event, queue.upgrade()) {
+                    let _ = queue.chop();
+                }
+            }
+        });
+        queue.push(1);
+        assert!(queue.is_empty());
+
+        queue.clear_observer();
+        queue.push(2);
+        assert_eq!(queue.chop().collect::<std::vec::Vec<_>>(), [2]);
+    }
+
+    #[test]
+    fn test_extend_trait() {
+        let mut fill_queue = (0..10).collect::<FillQueue<_>>();
+        assert_eq!(fill_queue.len(), 10);
+
+        Extend::extend(&mut fill_queue, 10..15);
+        assert_eq!(fill_queue.len(), 15);
+
+        let chopped = fill_queue.chop_mut().collect::<alloc::vec::Vec<_>>();
+        assert_eq!(chopped, (0..15).rev().collect::<alloc::vec::Vec<_>>());
+        assert!(core::iter::empty::<i32>().collect::<FillQueue<_>>().is_empty());
+    }
+
+    #[test]
+    fn test_many_zst_elements() {
+        const COUNT: usize = if cfg!(miri) { 1_000 } else { 1_000_000 };
+
+        let mut fill_queue = FillQueue::new();
+        (0..COUNT / 2).for_each(|_| fill_queue.push(()));
+        assert_eq!(fill_queue.extend(core::iter::repeat_n((), COUNT / 2)), COUNT / 2);
+        assert_eq!(fill_queue.len(), COUNT);
+
+        // Chopping is iterative, so long chains don't exhaust the stack
+        assert_eq!(fill_queue.chop().count(), COUNT);
+        assert_eq!(fill_queue.len(), 0);
+
+        (0..COUNT).for_each(|_| fill_queue.push_mut(()));
+        drop(fill_queue.chop_mut());
+        assert!(fill_queue.is_empty());
+        assert_eq!(fill_queue.len(), 0);
+    }
+
+    #[test]
+    fn test_chop_fifo() {
+        let queue = FillQueue::new();
+        assert_eq!(queue.chop_fifo().next(), None);
+
+        (0..10).for_each(|i| queue.push(i));
+        let mut iter = queue.chop_fifo();
+        assert_eq!(queue.len(), 10);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(queue.len(), 9);
+        assert_eq!(iter.collect::<alloc::vec::Vec<_>>(), (1..10).collect::<alloc::vec::Vec<_>>());
+
+        let mut queue = queue;
+        queue.push_mut(1);
+        assert_eq!(queue.chop_fifo_mut().collect::<alloc::vec::Vec<_>>(), [1]);
+        (2..5).for_each(|i| queue.push_mut(i));
+        assert_eq!(queue.chop_fifo_mut().collect::<alloc::vec::Vec<_>>(), [2, 3, 4]);
+    }
+
+    #[test]
+    fn test_retain_unchecked() {
+        let queue = FillQueue::new();
+        (0..10).for_each(|i| queue.push(i));
+
+        let mut visited = alloc::vec::Vec::new();
+        unsafe {
+            queue.retain_unchecked(|x| {
+                visited.push(*x);
+                *x % 3 != 0
+            })
+        };
+
+        assert_eq!(visited, (0..10).rev().collect::<alloc::vec::Vec<_>>());
+        assert_eq!(queue.len(), 6);
+        assert_eq!(queue.chop().collect::<alloc::vec::Vec<_>>(), [8, 7, 5, 4, 2, 1]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_retain_unchecked_concurrent_pushes() {
+        const THREADS: usize = 4;
+        #[cfg(miri)]
+        const RUNS: usize = 10;
+        #[cfg(not(miri))]
+        const RUNS: usize = 1000;
+
+        let queue = FillQueue::new();
+        std::thread::scope(|s| {
+            for _ in 0..THREADS {
+                s.spawn(|| (0..RUNS).for_each(|i| queue.push(i)));
+            }
+
+            // Removing the latest node races with the pushes on top of it
+            for _ in 0..RUNS {
+                unsafe { queue.retain_unchecked(|x| *x % 2 == 0) }
+            }
+        });
+
+        unsafe { queue.retain_unchecked(|x| *x % 2 == 0) }
+        let mut values = queue.chop().collect::<alloc::vec::Vec<_>>();
+        assert_eq!(queue.len(), 0);
+
+        values.sort_unstable();
+        let mut expected = (0..RUNS).filter(|x| x % 2 == 0).flat_map(|x| [x; THREADS]).collect::<alloc::vec::Vec<_>>();
+        expected.sort_unstable();
+        assert_eq!(values, expected);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_chop_fifo_keeps_submission_order() {
+        const THREADS: usize = 4;
+        const PUSHES: usize = 1000;
+
+        let queue = FillQueue::new();
+        let mut chopped = alloc::vec::Vec::new();
+        std::thread::scope(|s| {
+            for t in 0..THREADS {
+                let queue = &queue;
+                s.spawn(move || (0..PUSHES).for_each(|i| queue.push((t, i))));
+            }
+
+            // Chop while the producers may still be linking their nodes
+            for _ in 0..10 {
+                chopped.extend(queue.chop_fifo());
+                std::thread::yield_now();
+            }
+        });
+        chopped.extend(queue.chop_fifo());
+
+        // Every producer's elements come out in the order they were pushed
+        assert_eq!(chopped.len(), THREADS * PUSHES);
+        for t in 0..THREADS {
+            let pushed = chopped.iter().filter(|(x, _)| *x == t).map(|(_, i)| *i).collect::<alloc::vec::Vec<_>>();
+            assert_eq!(pushed, (0..PUSHES).collect::<alloc::vec::Vec<_>>());
+        }
+    }
+
+    struct DropCounter<'a>(&'a core::sync::atomic::AtomicUsize);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_drops_exactly_once() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let drops = AtomicUsize::new(0);
+        let fill_queue = FillQueue::new();
+        (0..10).for_each(|_| fill_queue.push(DropCounter(&drops)));
+
+        // Consumed values are dropped by the caller
+        let mut chop_iter = fill_queue.chop();
+        let first = chop_iter.next();
+        assert_eq!(drops.load(Ordering::Relaxed), 0);
+        drop(first);
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+
+        // Remaining values are dropped by the iterator
+        drop(chop_iter);
+        assert_eq!(drops.load(Ordering::Relaxed), 10);
+
+        let mut fill_queue = fill_queue;
+        (0..10).for_each(|_| fill_queue.push_mut(DropCounter(&drops)));
+        fill_queue.chop_mut().take(5).for_each(drop);
+        assert_eq!(drops.load(Ordering::Relaxed), 20);
+        assert!(fill_queue.is_empty());
+
+        // FIFO chops free the nodes they don't yield too
+        (0..10).for_each(|_| fill_queue.push(DropCounter(&drops)));
+        fill_queue.chop_fifo().take(3).for_each(drop);
+        (0..10).for_each(|_| fill_queue.push_mut(DropCounter(&drops)));
+        drop(fill_queue.chop_fifo_mut());
+        assert_eq!(drops.load(Ordering::Relaxed), 40);
+        assert_eq!(fill_queue.len(), 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_concurrent_drops_exactly_once() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        #[cfg(miri)]
+        const RUNS: usize = 10;
+        #[cfg(not(miri))]
+        const RUNS: usize = 100;
+
+        let drops = AtomicUsize::new(0);
+        let fill_queue = FillQueue::new();
+
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..RUNS {
+                        fill_queue.push(DropCounter(&drops));
+                    }
+                    fill_queue.chop().step_by(2).for_each(drop);
+                });
+            }
+        });
+
+        assert_eq!(drops.load(Ordering::Relaxed), 4 * RUNS);
+    }
+
     #[cfg(feature = "std")]
     #[test]
     fn test_concurrent_fill_queue() {
@@ -551,4 +1536,111 @@ mod tests {
 
         assert_eq!(*count.get_mut(), 100);
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_concurrent_len() {
+        use core::sync::atomic::{AtomicBool, Ordering};
+
+        const THREADS: usize = 4;
+        #[cfg(miri)]
+        const RUNS: usize = 10;
+        #[cfg(not(miri))]
+        const RUNS: usize = 1000;
+
+        let fill_queue = FillQueue::new();
+        let done = AtomicBool::new(false);
+
+        std::thread::scope(|s| {
+            let handles = (0..THREADS)
+                .map(|_| {
+                    s.spawn(|| {
+                        for i in 0..RUNS {
+                            fill_queue.push(i);
+                            if i % 3 == 0 {
+                                // Partially consumed chops still uncount every element they took
+                                let _ = fill_queue.chop().next();
+                            }
+                        }
+                    })
+                })
+                .collect::<alloc::vec::Vec<_>>();
+
+            s.spawn(|| {
+                while !done.load(Ordering::Relaxed) {
+                    // The counter never wraps below zero
+                    assert!(fill_queue.len() <= THREADS * RUNS);
+                }
+            });
+
+            for handle in handles {
+                handle.join().unwrap();
+            }
+            done.store(true, Ordering::Relaxed);
+        });
+
+        let len = fill_queue.len();
+        assert_eq!(fill_queue.chop().count(), len);
+        assert_eq!(fill_queue.len(), 0);
+    }
+
+    #[cfg(feature = "alloc_api")]
+    mod custom_allocator {
+        use super::{DropCounter, FillQueue};
+        use core::{
+            alloc::{AllocError, Allocator, Layout},
+            ptr::NonNull,
+            sync::atomic::{AtomicUsize, Ordering},
+        };
+        use std::alloc::System;
+
+        /// Allocator that fails once `remaining` allocations have been made, keeping count of the live ones.
+        struct FailingAlloc {
+            remaining: AtomicUsize,
+            live: AtomicUsize,
+        }
+
+        unsafe impl Allocator for FailingAlloc {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.remaining
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| x.checked_sub(1))
+                    .map_err(|_| AllocError)?;
+                let ptr = System.allocate(layout)?;
+                self.live.fetch_add(1, Ordering::Relaxed);
+                Ok(ptr)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                self.live.fetch_sub(1, Ordering::Relaxed);
+                System.deallocate(ptr, layout)
+            }
+        }
+
+        #[test]
+        fn test_try_extend_cleans_up() {
+            let alloc = FailingAlloc {
+                remaining: AtomicUsize::new(4),
+                live: AtomicUsize::new(0),
+            };
+            let drops = AtomicUsize::new(0);
+            let fill_queue = FillQueue::new_in(&alloc);
+
+            let values = (0..10).map(|_| DropCounter(&drops)).collect::<Vec<_>>();
+            assert!(fill_queue.try_extend(values).is_err());
+            assert!(fill_queue.is_empty());
+            assert_eq!(fill_queue.len(), 0);
+            assert_eq!(alloc.live.load(Ordering::Relaxed), 0);
+            assert_eq!(drops.load(Ordering::Relaxed), 10);
+
+            alloc.remaining.store(3, Ordering::Relaxed);
+            let values = (0..3).map(|_| DropCounter(&drops)).collect::<Vec<_>>();
+            assert_eq!(fill_queue.try_extend(values), Ok(3));
+            assert_eq!(alloc.live.load(Ordering::Relaxed), 3);
+            assert_eq!(drops.load(Ordering::Relaxed), 10);
+
+            assert_eq!(fill_queue.chop().count(), 3);
+            assert_eq!(alloc.live.load(Ordering::Relaxed), 0);
+            assert_eq!(drops.load(Ordering::Relaxed), 13);
+        }
+    }
 }
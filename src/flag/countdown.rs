@@ -0,0 +1,310 @@
+use crate::{
+    locks::{lock, Lock},
+    FillQueue,
+};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use docfg::docfg;
+
+/// A flag that completes after being counted down a fixed number of times.
+///
+/// Unlike [`mpmc::Flag`](super::mpmc::Flag), which completes when all it's clones have been dropped,
+/// this flag keeps an explicit counter, which makes it a better fit for "wait for N tasks" patterns.
+///
+/// # Example
+///
+/// ```rust
+/// use utils_atomics::flag::countdown::CountdownFlag;
+///
+/// let flag = CountdownFlag::new(4);
+///
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| flag.count_down());
+///     }
+///     flag.wait();
+/// });
+///
+/// assert!(flag.is_marked());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+pub struct CountdownFlag {
+    count: AtomicUsize,
+    wakers: FillQueue<Lock>,
+    #[cfg(feature = "futures")]
+    async_wakers: FillQueue<core::task::Waker>,
+}
+
+impl CountdownFlag {
+    /// Creates a new flag that will complete after `n` calls to [`count_down`](CountdownFlag::count_down).
+    /// If `n` is zero, the flag starts completed.
+    #[inline]
+    pub const fn new(n: usize) -> Self {
+        Self {
+            count: AtomicUsize::new(n),
+            wakers: FillQueue::new(),
+            #[cfg(feature = "futures")]
+            async_wakers: FillQueue::new(),
+        }
+    }
+
+    /// Returns the amount of calls to [`count_down`](CountdownFlag::count_down) left for the flag to complete.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if the flag has been completed, and `false` otherwise
+    #[inline]
+    pub fn is_marked(&self) -> bool {
+        self.count() == 0
+    }
+
+    /// Decrements the flag's counter, waking all it's subscribers if it reaches zero.
+    ///
+    /// Returns `true` if this call completed the flag. Counting down a completed flag has no effect.
+    pub fn count_down(&self) -> bool {
+        match self
+            .count
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |x| x.checked_sub(1))
+        {
+            Ok(1) => {
+                self.wakers.chop().for_each(Lock::wake);
+                #[cfg(feature = "futures")]
+                self.async_wakers.chop().for_each(core::task::Waker::wake);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Blocks the current thread until the flag gets completed.
+    pub fn wait(&self) {
+        if self.is_marked() {
+            return;
+        }
+
+        let (lock, sub) = lock();
+        self.wakers.push(lock);
+
+        // The flag may have been completed before our waker was registered
+        if self.is_marked() {
+            return;
+        }
+        sub.wait();
+
+        // Parking may wake up spuriously, but the registered lock still wakes this thread, so it isn't registered again
+        #[cfg(feature = "std")]
+        while !self.is_marked() {
+            std::thread::park();
+        }
+    }
+
+    /// Blocks the current thread until the flag gets completed or the timeout expires.
+    ///
+    /// # Errors
+    /// This method returns an error if the wait didn't conclude before the specified duration
+    #[docfg(feature = "std")]
+    pub fn wait_timeout(&self, dur: core::time::Duration) -> Result<(), crate::TimeoutElapsed> {
+        let start = std::time::Instant::now();
+        let deadline = start + dur;
+        if self.is_marked() {
+            return Ok(());
+        }
+
+        // The lock wakes this thread, so registering it once covers every park below
+        let (lock, _) = lock();
+        self.wakers.push(lock);
+
+        while !self.is_marked() {
+            let now = std::time::Instant::now();
+            if now >= deadline {
+                return Err(crate::TimeoutElapsed::since(start, dur));
+            }
+            std::thread::park_timeout(deadline - now);
+        }
+        return Ok(());
+    }
+
+    /// Returns a future that resolves when the flag gets completed.
+    #[docfg(feature = "futures")]
+    #[inline]
+    pub fn wait_async(&self) -> CountdownSubscribe<'_> {
+        CountdownSubscribe {
+            flag: self,
+            waker: None,
+        }
+    }
+}
+
+impl Drop for CountdownFlag {
+    #[inline]
+    fn drop(&mut self) {
+        // Any remaining waker belongs to a subscriber that is no longer waiting
+        self.wakers.chop_mut().for_each(Lock::silent_drop);
+        #[cfg(feature = "futures")]
+        self.async_wakers.chop_mut().for_each(core::mem::drop);
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "futures")] {
+        use core::{future::{Future, IntoFuture}, task::{Poll, Waker}};
+
+        /// Future returned by [`CountdownFlag::wait_async`]
+        #[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "futures"))))]
+        #[derive(Debug, Clone)]
+        pub struct CountdownSubscribe<'a> {
+            flag: &'a CountdownFlag,
+            // The last waker registered by this future
+            waker: Option<Waker>,
+        }
+
+        impl Future for CountdownSubscribe<'_> {
+            type Output = ();
+
+            #[inline]
+            fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+                if self.flag.is_marked() {
+                    return Poll::Ready(())
+                }
+
+                // Registered wakers are only released when the flag completes, so the same one isn't registered twice
+                if self.waker.as_ref().is_some_and(|x| x.will_wake(cx.waker())) {
+                    return Poll::Pending
+                }
+
+                self.waker = Some(cx.waker().clone());
+                self.flag.async_wakers.push(cx.waker().clone());
+                if self.flag.is_marked() {
+                    return Poll::Ready(())
+                }
+                return Poll::Pending
+            }
+        }
+
+        impl<'a> IntoFuture for &'a CountdownFlag {
+            type Output = ();
+            type IntoFuture = CountdownSubscribe<'a>;
+
+            #[inline]
+            fn into_future(self) -> Self::IntoFuture {
+                self.wait_async()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CountdownFlag;
+
+    #[test]
+    fn test_count_down() {
+        let flag = CountdownFlag::new(2);
+        assert_eq!(flag.count(), 2);
+        assert!(!flag.count_down());
+        assert!(!flag.is_marked());
+        assert!(flag.count_down());
+        assert!(flag.is_marked());
+        assert!(!flag.count_down());
+        assert_eq!(flag.count(), 0);
+
+        CountdownFlag::new(0).wait();
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_stressed_conditions() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use core::time::Duration;
+
+        const THREADS: usize = 10;
+
+        let flag = CountdownFlag::new(THREADS);
+        let counted = AtomicUsize::new(0);
+
+        std::thread::scope(|s| {
+            let waiters = (0..THREADS)
+                .map(|_| {
+                    s.spawn(|| {
+                        flag.wait();
+                        assert_eq!(counted.load(Ordering::Acquire), THREADS);
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            for _ in 0..THREADS {
+                s.spawn(|| {
+                    std::thread::sleep(Duration::from_millis(50));
+                    counted.fetch_add(1, Ordering::AcqRel);
+                    flag.count_down();
+                });
+            }
+
+            for handle in waiters {
+                handle.join().unwrap();
+            }
+        });
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_wait_timeout() {
+        use core::time::Duration;
+
+        let flag = CountdownFlag::new(1);
+        let err = flag.wait_timeout(Duration::from_millis(100)).unwrap_err();
+        assert_eq!(err.requested, Duration::from_millis(100));
+        // The flag keeps waiting until the deadline, even if woken up early
+        assert!(err.elapsed >= err.requested);
+        assert!(err.elapsed < Duration::from_secs(10));
+        flag.count_down();
+        assert!(flag.wait_timeout(Duration::from_millis(100)).is_ok());
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_repeated_polls_register_once() {
+        use core::{future::Future, pin::Pin, task::Context};
+
+        let flag = CountdownFlag::new(1);
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut fut = flag.wait_async();
+        for _ in 0..10 {
+            assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+        }
+        assert_eq!(flag.async_wakers.len(), 1);
+
+        flag.count_down();
+        assert!(Pin::new(&mut fut).poll(&mut cx).is_ready());
+    }
+
+    #[cfg(feature = "futures")]
+    #[tokio::test]
+    async fn test_async_count_down() {
+        use std::sync::Arc;
+
+        const TASKS: usize = 10;
+
+        let flag = Arc::new(CountdownFlag::new(TASKS));
+        let handles = (0..TASKS)
+            .map(|_| {
+                let flag = flag.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(core::time::Duration::from_millis(50)).await;
+                    flag.count_down();
+                })
+            })
+            .collect::<Vec<_>>();
+
+        (&*flag).await;
+        assert!(flag.is_marked());
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    }
+}
@@ -3,3 +3,6 @@ pub mod mpmc;
 
 /// Multiple producer - Single consumer flag. Can also be used as a SPSC flag
 pub mod mpsc;
+
+/// Flag that completes after being counted down a fixed number of times
+pub mod countdown;
@@ -24,13 +24,20 @@ pub struct Subscribe {
 }
 
 impl Flag {
-    /// See [`Arc::into_raw`]
+    /// Consumes the flag, returning a pointer to it's queue of waiters. See [`Arc::into_raw`]
+    ///
+    /// # Safety
+    /// The pointer must be converted back into a [`Flag`] with [`from_raw`](Flag::from_raw) for it to be completed.
+    /// The queue mustn't be modified through the pointer.
     #[inline]
     pub unsafe fn into_raw(self) -> *const FillQueue<Lock> {
         Arc::into_raw(self.inner).cast()
     }
 
-    /// See [`Arc::from_raw`]
+    /// Constructs a flag from a pointer returned by [`into_raw`](Flag::into_raw). See [`Arc::from_raw`]
+    ///
+    /// # Safety
+    /// `ptr` must have been returned by [`Flag::into_raw`], and can only be converted back once per call to it.
     #[inline]
     pub unsafe fn from_raw(ptr: *const FillQueue<Lock>) -> Self {
         Self {
@@ -64,13 +71,71 @@ impl Subscribe {
     }
 
     /// Blocks the current thread until the flag gets marked.
+    ///
+    /// Returns `true` if the thread actually blocked, and `false` if the flag was already marked, in which case it returns immediately.
     #[inline]
-    pub fn wait(self) {
+    pub fn wait(self) -> bool {
         if let Some(queue) = self.inner.upgrade() {
             let (waker, sub) = lock();
             queue.0.push(waker);
             drop(queue);
-            sub.wait()
+            sub.wait();
+            return true;
+        }
+        return false;
+    }
+
+    /// Blocks the current thread for as long as `condition` returns `true` and the flag isn't completed, without consuming the subscriber.
+    ///
+    /// `condition` is checked before blocking, right after the thread registers itself and after every wake-up, so spurious wake-ups are ignored.
+    /// Returns `true` once `condition` returns `false`, or `false` if the flag got completed while `condition` still held.
+    ///
+    /// Once blocked, `condition` is only checked again when the flag completes (or the thread wakes up spuriously). Flags complete
+    /// once every reference to them has been marked or dropped, so neither the marks before the last one nor changes to the state
+    /// checked by `condition` wake the thread. To re-check the state on every notification, use a [`Listener`](crate::notify::Listener)
+    /// and it's [`recv_while`](crate::notify::Listener::recv_while) method instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::flag::mpmc::flag;
+    /// use core::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// let (flag, sub) = flag();
+    /// let count = AtomicUsize::new(0);
+    ///
+    /// std::thread::scope(|s| {
+    ///     for _ in 0..3 {
+    ///         let flag = flag.clone();
+    ///         let count = &count;
+    ///         s.spawn(move || {
+    ///             count.fetch_add(1, Ordering::AcqRel);
+    ///             flag.mark();
+    ///         });
+    ///     }
+    ///     drop(flag);
+    ///
+    ///     assert!(sub.wait_while(|| count.load(Ordering::Acquire) < 3));
+    /// });
+    /// ```
+    pub fn wait_while<F: FnMut() -> bool>(&self, mut condition: F) -> bool {
+        loop {
+            if !condition() {
+                return true;
+            }
+
+            let Some(queue) = self.inner.upgrade() else {
+                // The state may have changed right before the flag was completed
+                return !condition();
+            };
+
+            let (waker, sub) = lock();
+            queue.0.push(waker);
+            drop(queue);
+
+            if !condition() {
+                return true;
+            }
+            sub.wait();
         }
     }
 
@@ -80,7 +145,8 @@ impl Subscribe {
     /// This method returns an error if the wait didn't conclude before the specified duration
     #[docfg(feature = "std")]
     #[inline]
-    pub fn wait_timeout(self, dur: core::time::Duration) -> Result<(), crate::Timeout> {
+    pub fn wait_timeout(self, dur: core::time::Duration) -> Result<(), crate::TimeoutElapsed> {
+        let start = std::time::Instant::now();
         if let Some(queue) = self.inner.upgrade() {
             let (waker, sub) = lock();
             queue.0.push(waker);
@@ -88,7 +154,7 @@ impl Subscribe {
             sub.wait_timeout(dur);
             return match self.is_marked() {
                 true => Ok(()),
-                false => Err(crate::Timeout),
+                false => Err(crate::TimeoutElapsed::since(start, dur)),
             };
         }
         return Ok(());
@@ -105,6 +171,7 @@ pub fn flag() -> (Flag, Subscribe) {
     (Flag { inner: flag }, Subscribe { inner: sub })
 }
 
+// `Flag::into_raw` relies on the queue having the same layout as `FillQueue<Lock>`
 #[repr(transparent)]
 #[derive(Debug)]
 struct FlagQueue(pub FillQueue<Lock>);
@@ -114,7 +181,7 @@ impl FlagQueue {
     pub fn silent_drop(self) {
         let mut this = ManuallyDrop::new(self);
         this.0.chop_mut().for_each(Lock::silent_drop);
-        unsafe { core::ptr::drop_in_place(&mut this) };
+        unsafe { core::ptr::drop_in_place(core::ptr::addr_of_mut!(this)) };
     }
 }
 
@@ -128,6 +195,7 @@ impl Drop for FlagQueue {
 cfg_if::cfg_if! {
     if #[cfg(feature = "futures")] {
         use core::{future::Future, task::{Waker, Poll}};
+        use alloc::task::Wake;
         use futures::future::FusedFuture;
 
         /// Creates a new pair of [`AsyncFlag`] and [`AsyncSubscribe`]
@@ -147,13 +215,20 @@ cfg_if::cfg_if! {
         }
 
         impl AsyncFlag {
-            /// See [`Arc::into_raw`]
+            /// Consumes the flag, returning a pointer to it's queue of wakers. See [`Arc::into_raw`]
+            ///
+            /// # Safety
+            /// The pointer must be converted back into an [`AsyncFlag`] with [`from_raw`](AsyncFlag::from_raw) for it to be completed.
+            /// The queue mustn't be modified through the pointer.
             #[inline]
             pub unsafe fn into_raw (self) -> *const FillQueue<Waker> {
                 Arc::into_raw(self.inner).cast()
             }
 
-            /// See [`Arc::from_raw`]
+            /// Constructs a flag from a pointer returned by [`into_raw`](AsyncFlag::into_raw). See [`Arc::from_raw`]
+            ///
+            /// # Safety
+            /// `ptr` must have been returned by [`AsyncFlag::into_raw`], and can only be converted back once per call to it.
             #[inline]
             pub unsafe fn from_raw (ptr: *const FillQueue<Waker>) -> Self {
                 Self { inner: Arc::from_raw(ptr.cast()) }
@@ -176,6 +251,37 @@ cfg_if::cfg_if! {
                 }
             }
 
+            /// Registers `w` to be woken when the flag is completed, bridging the flag with custom wake logic.
+            ///
+            /// If the flag is dropped silently, `w` will be dropped without being woken.
+            /// Since [`Wake`] isn't object safe, `w` is taken as a concrete [`Arc`], which coerces into the stored [`Waker`].
+            ///
+            /// # Example
+            /// ```rust
+            /// use utils_atomics::flag::mpmc::async_flag;
+            /// use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+            /// use std::task::Wake;
+            ///
+            /// struct Woken (AtomicBool);
+            ///
+            /// impl Wake for Woken {
+            ///     fn wake (self: Arc<Self>) {
+            ///         self.0.store(true, Ordering::Release);
+            ///     }
+            /// }
+            ///
+            /// let (flag, _) = async_flag();
+            /// let woken = Arc::new(Woken(AtomicBool::new(false)));
+            ///
+            /// flag.subscribe_with_wake(woken.clone());
+            /// flag.mark();
+            /// assert!(woken.0.load(Ordering::Acquire));
+            /// ```
+            #[inline]
+            pub fn subscribe_with_wake<W: 'static + Wake + Send + Sync> (&self, w: Arc<W>) {
+                self.inner.0.push(Waker::from(w));
+            }
+
             /// Drops the flag without **notifying** it as completed.
             /// This method may leak memory.
             #[inline]
@@ -233,6 +339,8 @@ cfg_if::cfg_if! {
             }
         }
 
+        // `AsyncFlag::into_raw` relies on the queue having the same layout as `FillQueue<Waker>`
+        #[repr(transparent)]
         #[derive(Debug)]
         struct AsyncFlagQueue (pub FillQueue<Waker>);
 
@@ -241,7 +349,7 @@ cfg_if::cfg_if! {
             pub fn silent_drop (self) {
                 let mut this = ManuallyDrop::new(self);
                 let _: crate::prelude::ChopIter<Waker> = this.0.chop_mut();
-                unsafe { core::ptr::drop_in_place(&mut this.0) }
+                unsafe { core::ptr::drop_in_place(core::ptr::addr_of_mut!(this.0)) }
             }
         }
 
@@ -276,7 +384,87 @@ mod tests {
             f.mark();
         });
 
-        s.wait();
+        assert!(s.wait());
+    }
+
+    #[test]
+    fn test_wait_marked() {
+        let (f, s) = flag();
+        let s2 = s.clone();
+        f.mark();
+        assert!(!s.wait());
+        assert!(!s2.wait());
+    }
+
+    #[test]
+    fn test_wait_while() {
+        use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let (f, s) = flag();
+        let count = Arc::new(AtomicUsize::new(0));
+        let markers = (0..4)
+            .map(|_| {
+                let f = f.clone();
+                let count = count.clone();
+                thread::spawn(move || {
+                    thread::sleep(Duration::from_millis(20));
+                    count.fetch_add(1, Ordering::AcqRel);
+                    f.mark();
+                })
+            })
+            .collect::<Vec<_>>();
+        drop(f);
+
+        // Only the last mark wakes the thread, and by then the condition no longer holds
+        assert!(s.wait_while(|| count.load(Ordering::Acquire) < 4));
+        assert!(s.is_marked());
+        for marker in markers {
+            marker.join().unwrap();
+        }
+
+        let (f, s) = flag();
+        let ready = Arc::new(AtomicBool::new(false));
+        let waiter = {
+            let (s, ready) = (s.clone(), ready.clone());
+            thread::spawn(move || s.wait_while(|| !ready.load(Ordering::Acquire)))
+        };
+        let stuck = {
+            let s = s.clone();
+            thread::spawn(move || s.wait_while(|| true))
+        };
+
+        // Changes to the state go unnoticed until the flag completes
+        thread::sleep(Duration::from_millis(50));
+        ready.store(true, Ordering::Release);
+        thread::sleep(Duration::from_millis(50));
+        assert!(!waiter.is_finished());
+
+        // The subscriber isn't consumed, and can keep waiting on new conditions
+        assert!(!s.is_marked());
+        assert!(s.wait_while(|| false));
+
+        f.mark();
+        assert!(waiter.join().unwrap());
+        assert!(!stuck.join().unwrap());
+        assert!(!s.wait_while(|| true));
+    }
+
+    #[test]
+    fn test_raw_round_trip() {
+        let (f, s) = flag();
+        let g = f.clone();
+
+        let ptr = unsafe { Flag::into_raw(f) };
+        assert!(unsafe { &*ptr }.is_empty());
+
+        let f = unsafe { Flag::from_raw(ptr) };
+        assert!(f.has_subscriber());
+
+        drop(g);
+        assert!(!s.is_marked());
+        f.mark();
+        assert!(s.is_marked());
     }
 
     #[test]
@@ -288,8 +476,9 @@ mod tests {
         std::thread::sleep(Duration::from_millis(200));
         f.silent_drop();
 
-        let time = handle.join().unwrap();
-        assert!(time.is_err());
+        let err = handle.join().unwrap().unwrap_err();
+        assert_eq!(err.requested, Duration::from_millis(100));
+        assert!(err.elapsed > Duration::ZERO);
     }
 
     #[test]
@@ -349,6 +538,24 @@ mod async_tests {
         assert_eq!(s.is_marked(), true);
     }
 
+    #[tokio::test]
+    async fn test_async_raw_round_trip() {
+        let (f, s) = async_flag();
+        let g = f.clone();
+
+        let ptr = unsafe { AsyncFlag::into_raw(f) };
+        assert!(unsafe { &*ptr }.is_empty());
+
+        let f = unsafe { AsyncFlag::from_raw(ptr) };
+        assert!(f.has_subscriber());
+
+        drop(g);
+        assert!(!s.is_marked());
+        f.mark();
+        assert!(s.is_marked());
+        s.await;
+    }
+
     #[tokio::test]
     async fn test_silent_drop() {
         let (f, s) = async_flag();
@@ -370,6 +577,41 @@ mod async_tests {
         }
     }
 
+    #[test]
+    fn test_subscribe_with_wake() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+        use std::task::Wake;
+
+        struct CountWake(AtomicUsize);
+
+        impl Wake for CountWake {
+            fn wake(self: Arc<Self>) {
+                self.0.fetch_add(1, Ordering::AcqRel);
+            }
+        }
+
+        let (f, s) = async_flag();
+        let count = Arc::new(CountWake(AtomicUsize::new(0)));
+
+        f.subscribe_with_wake(count.clone());
+        f.clone().subscribe_with_wake(count.clone());
+        assert_eq!(count.0.load(Ordering::Acquire), 0);
+
+        f.mark();
+        assert!(s.is_marked());
+        assert_eq!(count.0.load(Ordering::Acquire), 2);
+        // Wakers are released once woken
+        assert_eq!(Arc::strong_count(&count), 1);
+
+        let (f, _) = async_flag();
+        f.subscribe_with_wake(count.clone());
+        f.silent_drop();
+        assert_eq!(count.0.load(Ordering::Acquire), 2);
+    }
+
     #[tokio::test]
     async fn test_async_stressed_conditions() {
         let (f, s) = async_flag();
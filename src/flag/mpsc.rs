@@ -1,6 +1,14 @@
 use crate::locks::{lock, Lock};
-use alloc::sync::{Arc, Weak};
-use core::{cell::UnsafeCell, fmt::Debug};
+use alloc::{
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use crate::TakeCell;
+use core::{
+    cell::UnsafeCell,
+    fmt::Debug,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 use docfg::docfg;
 
 /// Creates a new pair of [`Flag`] and [`Subscribe`]
@@ -8,6 +16,8 @@ use docfg::docfg;
 pub fn flag() -> (Flag, Subscribe) {
     let waker = FlagWaker {
         waker: UnsafeCell::new(None),
+        #[cfg(feature = "futures")]
+        async_waker: UnsafeCell::new(None),
     };
 
     let flag = Arc::new(waker);
@@ -15,6 +25,87 @@ pub fn flag() -> (Flag, Subscribe) {
     (Flag { inner: flag }, Subscribe { inner: sub })
 }
 
+/// Creates `n` [`MultiFlag`]s sharing a single [`Subscribe`], which completes once every one of them has been marked (or dropped).
+///
+/// Each flag is a separate marker for one of `n` parties, which makes fan-out/fan-in patterns clearer than cloning a single flag.
+/// If `n` is zero, the subscriber starts completed.
+///
+/// # Example
+/// ```rust
+/// use utils_atomics::flag::mpsc::multi_flag;
+///
+/// let (flags, sub) = multi_flag(4);
+///
+/// std::thread::scope(|s| {
+///     for flag in flags {
+///         s.spawn(move || flag.mark());
+///     }
+///     sub.wait();
+/// });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn multi_flag(n: usize) -> (Vec<MultiFlag>, Subscribe) {
+    let (flag, sub) = flag();
+    let shared = Arc::new(MultiFlagShared {
+        remaining: AtomicUsize::new(n),
+        flag: TakeCell::new(flag),
+    });
+
+    let flags = (0..n)
+        .map(|_| MultiFlag {
+            shared: shared.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    return (flags, sub);
+}
+
+/// One of the markers created by [`multi_flag`].
+///
+/// Unlike [`Flag`], markers can't be cloned, and the subscriber completes once every one of them is marked or dropped.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct MultiFlag {
+    shared: Arc<MultiFlagShared>,
+}
+
+struct MultiFlagShared {
+    remaining: AtomicUsize,
+    flag: TakeCell<Flag>,
+}
+
+impl MultiFlag {
+    /// Returns the amount of markers that haven't been marked or dropped yet, including this one
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        return self.shared.remaining.load(Ordering::Acquire);
+    }
+
+    /// Marks this marker as completed, consuming it
+    #[inline]
+    pub fn mark(self) {}
+}
+
+impl Drop for MultiFlag {
+    #[inline]
+    fn drop(&mut self) {
+        if self.shared.remaining.fetch_sub(1, Ordering::AcqRel) == 1 {
+            // Only the last marker gets here, so the flag is always available
+            if let Some(flag) = self.shared.flag.try_take() {
+                flag.mark();
+            }
+        }
+    }
+}
+
+impl Debug for MultiFlag {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MultiFlag")
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
 /// A flag type that completes when all it's references are marked or dropped.
 ///
 /// This flag drops loudly by default (a.k.a will complete when dropped),
@@ -34,13 +125,19 @@ pub struct Subscribe {
 }
 
 impl Flag {
-    /// See [`Arc::into_raw`]
+    /// Consumes the flag, returning an opaque pointer to it. See [`Arc::into_raw`]
+    ///
+    /// # Safety
+    /// The pointer must be converted back into a [`Flag`] with [`from_raw`](Flag::from_raw) for it to be completed.
     #[inline]
     pub unsafe fn into_raw(self) -> *const () {
         Arc::into_raw(self.inner).cast()
     }
 
-    /// See [`Arc::from_raw`]
+    /// Constructs a flag from a pointer returned by [`into_raw`](Flag::into_raw). See [`Arc::from_raw`]
+    ///
+    /// # Safety
+    /// `ptr` must have been returned by [`Flag::into_raw`], and can only be converted back once per call to it.
     #[inline]
     pub unsafe fn from_raw(ptr: *const ()) -> Self {
         Self {
@@ -57,6 +154,12 @@ impl Flag {
     #[inline]
     pub fn mark(self) {}
 
+    /// Returns `true` if `sub` is a subscriber of this flag
+    #[inline]
+    pub(crate) fn is_paired(&self, sub: &Subscribe) -> bool {
+        return core::ptr::eq(Arc::as_ptr(&self.inner), sub.inner.as_ptr());
+    }
+
     /// Drops the flag without **notifying** it as completed.
     /// This method may leak memory.
     #[inline]
@@ -65,6 +168,10 @@ impl Flag {
             if let Some(inner) = inner.waker.into_inner() {
                 inner.silent_drop();
             }
+            #[cfg(feature = "futures")]
+            if let Some(inner) = inner.async_waker.into_inner() {
+                inner.silent_drop();
+            }
         }
     }
 }
@@ -79,6 +186,12 @@ impl Subscribe {
     /// Blocks the current thread until the flag gets fully marked.
     #[inline]
     pub fn wait(self) {
+        self.wait_by_ref();
+    }
+
+    /// Blocks the current thread until the flag gets fully marked, without consuming the subscriber.
+    #[inline]
+    pub(crate) fn wait_by_ref(&self) {
         if let Some(queue) = self.inner.upgrade() {
             let (lock, sub) = lock();
             unsafe { *queue.waker.get() = Some(lock) }
@@ -93,7 +206,8 @@ impl Subscribe {
     /// This method returns an error if the wait didn't conclude before the specified duration
     #[docfg(feature = "std")]
     #[inline]
-    pub fn wait_timeout(&self, dur: core::time::Duration) -> Result<(), crate::Timeout> {
+    pub fn wait_timeout(&self, dur: core::time::Duration) -> Result<(), crate::TimeoutElapsed> {
+        let start = std::time::Instant::now();
         if let Some(queue) = self.inner.upgrade() {
             let (lock, sub) = lock();
             unsafe { *queue.waker.get() = Some(lock) }
@@ -101,15 +215,29 @@ impl Subscribe {
             sub.wait_timeout(dur);
             return match self.is_marked() {
                 true => Ok(()),
-                false => Err(crate::Timeout),
+                false => Err(crate::TimeoutElapsed::since(start, dur)),
             };
         }
         return Ok(());
     }
+
+    /// Converts this subscriber into an [`AsyncSubscribe`] that completes when the flag gets fully marked.
+    #[docfg(feature = "futures")]
+    #[inline]
+    pub fn into_async(self) -> AsyncSubscribe {
+        if let Some(queue) = self.inner.upgrade() {
+            let (flag, sub) = async_flag();
+            unsafe { *queue.async_waker.get() = Some(flag) }
+            return sub;
+        }
+        return AsyncSubscribe { inner: None };
+    }
 }
 
 struct FlagWaker {
     waker: UnsafeCell<Option<Lock>>,
+    #[cfg(feature = "futures")]
+    async_waker: UnsafeCell<Option<AsyncFlag>>,
 }
 
 impl Debug for FlagWaker {
@@ -151,13 +279,20 @@ cfg_if::cfg_if! {
         }
 
         impl AsyncFlag {
-            /// See [`Arc::into_raw`]
+            /// Consumes the flag, returning a pointer to it's waker slot. See [`Arc::into_raw`]
+            ///
+            /// # Safety
+            /// The pointer must be converted back into an [`AsyncFlag`] with [`from_raw`](AsyncFlag::from_raw) for it to be completed.
+            /// The slot is written by the subscriber without synchronization, so it mustn't be accessed through the pointer.
             #[inline]
             pub unsafe fn into_raw (self) -> *const Option<Waker> {
                 Arc::into_raw(self.inner).cast()
             }
 
-            /// See [`Arc::from_raw`]
+            /// Constructs a flag from a pointer returned by [`into_raw`](AsyncFlag::into_raw). See [`Arc::from_raw`]
+            ///
+            /// # Safety
+            /// `ptr` must have been returned by [`AsyncFlag::into_raw`], and can only be converted back once per call to it.
             #[inline]
             pub unsafe fn from_raw (ptr: *const Option<Waker>) -> Self {
                 Self { inner: Arc::from_raw(ptr.cast()) }
@@ -224,6 +359,8 @@ cfg_if::cfg_if! {
             }
         }
 
+        // `AsyncFlag::into_raw` relies on the waker having the same layout as `Option<Waker>`
+        #[repr(transparent)]
         struct AsyncFlagWaker {
             waker: UnsafeCell<Option<Waker>>
         }
@@ -232,7 +369,7 @@ cfg_if::cfg_if! {
             #[inline]
             pub fn silent_drop (self) {
                 let mut this = core::mem::ManuallyDrop::new(self);
-                unsafe { core::ptr::drop_in_place(&mut this.waker) }
+                unsafe { core::ptr::drop_in_place(core::ptr::addr_of_mut!(this.waker)) }
             }
         }
 
@@ -270,6 +407,42 @@ mod tests {
         drop(flag);
     }
 
+    #[test]
+    fn test_multi_flag() {
+        let (flags, sub) = multi_flag(5);
+        assert_eq!(flags.len(), 5);
+
+        let mut flags = flags.into_iter();
+        for (i, flag) in flags.by_ref().take(4).enumerate() {
+            assert!(!sub.is_marked());
+            assert_eq!(flag.remaining(), 5 - i);
+            flag.mark();
+        }
+
+        // Only the last marker completes the subscriber
+        assert!(!sub.is_marked());
+        let last = flags.next().unwrap();
+        assert_eq!(last.remaining(), 1);
+        drop(last);
+        assert!(sub.is_marked());
+
+        let (flags, sub) = multi_flag(0);
+        assert!(flags.is_empty());
+        assert!(sub.is_marked());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_multi_flag_threads() {
+        let (flags, sub) = multi_flag(8);
+        thread::scope(|s| {
+            for flag in flags {
+                s.spawn(move || flag.mark());
+            }
+            sub.wait();
+        });
+    }
+
     #[test]
     fn test_flag_mark() {
         let (flag, subscribe) = flag();
@@ -277,6 +450,18 @@ mod tests {
         assert!(subscribe.is_marked());
     }
 
+    #[test]
+    fn test_raw_round_trip() {
+        let (flag, subscribe) = flag();
+        let flag = unsafe { Flag::from_raw(Flag::into_raw(flag)) };
+        assert!(flag.has_subscriber());
+        assert!(!subscribe.is_marked());
+
+        flag.mark();
+        assert!(subscribe.is_marked());
+        subscribe.wait();
+    }
+
     #[cfg(feature = "std")]
     #[test]
     fn test_flag_silent_drop() {
@@ -357,6 +542,22 @@ mod tests {
             assert!(async_subscribe.is_marked());
         }
 
+        #[tokio::test]
+        async fn test_async_raw_round_trip() {
+            let (flag, subscribe) = async_flag();
+            let flag = unsafe { AsyncFlag::from_raw(AsyncFlag::into_raw(flag)) };
+            assert!(flag.has_subscriber());
+            assert!(!subscribe.is_marked());
+
+            let handle = tokio::spawn(async move {
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                flag.mark();
+            });
+
+            subscribe.await;
+            handle.await.unwrap();
+        }
+
         #[tokio::test]
         async fn test_flag_silent_drop() {
             use core::time::Duration;
@@ -12,6 +12,7 @@
 #![cfg_attr(test, allow(clippy::bool_assert_comparison))]
 /* */
 #![cfg_attr(feature = "nightly", feature(int_roundings, negative_impls, c_size_t))]
+#![cfg_attr(all(feature = "nightly", target_has_atomic = "128"), feature(integer_atomics))]
 #![cfg_attr(all(feature = "nightly", feature = "alloc"), feature(new_uninit))]
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(feature = "alloc_api", feature(allocator_api))]
@@ -73,14 +74,44 @@ cfg_if::cfg_if! {
         #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
         pub mod notify;
         mod cell;
+        mod slot;
+        mod condition;
+        mod tagged;
+        #[cfg(feature = "futures")]
+        mod async_semaphore;
+        #[cfg(feature = "std")]
+        mod rcu;
+        #[cfg(feature = "std")]
+        mod blocking_queue;
+        #[cfg(feature = "std")]
+        mod work_stealing;
         // #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
         // pub mod arc_cell;
         mod locks;
+        mod sync;
 
         #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
         pub use bitfield::AtomicBitBox;
         #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-        pub use cell::AtomicCell;
+        pub use cell::{take_all, AtomicCell, AtomicCellArray, RestoreGuard, SharedCell};
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        pub use slot::Slot;
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        pub use condition::Condition;
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        pub use tagged::{TaggedCell, TaggedPtr};
+        #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+        #[cfg(feature = "futures")]
+        pub use async_semaphore::{AsyncSemaphore, AsyncSemaphorePermit, OwnedPermit};
+        #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+        #[cfg(feature = "std")]
+        pub use rcu::{RcuCell, RcuGuard};
+        #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+        #[cfg(feature = "std")]
+        pub use blocking_queue::BlockingQueue;
+        #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+        #[cfg(feature = "std")]
+        pub use work_stealing::{WorkStealingQueue, Stealer};
         #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
         pub use fill_queue::FillQueue;
         #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
@@ -90,7 +121,7 @@ cfg_if::cfg_if! {
     }
 }
 
-flat_mod!(take);
+flat_mod!(take, padded);
 
 #[path = "trait.rs"]
 pub mod traits;
@@ -141,6 +172,68 @@ impl Display for Timeout {
 #[docfg(feature = "std")]
 impl std::error::Error for Timeout {}
 
+/// Error returned by the blocking `wait_timeout` methods when a timeout ocurrs before the main operation completes,
+/// with details on how long the operation actually waited.
+#[docfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeoutElapsed {
+    /// The timeout that was requested
+    pub requested: core::time::Duration,
+    /// The time that actually elapsed before the operation gave up
+    pub elapsed: core::time::Duration,
+}
+
+#[cfg(feature = "std")]
+impl TimeoutElapsed {
+    #[inline]
+    pub(crate) fn since(start: std::time::Instant, requested: core::time::Duration) -> Self {
+        return Self {
+            requested,
+            elapsed: start.elapsed(),
+        };
+    }
+}
+
+#[cfg(feature = "std")]
+impl Display for TimeoutElapsed {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "The main operation timed out after {:?} (requested {:?}) before it could be completed",
+            self.elapsed, self.requested
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<TimeoutElapsed> for Timeout {
+    #[inline]
+    fn from(_: TimeoutElapsed) -> Self {
+        Timeout
+    }
+}
+
+#[docfg(feature = "std")]
+impl std::error::Error for TimeoutElapsed {}
+
+/// Error returned when a bounded operation runs out of attempts before it could be completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct BoundExceeded;
+
+impl Display for BoundExceeded {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "The main operation exceeded it's maximum number of attempts before it could be completed"
+        )
+    }
+}
+
+#[docfg(feature = "std")]
+impl std::error::Error for BoundExceeded {}
+
 #[allow(unused)]
 #[inline]
 pub(crate) fn is_some_and<T, F: FnOnce(T) -> bool>(v: Option<T>, f: F) -> bool {
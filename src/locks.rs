@@ -1,6 +1,11 @@
 #[cfg(not(feature = "nightly"))]
 use core::marker::PhantomData;
-use core::{fmt::Debug, mem::ManuallyDrop};
+use crate::FillQueue;
+use core::{
+    fmt::Debug,
+    mem::ManuallyDrop,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "std")] {
@@ -39,7 +44,7 @@ cfg_if::cfg_if! {
             #[inline]
             pub fn into_raw (self) -> *mut () {
                 static_assertions::assert_eq_align!(Lock, *mut ());
-                return unsafe { core::mem::transmute(self) }
+                return unsafe { core::mem::transmute::<Lock, *mut ()>(self) }
             }
 
             /// Constructs a `Lock` from a raw mutable pointer.
@@ -51,7 +56,25 @@ cfg_if::cfg_if! {
             #[inline]
             pub unsafe fn from_raw (raw: *mut ()) -> Self {
                 static_assertions::assert_eq_align!(Lock, *mut ());
-                return Self(core::mem::transmute(raw))
+                return Self(core::mem::transmute::<*mut (), std::thread::Thread>(raw))
+            }
+
+            /// Returns the handle of the thread that created the lock, which is the one woken up by it.
+            ///
+            /// This can be used for diagnostics, to identify waiters by their name or id.
+            ///
+            /// # Example
+            ///
+            /// ```
+            /// use utils_atomics::lock;
+            ///
+            /// let (lock, _lock_sub) = lock();
+            /// assert_eq!(lock.thread().id(), std::thread::current().id());
+            /// ```
+            #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+            #[inline]
+            pub fn thread (&self) -> &std::thread::Thread {
+                return &self.0
             }
 
             /// Drops the `Lock` without waking up the waiting threads.
@@ -59,7 +82,7 @@ cfg_if::cfg_if! {
             #[inline]
             pub fn silent_drop (self) {
                 let mut this = ManuallyDrop::new(self);
-                unsafe { core::ptr::drop_in_place(&mut this.0) }
+                unsafe { core::ptr::drop_in_place(core::ptr::addr_of_mut!(this.0)) }
             }
         }
 
@@ -181,7 +204,7 @@ cfg_if::cfg_if! {
             #[inline]
             pub fn into_raw (self) -> *mut () {
                 let this = ManuallyDrop::new(self);
-                return unsafe { Arc::into_raw(core::ptr::read(&this.0)).cast_mut() }
+                return unsafe { Arc::into_raw(core::ptr::read(core::ptr::addr_of!(this.0))).cast_mut() }
             }
 
             /// Constructs a `Lock` from a raw mutable pointer.
@@ -226,12 +249,33 @@ cfg_if::cfg_if! {
                 let mut this = self.0;
                 loop {
                     match alloc::sync::Arc::try_unwrap(this) {
-                        Ok(_) => return,
+                        Ok(()) => return,
                         Err(e) => this = e
                     }
                     core::hint::spin_loop()
                 }
             }
+
+            /// Blocks the current thread until the associated `Lock` is dropped, or until `now` reaches `deadline`,
+            /// whichever comes first. Returns `true` if the lock was released, and `false` if the wait timed out.
+            ///
+            /// Since there is no standard clock in `no_std` environments, the time source is provided by `now`,
+            /// which may return any monotonic measure of time (e.g. a hardware tick counter), as long as `deadline` uses the same units.
+            #[cfg_attr(docsrs, doc(cfg(not(feature = "std"))))]
+            pub fn wait_timeout_with<F: Fn() -> u64> (self, now: F, deadline: u64) -> bool {
+                let mut this = self.0;
+                loop {
+                    match alloc::sync::Arc::try_unwrap(this) {
+                        Ok(()) => return true,
+                        Err(e) => this = e
+                    }
+
+                    if now() >= deadline {
+                        return false
+                    }
+                    core::hint::spin_loop()
+                }
+            }
         }
 
         /// Acquires a `Lock` and its corresponding `LockSub` for coordinating access to a shared resource.
@@ -256,6 +300,27 @@ cfg_if::cfg_if! {
             return (Lock(lock.clone()), LockSub(lock, #[cfg(not(feature = "nightly"))] PhantomData))
         }
 
+        /// Creates a `Lock` and its corresponding `LockSub` from an existing reference count, instead of allocating a new one.
+        ///
+        /// The `LockSub` is released once every other reference to `arc` has been dropped, so any clones of it held elsewhere
+        /// keep the `LockSub` waiting alongside the `Lock`.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use utils_atomics::lock_from_arc;
+        /// use std::sync::Arc;
+        ///
+        /// let (lock, lock_sub) = lock_from_arc(Arc::new(()));
+        /// std::thread::spawn(move || lock.wake());
+        /// lock_sub.wait();
+        /// ```
+        #[cfg_attr(docsrs, doc(cfg(not(feature = "std"))))]
+        #[inline]
+        pub fn lock_from_arc (arc: Arc<()>) -> (Lock, LockSub) {
+            return (Lock(arc.clone()), LockSub(arc, #[cfg(not(feature = "nightly"))] PhantomData))
+        }
+
         impl Drop for Lock {
             #[inline]
             fn drop (&mut self) {}
@@ -285,6 +350,284 @@ impl Lock {
     pub fn wake(self) {}
 }
 
+/// Wakes up every `Lock` of `locks`.
+///
+/// The locks are first collected into a contiguous buffer, and then woken in a tight loop. When the locks come from a linked
+/// structure (like a chopped [`FillQueue`](crate::FillQueue)), this keeps the pointer chasing apart from the wake-ups, so waking
+/// large amounts of them causes fewer cache misses than waking each one as it's reached.
+///
+/// # Example
+///
+/// ```
+/// use utils_atomics::{lock, wake_all};
+///
+/// let (lock, lock_sub) = lock();
+/// std::thread::spawn(move || wake_all([lock]));
+/// lock_sub.wait();
+/// ```
+#[allow(clippy::needless_collect)]
+pub fn wake_all<I: IntoIterator<Item = Lock>>(locks: I) {
+    let locks = locks.into_iter().collect::<alloc::vec::Vec<_>>();
+    locks.into_iter().for_each(Lock::wake);
+}
+
+/// A queue of waiters, which wakers can look through (and unregister from) in place.
+///
+/// Registering a waiter is lock-free, but wakers are serialized between themselves, so that none of them frees the
+/// waiters another one is looking through. Since waiters are never taken out of the queue to be looked through, a waiter
+/// is visible to every waker from the moment it's registered until it's removed.
+#[derive(Debug)]
+pub(crate) struct WaitQueue<T> {
+    queue: FillQueue<T>,
+    waking: AtomicBool,
+}
+
+/// Exclusive access to the waiters of a [`WaitQueue`], which is released when dropped.
+pub(crate) struct Waking<'a, T>(&'a WaitQueue<T>);
+
+impl<T> WaitQueue<T> {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        return Self {
+            queue: FillQueue::new(),
+            waking: AtomicBool::new(false),
+        };
+    }
+
+    /// Registers a new waiter
+    #[inline]
+    pub(crate) fn push(&self, v: T) {
+        self.queue.push(v);
+    }
+
+    /// Returns `true` if no waiter is registered. Like [`FillQueue::is_empty`], the result should be considered immediately stale.
+    #[cfg(test)]
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        return self.queue.is_empty();
+    }
+
+    /// Returns how many waiters are registered. Like [`FillQueue::len`], the result should be considered immediately stale.
+    #[cfg(test)]
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        return self.queue.len();
+    }
+
+    /// Blocks until no other waker is looking through the waiters
+    pub(crate) fn waking(&self) -> Waking<'_, T> {
+        while self
+            .waking
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        return Waking(self);
+    }
+}
+
+impl<'a, T> Waking<'a, T> {
+    /// Returns a LIFO iterator over the registered waiters, which are kept in the queue
+    #[inline]
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        // Only wakers remove waiters from the queue, and we hold exclusive access over them
+        return unsafe { self.0.queue.iter_mut_unchecked() };
+    }
+
+    /// Unregisters, in place and in LIFO order, every waiter for which `f` returns `false`
+    #[inline]
+    pub(crate) fn retain<F: FnMut(&mut T) -> bool>(&mut self, f: F) {
+        // Only wakers remove waiters from the queue, and we hold exclusive access over them
+        unsafe { self.0.queue.retain_unchecked(f) }
+    }
+
+    /// Takes every registered waiter out of the queue
+    #[inline]
+    pub(crate) fn chop(&mut self) -> crate::fill_queue::ChopIter<'a, T> {
+        return self.0.queue.chop();
+    }
+}
+
+impl<T> Drop for Waking<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.waking.store(false, Ordering::Release);
+    }
+}
+
+/// A [`Lock`] registered in a wait queue, which is skipped by notifications once it's thread stops waiting.
+///
+/// A waiter that's dropped whilst it's thread is still waiting wakes it.
+#[derive(Debug)]
+pub(crate) struct Waiter {
+    // Taken by the notification that wakes the waiter
+    lock: Option<Lock>,
+    // Cleared by whoever gets to the waiter first, be it a notification or the waiting thread itself
+    waiting: alloc::sync::Arc<AtomicBool>,
+}
+
+/// Keeps a [`Waiter`] registered until it's dropped.
+#[derive(Debug)]
+pub(crate) struct WaiterGuard(alloc::sync::Arc<AtomicBool>);
+
+/// Creates a new [`Waiter`] alongside it's [`LockSub`], and the guard that unregisters it
+#[inline]
+pub(crate) fn waiter() -> (Waiter, LockSub, WaiterGuard) {
+    let (lock, sub) = lock();
+    let waiting = alloc::sync::Arc::new(AtomicBool::new(true));
+    return (
+        Waiter {
+            lock: Some(lock),
+            waiting: waiting.clone(),
+        },
+        sub,
+        WaiterGuard(waiting),
+    );
+}
+
+impl Waiter {
+    /// Wakes the waiter in place, returning `false` (without waking it) if it already stopped waiting
+    #[inline]
+    pub(crate) fn wake(&mut self) -> bool {
+        if self.waiting.swap(false, Ordering::Relaxed) {
+            if let Some(lock) = self.lock.take() {
+                lock.wake();
+                return true;
+            }
+        }
+        return false;
+    }
+
+    /// Wakes one of the waiters of `queue`, returning `true` if there was one still waiting.
+    ///
+    /// Waiters are woken in LIFO order. The ones that already stopped waiting are skipped and removed from the queue.
+    pub(crate) fn wake_one(queue: &WaitQueue<Self>) -> bool {
+        let mut woken = false;
+        queue.waking().retain(|waiter| {
+            woken = woken || waiter.wake();
+            // Both the woken waiter and the ones that stopped waiting are unregistered
+            return waiter.waiting.load(Ordering::Relaxed);
+        });
+        return woken;
+    }
+
+    /// Wakes every waiter of `queue` that's still waiting
+    #[inline]
+    pub(crate) fn wake_all(queue: &WaitQueue<Self>) {
+        queue.waking().chop().for_each(|mut x| {
+            let _ = x.wake();
+        });
+    }
+}
+
+impl Drop for Waiter {
+    #[inline]
+    fn drop(&mut self) {
+        if self.wake() {
+            return;
+        }
+
+        // The thread already stopped waiting, so it's lock is released without waking it
+        if let Some(lock) = self.lock.take() {
+            #[cfg(feature = "std")]
+            lock.silent_drop();
+            // Without `std`, dropping the lock only releases a reference count that nobody waits on anymore
+            #[cfg(not(feature = "std"))]
+            drop(lock);
+        }
+    }
+}
+
+impl Drop for WaiterGuard {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "futures")] {
+        use crate::flag::mpsc::{async_flag, AsyncFlag, AsyncSubscribe};
+        use core::{future::Future, pin::Pin, task::{Context, Poll}};
+        use futures::future::FusedFuture;
+
+        /// The asynchronous counterpart of [`Lock`], which wakes the task awaiting it's [`AsyncLockSub`] when dropped.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use utils_atomics::async_lock;
+        ///
+        /// # #[tokio::main]
+        /// # async fn main() {
+        /// let (lock, lock_sub) = async_lock();
+        /// tokio::spawn(async move {
+        ///     // Do some work with the shared resource
+        ///     lock.wake();
+        /// });
+        ///
+        /// // Do some work with the shared resource
+        /// lock_sub.await;
+        /// # }
+        /// ```
+        #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+        #[derive(Debug)]
+        pub struct AsyncLock (AsyncFlag);
+
+        /// A future that completes once it's associated [`AsyncLock`] is dropped.
+        #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+        #[derive(Debug)]
+        #[must_use = "futures do nothing unless you `.await` or poll them"]
+        pub struct AsyncLockSub (AsyncSubscribe);
+
+        impl AsyncLock {
+            /// Wakes up the task awaiting the associated [`AsyncLockSub`].
+            #[inline]
+            pub fn wake (self) {}
+
+            /// Drops the `AsyncLock` without waking up the waiting task.
+            /// This method may leak memory.
+            #[inline]
+            pub fn silent_drop (self) {
+                self.0.silent_drop();
+            }
+        }
+
+        impl AsyncLockSub {
+            /// Returns `true` if the associated [`AsyncLock`] has already been dropped, and `false` otherwise
+            #[inline]
+            pub fn is_released (&self) -> bool {
+                return self.0.is_marked()
+            }
+        }
+
+        impl Future for AsyncLockSub {
+            type Output = ();
+
+            #[inline]
+            fn poll (mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                return Pin::new(&mut self.0).poll(cx)
+            }
+        }
+
+        impl FusedFuture for AsyncLockSub {
+            #[inline]
+            fn is_terminated (&self) -> bool {
+                self.0.is_terminated()
+            }
+        }
+
+        /// Creates an [`AsyncLock`] and its corresponding [`AsyncLockSub`], which can be awaited instead of blocking the current thread.
+        #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+        #[inline]
+        pub fn async_lock () -> (AsyncLock, AsyncLockSub) {
+            let (flag, sub) = async_flag();
+            return (AsyncLock(flag), AsyncLockSub(sub))
+        }
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "nightly")] {
         impl !Send for LockSub {}
@@ -292,3 +635,97 @@ cfg_if::cfg_if! {
         unsafe impl Sync for LockSub {}
     }
 }
+
+#[cfg(all(test, not(feature = "std")))]
+mod tests {
+    use super::lock;
+    use core::cell::Cell;
+
+    #[test]
+    fn test_lock_from_arc() {
+        use super::lock_from_arc;
+        use alloc::sync::Arc;
+
+        let arc = Arc::new(());
+        let (held, sub) = lock_from_arc(arc.clone());
+        assert_eq!(Arc::strong_count(&arc), 3);
+
+        // Other references to the `Arc` keep the sub waiting, just like the lock
+        drop(held);
+        assert!(!sub.wait_timeout_with(|| 0, 0));
+
+        // Once the lock is woken, the sub holds the only reference left
+        let (released, sub) = lock_from_arc(arc);
+        released.wake();
+        sub.wait();
+    }
+
+    #[test]
+    fn test_wait_timeout_with() {
+        let ticks = Cell::new(0);
+        let now = || {
+            ticks.set(ticks.get() + 1);
+            ticks.get()
+        };
+
+        // The lock is never released, so the wait concludes once the clock reaches the deadline
+        let (held, sub) = lock();
+        assert!(!sub.wait_timeout_with(now, 10));
+        assert_eq!(ticks.get(), 10);
+        drop(held);
+
+        // Released locks are observed before the clock is consulted
+        let (released, sub) = lock();
+        drop(released);
+        assert!(sub.wait_timeout_with(now, 0));
+        assert_eq!(ticks.get(), 10);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod std_tests {
+    use super::lock;
+    use std::thread;
+
+    #[test]
+    fn test_thread() {
+        let (current, _sub) = lock();
+        assert_eq!(current.thread().id(), thread::current().id());
+
+        let handle = thread::Builder::new()
+            .name("waiter".into())
+            .spawn(|| {
+                let (lock, sub) = lock();
+                (lock, thread::current().id(), sub.wait_timeout(core::time::Duration::ZERO))
+            })
+            .unwrap();
+
+        let (waiter, id, ()) = handle.join().unwrap();
+        assert_eq!(waiter.thread().id(), id);
+        assert_ne!(waiter.thread().id(), current.thread().id());
+        assert_eq!(waiter.thread().name(), Some("waiter"));
+    }
+
+    #[cfg(feature = "futures")]
+    #[tokio::test]
+    async fn test_async_lock() {
+        use super::async_lock;
+        use core::time::Duration;
+
+        let (lock, sub) = async_lock();
+        assert!(!sub.is_released());
+
+        let handle = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            lock.wake();
+        });
+
+        tokio::time::timeout(Duration::from_secs(10), sub).await.unwrap();
+        handle.await.unwrap();
+
+        let (lock, sub) = async_lock();
+        drop(lock);
+        assert!(sub.is_released());
+        sub.await;
+    }
+}
@@ -1,13 +1,20 @@
-use crate::{
-    locks::{lock, Lock},
-    FillQueue,
+use crate::locks::{lock, Lock, WaitQueue};
+use alloc::{
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{
+    cmp::Reverse,
+    mem::ManuallyDrop,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
 };
-use alloc::sync::{Arc, Weak};
 
 /// Creates a new notifier and a listener to it.
 pub fn notify() -> (Notify, Listener) {
     let inner = Arc::new(Inner {
-        wakers: FillQueue::new(),
+        wakers: WaitQueue::new(),
+        notifiers: AtomicUsize::new(1),
+        silent: AtomicBool::new(false),
     });
 
     let listener = Listener {
@@ -18,7 +25,46 @@ pub fn notify() -> (Notify, Listener) {
 
 #[derive(Debug)]
 struct Inner {
-    wakers: FillQueue<Lock>,
+    wakers: WaitQueue<Waiter>,
+    // Listeners briefly hold strong references while registering, so the notifiers are counted separately
+    notifiers: AtomicUsize,
+    silent: AtomicBool,
+}
+
+impl Drop for Inner {
+    #[inline]
+    fn drop(&mut self) {
+        // Locks registered after the last notifier was dropped are released here, honoring how it was dropped
+        if *self.silent.get_mut() {
+            self.wakers.waking().chop().for_each(Waiter::silent_drop);
+        } else {
+            self.wakers.waking().chop().for_each(Waiter::wake);
+        }
+    }
+}
+
+/// A blocked listener, alongside the priority it registered with
+#[derive(Debug)]
+struct Waiter {
+    // Taken by the notification that wakes the listener
+    lock: Option<Lock>,
+    priority: i32,
+}
+
+impl Waiter {
+    #[inline]
+    fn wake(self) {
+        if let Some(lock) = self.lock {
+            lock.wake();
+        }
+    }
+
+    #[inline]
+    fn silent_drop(self) {
+        if let Some(lock) = self.lock {
+            lock.silent_drop();
+        }
+    }
 }
 
 /// Synchronous notifier. This structure can be used not block threads until desired,
@@ -26,7 +72,7 @@ struct Inner {
 ///
 /// This structure drops loudly by default (a.k.a it will awake blocked threads when dropped),
 /// but can be droped silently via [`silent_drop`](Notify::silent_drop)
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Notify {
     inner: Arc<Inner>,
 }
@@ -38,7 +84,8 @@ pub struct Listener {
 
 impl Notify {
     pub unsafe fn into_raw(self) -> *const () {
-        Arc::into_raw(self.inner).cast()
+        let this = ManuallyDrop::new(self);
+        Arc::into_raw(core::ptr::read(core::ptr::addr_of!(this.inner))).cast()
     }
 
     pub unsafe fn from_raw(ptr: *const ()) -> Self {
@@ -54,7 +101,63 @@ impl Notify {
 
     #[inline]
     pub fn notify_all(&self) {
-        self.inner.wakers.chop().for_each(Lock::wake)
+        self.inner.wakers.waking().chop().for_each(Waiter::wake)
+    }
+
+    /// Wakes all the waiting threads, like [`notify_all`](Notify::notify_all), but collecting them before waking any of them.
+    ///
+    /// Every wake-up is still a separate `unpark`, but they are issued in a tight loop, apart from the traversal of the waiters' queue
+    /// (see [`wake_all`](crate::wake_all)). This may reduce the cost of waking large amounts of threads, at the expense of a temporary allocation.
+    #[inline]
+    pub fn notify_all_batched(&self) {
+        crate::locks::wake_all(self.inner.wakers.waking().chop().filter_map(|x| x.lock));
+    }
+
+    /// Wakes the highest-priority waiting thread, returning `true` if there was one to be woken.
+    /// See [`notify_many`](Notify::notify_many) for the order in which waiters are selected.
+    #[inline]
+    pub fn notify_one(&self) -> bool {
+        return self.notify_many(1) == 1;
+    }
+
+    /// Wakes up to `n` of the currently waiting threads, returning how many were actually woken.
+    ///
+    /// Waiters are woken from highest to lowest priority (see [`recv_with_priority`](Listener::recv_with_priority)), and waiters with
+    /// the same priority are woken in FIFO order (the earliest registered ones first). Waiters are selected and woken in place, so
+    /// the remaining ones keep their place in the queue, and are never hidden from concurrent notifications.
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::notify::notify;
+    ///
+    /// let (notify, listener) = notify();
+    /// std::thread::scope(|s| {
+    ///     s.spawn(|| listener.recv());
+    ///     std::thread::sleep(core::time::Duration::from_millis(100));
+    ///     assert_eq!(notify.notify_many(2), 1);
+    /// });
+    /// ```
+    pub fn notify_many(&self, n: usize) -> usize {
+        let mut wakers = self.inner.wakers.waking();
+        let mut waiters = wakers.iter_mut().collect::<Vec<_>>();
+        if n < waiters.len() {
+            // Waiters are visited in LIFO order, so they're reversed for the (stable) sort to keep the earliest registered ones first
+            waiters.reverse();
+            waiters.sort_by_key(|x| Reverse(x.priority));
+        }
+
+        let mut woken = 0;
+        for waiter in waiters.into_iter().take(n) {
+            if let Some(lock) = waiter.lock.take() {
+                lock.wake();
+                woken += 1;
+            }
+        }
+
+        // Only the woken waiters are unregistered, the rest never leave the queue
+        if woken > 0 {
+            wakers.retain(|x| x.lock.is_some());
+        }
+        return woken;
     }
 
     #[inline]
@@ -66,10 +169,35 @@ impl Notify {
 
     /// Drops the notifier without awaking blocked threads.
     /// This method may leak memory.
+    ///
+    /// Listeners that are registering themselves while the last notifier is silently dropped won't be awaken either,
+    /// even if they end up releasing the notifier's shared state.
     #[inline]
     pub fn silent_drop(self) {
-        if let Ok(mut inner) = Arc::try_unwrap(self.inner) {
-            inner.wakers.chop_mut().for_each(Lock::silent_drop);
+        let mut this = ManuallyDrop::new(self);
+        if this.inner.notifiers.fetch_sub(1, Ordering::AcqRel) == 1 {
+            this.inner.silent.store(true, Ordering::Release);
+            this.inner.wakers.waking().chop().for_each(Waiter::silent_drop);
+        }
+        unsafe { core::ptr::drop_in_place(core::ptr::addr_of_mut!(this.inner)) }
+    }
+}
+
+impl Clone for Notify {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.inner.notifiers.fetch_add(1, Ordering::Relaxed);
+        return Self {
+            inner: self.inner.clone(),
+        };
+    }
+}
+
+impl Drop for Notify {
+    #[inline]
+    fn drop(&mut self) {
+        if self.inner.notifiers.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.inner.wakers.waking().chop().for_each(Waiter::wake);
         }
     }
 }
@@ -97,20 +225,120 @@ impl Listener {
 
     #[inline]
     pub fn try_recv(&self) -> bool {
+        return self.try_recv_with_priority(0);
+    }
+
+    /// Blocks the current thread until notified, registering it with the specified `priority`.
+    ///
+    /// [`notify_one`](Notify::notify_one) and [`notify_many`](Notify::notify_many) wake higher-priority waiters first.
+    /// Waiters registered with [`recv`](Listener::recv) have a priority of `0`.
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::notify::notify;
+    /// use core::time::Duration;
+    ///
+    /// let (notify, listener) = notify();
+    /// std::thread::scope(|s| {
+    ///     let low = s.spawn(|| listener.recv_with_priority(-1));
+    ///     std::thread::sleep(Duration::from_millis(50));
+    ///     let high = s.spawn(|| listener.recv_with_priority(1));
+    ///     std::thread::sleep(Duration::from_millis(50));
+    ///
+    ///     assert!(notify.notify_one());
+    ///     high.join().unwrap();
+    ///     assert!(!low.is_finished());
+    ///     assert!(notify.notify_one());
+    /// });
+    /// ```
+    #[inline]
+    pub fn recv_with_priority(&self, priority: i32) {
+        let _: bool = self.try_recv_with_priority(priority);
+    }
+
+    /// Like [`try_recv`](Listener::try_recv), but registers the current thread with the specified `priority`.
+    /// See [`recv_with_priority`](Listener::recv_with_priority).
+    pub fn try_recv_with_priority(&self, priority: i32) -> bool {
         if let Some(inner) = self.inner.upgrade() {
+            if inner.notifiers.load(Ordering::Acquire) == 0 {
+                return false;
+            }
+
             let (lock, sub) = lock();
-            inner.wakers.push(lock);
+            inner.wakers.push(Waiter {
+                lock: Some(lock),
+                priority,
+            });
+            // We mustn't keep the notifier alive while we wait, otherwise it won't be able to wake us when dropped.
+            drop(inner);
             sub.wait();
             return true;
         }
         return false;
     }
+
+    /// Blocks the current thread for as long as `condition` returns `true`, like [`Condvar::wait_while`](std::sync::Condvar::wait_while).
+    ///
+    /// `condition` is checked before blocking and after every notification, so spurious or unrelated notifications are ignored.
+    /// It's also checked right after the thread registers itself, so a notification sent after the state changed is never missed,
+    /// as long as the state is changed before notifying. This makes the listener usable as a drop-in for condvar-style predicate loops.
+    ///
+    /// Returns `true` once `condition` returns `false`, or `false` if every notifier was dropped while `condition` still held.
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::notify::notify;
+    /// use core::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// let (notify, listener) = notify();
+    /// let count = AtomicUsize::new(0);
+    ///
+    /// std::thread::scope(|s| {
+    ///     s.spawn(|| {
+    ///         for _ in 0..3 {
+    ///             count.fetch_add(1, Ordering::AcqRel);
+    ///             notify.notify_all();
+    ///         }
+    ///     });
+    ///
+    ///     assert!(listener.recv_while(|| count.load(Ordering::Acquire) < 3));
+    /// });
+    /// ```
+    pub fn recv_while<F: FnMut() -> bool>(&self, mut condition: F) -> bool {
+        loop {
+            if !condition() {
+                return true;
+            }
+
+            let Some(inner) = self.inner.upgrade() else {
+                return false;
+            };
+            if inner.notifiers.load(Ordering::Acquire) == 0 {
+                return false;
+            }
+
+            let (lock, sub) = lock();
+            inner.wakers.push(Waiter {
+                lock: Some(lock),
+                priority: 0,
+            });
+            drop(inner);
+
+            // The state may have changed (and been notified) before our waker was registered
+            if !condition() {
+                return true;
+            }
+            sub.wait();
+        }
+    }
 }
 
+static_assertions::assert_impl_all!(Notify: Send, Sync);
+static_assertions::assert_impl_all!(Listener: Send, Sync);
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "futures")] {
         use futures::{FutureExt, Stream};
         use crate::flag::mpsc::{AsyncFlag, AsyncSubscribe, async_flag};
+        use crate::FillQueue;
         use core::task::Poll;
         use futures::stream::FusedStream;
 
@@ -118,11 +346,15 @@ cfg_if::cfg_if! {
         pub fn async_notify() -> (AsyncNotify, AsyncListener) {
             let inner = Arc::new(AsyncInner {
                 wakers: FillQueue::new(),
+                generation: AtomicUsize::new(0),
+                notifiers: AtomicUsize::new(1),
+                silent: AtomicBool::new(false),
             });
 
             let listener = AsyncListener {
                 inner: Some(Arc::downgrade(&inner)),
-                sub: None
+                sub: None,
+                generation: 0,
             };
 
             return (AsyncNotify { inner }, listener);
@@ -131,6 +363,24 @@ cfg_if::cfg_if! {
         #[derive(Debug)]
         struct AsyncInner {
             wakers: FillQueue<AsyncFlag>,
+            // Incremented by every `notify_all`, so listeners can tell if they missed one while they weren't registered
+            generation: AtomicUsize,
+            // Listeners briefly hold strong references while polling, so the notifiers are counted separately
+            notifiers: AtomicUsize,
+            silent: AtomicBool,
+        }
+
+        impl Drop for AsyncInner {
+            #[inline]
+            fn drop(&mut self) {
+                // Flags registered after the last notifier was dropped are released here, honoring how it was dropped,
+                // so that the listeners of a loud drop get woken and then terminate, instead of being leaked alongside the queue
+                if *self.silent.get_mut() {
+                    self.wakers.chop_mut().for_each(AsyncFlag::silent_drop);
+                } else {
+                    self.wakers.chop_mut().for_each(AsyncFlag::mark);
+                }
+            }
         }
 
         /// Synchronous notifier. This structure can be used not block tasks until desired,
@@ -138,20 +388,27 @@ cfg_if::cfg_if! {
         ///
         /// This structure drops loudly by default (a.k.a it will awake blocked tasks when dropped),
         /// but can be droped silently via [`silent_drop`](AsyncNotify::silent_drop)
-        #[derive(Debug, Clone)]
+        #[derive(Debug)]
         pub struct AsyncNotify {
             inner: Arc<AsyncInner>,
         }
 
+        /// Asynchronous listener of an [`AsyncNotify`], which yields an item for every notification.
+        ///
+        /// Notifications sent while the listener isn't registered (e.g. between an item being yielded and the stream being polled again)
+        /// aren't missed: the next poll yields immediately. Multiple notifications sent in that gap are coalesced into a single item.
         #[derive(Debug)]
         pub struct AsyncListener {
             inner: Option<Weak<AsyncInner>>,
-            sub: Option<AsyncSubscribe>
+            sub: Option<AsyncSubscribe>,
+            // Generation of the notifier when the last item was yielded
+            generation: usize,
         }
 
         impl AsyncNotify {
             pub unsafe fn into_raw(self) -> *const () {
-                Arc::into_raw(self.inner).cast()
+                let this = ManuallyDrop::new(self);
+                Arc::into_raw(core::ptr::read(core::ptr::addr_of!(this.inner))).cast()
             }
 
             pub unsafe fn from_raw(ptr: *const ()) -> Self {
@@ -165,25 +422,62 @@ cfg_if::cfg_if! {
                 return Arc::weak_count(&self.inner);
             }
 
-            #[inline]
-            pub fn notify_all(&self) {
-                self.inner.wakers.chop().for_each(AsyncFlag::mark)
+            /// Wakes all the waiting tasks, returning how many were woken.
+            ///
+            /// Listeners that stopped waiting (e.g. because they were dropped) after registering aren't counted.
+            pub fn notify_all(&self) -> usize {
+                self.inner.generation.fetch_add(1, Ordering::AcqRel);
+
+                let mut count = 0;
+                for flag in self.inner.wakers.chop() {
+                    if flag.has_subscriber() {
+                        count += 1;
+                    }
+                    flag.mark();
+                }
+                return count;
             }
 
             #[inline]
             pub fn listen(&self) -> AsyncListener {
                 return AsyncListener {
                     inner: Some(Arc::downgrade(&self.inner)),
-                    sub: None
+                    sub: None,
+                    generation: self.inner.generation.load(Ordering::Acquire),
                 };
             }
 
             /// Drops the notifier without awaking blocked tasks.
             /// This method may leak memory.
+            ///
+            /// Listeners that are registering themselves while the last notifier is silently dropped won't be awaken either,
+            /// even if they end up releasing the notifier's shared state.
             #[inline]
             pub fn silent_drop (self) {
-                if let Ok(mut inner) = Arc::try_unwrap(self.inner) {
-                    inner.wakers.chop_mut().for_each(AsyncFlag::silent_drop);
+                let mut this = ManuallyDrop::new(self);
+                if this.inner.notifiers.fetch_sub(1, Ordering::AcqRel) == 1 {
+                    this.inner.silent.store(true, Ordering::Release);
+                    this.inner.wakers.chop().for_each(AsyncFlag::silent_drop);
+                }
+                unsafe { core::ptr::drop_in_place(core::ptr::addr_of_mut!(this.inner)) }
+            }
+        }
+
+        impl Clone for AsyncNotify {
+            #[inline]
+            fn clone(&self) -> Self {
+                self.inner.notifiers.fetch_add(1, Ordering::Relaxed);
+                return Self {
+                    inner: self.inner.clone(),
+                };
+            }
+        }
+
+        impl Drop for AsyncNotify {
+            #[inline]
+            fn drop(&mut self) {
+                if self.inner.notifiers.fetch_sub(1, Ordering::AcqRel) == 1 {
+                    self.inner.wakers.chop().for_each(AsyncFlag::mark);
                 }
             }
         }
@@ -204,15 +498,33 @@ cfg_if::cfg_if! {
             fn poll_next(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Option<Self::Item>> {
                 if let Some(ref mut sub) = self.sub {
                     return match sub.poll_unpin(cx) {
-                        Poll::Ready(_) => {
+                        Poll::Ready(()) => {
                             self.sub = None;
+                            if let Some(inner) = self.inner.as_ref().and_then(Weak::upgrade) {
+                                self.generation = inner.generation.load(Ordering::Acquire);
+                            }
                             Poll::Ready(Some(()))
                         },
                         Poll::Pending => Poll::Pending
                     }
-                } else if let Some(inner) = self.inner.as_ref().and_then(Weak::upgrade) {
+                } else if let Some(inner) = self.inner.as_ref().and_then(Weak::upgrade).filter(|x| x.notifiers.load(Ordering::Acquire) > 0) {
+                    let generation = inner.generation.load(Ordering::Acquire);
+                    if generation != self.generation {
+                        // A notification was sent while we weren't registered
+                        self.generation = generation;
+                        return Poll::Ready(Some(()))
+                    }
+
                     let (flag, sub) = async_flag();
                     inner.wakers.push(flag);
+
+                    // A notification may have been sent before our flag was registered
+                    let generation = inner.generation.load(Ordering::Acquire);
+                    if generation != self.generation {
+                        self.generation = generation;
+                        return Poll::Ready(Some(()))
+                    }
+
                     self.sub = Some(sub);
                     return self.poll_next(cx)
                 }
@@ -250,7 +562,8 @@ cfg_if::cfg_if! {
             fn clone(&self) -> Self {
                 return Self {
                     inner: self.inner.clone(),
-                    sub: None
+                    sub: None,
+                    generation: self.generation,
                 }
             }
         }
@@ -315,13 +628,294 @@ mod tests {
 
         assert_eq!(listener.listeners(), 1);
     }
+
+    #[test]
+    fn test_notify_many() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        const THREADS: usize = 10;
+
+        let (notify, listener) = notify();
+        let woken = AtomicUsize::new(0);
+
+        thread::scope(|s| {
+            for _ in 0..THREADS {
+                s.spawn(|| {
+                    listener.recv();
+                    woken.fetch_add(1, Ordering::AcqRel);
+                });
+            }
+
+            thread::sleep(Duration::from_millis(100));
+            assert_eq!(notify.notify_many(3), 3);
+            thread::sleep(Duration::from_millis(100));
+            assert_eq!(woken.load(Ordering::Acquire), 3);
+
+            // The remaining waiters weren't lost
+            assert_eq!(notify.notify_many(usize::MAX), THREADS - 3);
+        });
+
+        assert_eq!(woken.into_inner(), THREADS);
+        assert_eq!(notify.notify_many(3), 0);
+    }
+
+    #[test]
+    fn test_notify_many_and_notify_all() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use std::{sync::Barrier, time::Instant};
+
+        const THREADS: usize = 16;
+        const RUNS: usize = if cfg!(miri) { 10 } else { 200 };
+
+        for _ in 0..RUNS {
+            let (notify, listener) = notify();
+            let woken = AtomicUsize::new(0);
+            let start = Barrier::new(2);
+
+            thread::scope(|s| {
+                for _ in 0..THREADS {
+                    s.spawn(|| {
+                        listener.recv();
+                        woken.fetch_add(1, Ordering::AcqRel);
+                    });
+                }
+
+                while notify.inner.wakers.len() < THREADS {
+                    thread::yield_now();
+                }
+
+                // Every waiter registered before `notify_all`, so none of them may be missed by it
+                s.spawn(|| {
+                    start.wait();
+                    notify.notify_many(1)
+                });
+                s.spawn(|| {
+                    start.wait();
+                    notify.notify_all();
+                });
+
+                let deadline = Instant::now() + Duration::from_secs(10);
+                while woken.load(Ordering::Acquire) < THREADS && Instant::now() < deadline {
+                    thread::yield_now();
+                }
+
+                let lost = woken.load(Ordering::Acquire) < THREADS;
+                // Releases the waiters either way, so that a failure doesn't hang the test
+                while woken.load(Ordering::Acquire) < THREADS {
+                    notify.notify_all();
+                    thread::yield_now();
+                }
+                assert!(!lost, "a waiter was missed by notify_all");
+            });
+        }
+    }
+
+    #[test]
+    fn test_notify_all_batched() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        const THREADS: usize = 50;
+
+        let (notify, listener) = notify();
+        let woken = AtomicUsize::new(0);
+
+        thread::scope(|s| {
+            for _ in 0..THREADS {
+                s.spawn(|| {
+                    listener.recv();
+                    woken.fetch_add(1, Ordering::AcqRel);
+                });
+            }
+
+            // Late registrations are caught by the following notifications
+            while woken.load(Ordering::Acquire) < THREADS {
+                thread::sleep(Duration::from_millis(10));
+                notify.notify_all_batched();
+            }
+        });
+
+        assert_eq!(woken.into_inner(), THREADS);
+        assert_eq!(notify.notify_many(usize::MAX), 0);
+    }
+
+    #[test]
+    fn test_recv_while() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let (notify, listener) = notify();
+        let count = AtomicUsize::new(0);
+
+        thread::scope(|s| {
+            let waiter = s.spawn(|| listener.recv_while(|| count.load(Ordering::Acquire) < 10));
+
+            // Notifications sent while the predicate still holds are treated as spurious
+            for _ in 0..10 {
+                thread::sleep(Duration::from_millis(5));
+                notify.notify_all();
+                assert!(!waiter.is_finished());
+                count.fetch_add(1, Ordering::AcqRel);
+            }
+
+            notify.notify_all();
+            assert!(waiter.join().unwrap());
+        });
+
+        // Predicates that no longer hold return right away, and dropped notifiers stop the loop
+        assert!(listener.recv_while(|| false));
+        drop(notify);
+        assert!(!listener.recv_while(|| true));
+    }
+
+    #[test]
+    fn test_notify_priority() {
+        use crate::locks::lock;
+        use std::sync::{mpsc, Mutex};
+
+        const PRIORITIES: [i32; 5] = [1, 3, 2, 3, 1];
+        const LATE_PRIORITY: i32 = 3;
+
+        let (notify, _listener) = notify();
+        let woken = Mutex::new(Vec::new());
+
+        thread::scope(|s| {
+            // The waiters are registered from this thread, so that their order is known
+            let register = |i: usize, priority: i32| {
+                let (tx, rx) = mpsc::channel();
+                let woken = &woken;
+                s.spawn(move || {
+                    let (lock, sub) = lock();
+                    tx.send(lock).unwrap();
+                    sub.wait();
+                    woken.lock().unwrap().push(i);
+                });
+                notify.inner.wakers.push(super::Waiter {
+                    lock: Some(rx.recv().unwrap()),
+                    priority,
+                });
+            };
+
+            for (i, priority) in PRIORITIES.into_iter().enumerate() {
+                register(i, priority);
+            }
+
+            for i in 1..=PRIORITIES.len() + 1 {
+                assert!(notify.notify_one());
+                while woken.lock().unwrap().len() < i {
+                    thread::yield_now();
+                }
+
+                // Waiters that weren't woken keep their place ahead of the ones that register afterwards
+                if i == 1 {
+                    register(PRIORITIES.len(), LATE_PRIORITY);
+                }
+            }
+            assert!(!notify.notify_one());
+        });
+
+        assert_eq!(woken.into_inner().unwrap(), [1, 3, 5, 2, 0, 4]);
+    }
+
+    #[test]
+    fn test_loud_drop() {
+        let (notify, listener) = notify();
+
+        let handle = thread::spawn(move || listener.try_recv());
+        thread::sleep(Duration::from_millis(100));
+        drop(notify);
+
+        assert!(handle.join().unwrap());
+    }
+
+    #[test]
+    fn test_silent_drop_while_registering() {
+        use crate::locks::lock;
+        use std::sync::Barrier;
+        use std::time::Instant;
+
+        const TIMEOUT: Duration = Duration::from_millis(200);
+
+        let (notify, listener) = notify();
+        let upgraded = Barrier::new(2);
+        let dropped = Barrier::new(2);
+
+        thread::scope(|s| {
+            let handle = s.spawn(|| {
+                // Emulate a listener in the middle of `try_recv`, holding a strong reference to the notifier
+                let inner = listener.inner.upgrade().unwrap();
+                upgraded.wait();
+                dropped.wait();
+
+                let (lock, sub) = lock();
+                inner.wakers.push(super::Waiter {
+                    lock: Some(lock),
+                    priority: 0,
+                });
+
+                // We're now the last owner of the notifier's state, so it gets dropped here
+                drop(inner);
+                let start = Instant::now();
+                sub.wait_timeout(TIMEOUT);
+                start.elapsed()
+            });
+
+            upgraded.wait();
+            notify.silent_drop();
+            dropped.wait();
+
+            assert!(handle.join().unwrap() >= TIMEOUT);
+        });
+
+        assert!(!listener.try_recv());
+    }
+
+    #[test]
+    fn test_loud_drop_with_clones() {
+        let (notify, listener) = notify();
+        let notify2 = notify.clone();
+
+        let handle = thread::spawn(move || listener.try_recv());
+        thread::sleep(Duration::from_millis(100));
+        notify2.silent_drop();
+        drop(notify);
+
+        assert!(handle.join().unwrap());
+    }
+
+    #[test]
+    fn test_concurrent_registration() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        const THREADS: usize = 10;
+        const ITERATIONS: usize = if cfg!(miri) { 10 } else { 1000 };
+
+        let (notify, listener) = notify();
+        let done = AtomicUsize::new(0);
+
+        thread::scope(|s| {
+            for _ in 0..THREADS {
+                let listener = listener.clone();
+                let done = &done;
+                s.spawn(move || {
+                    for _ in 0..ITERATIONS {
+                        assert!(listener.try_recv());
+                    }
+                    done.fetch_add(1, Ordering::AcqRel);
+                });
+            }
+
+            while done.load(Ordering::Acquire) < THREADS {
+                notify.notify_all();
+                thread::yield_now();
+            }
+        });
+    }
 }
 
 #[cfg(all(feature = "futures", test))]
 mod async_tests {
     use crate::notify::async_notify;
     use core::time::Duration;
-    use futures::stream::StreamExt;
+    use futures::stream::{FusedStream, StreamExt};
 
     #[tokio::test]
     async fn test_basic_functionality_async_tokio() {
@@ -341,6 +935,115 @@ mod async_tests {
         assert_eq!(notify.listeners(), 0);
     }
 
+    #[tokio::test]
+    async fn test_async_loud_drop() {
+        let (notify, mut listener) = async_notify();
+        let mut listener2 = notify.listen();
+
+        let handle = tokio::spawn(async move {
+            // The drop wakes the listener, which then terminates
+            assert_eq!(listener2.next().await, Some(()));
+            assert_eq!(listener2.next().await, None);
+            assert!(listener2.is_terminated());
+        });
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        drop(notify);
+
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("listener hanged after the notifier was dropped")
+            .unwrap();
+        assert_eq!(listener.next().await, None);
+    }
+
+    #[test]
+    fn test_async_silent_drop_while_registering() {
+        use crate::flag::mpsc::async_flag;
+        use core::{future::Future, pin::Pin};
+        use std::sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        };
+        use std::task::{Context, Wake, Waker};
+
+        struct Woken(AtomicBool);
+
+        impl Wake for Woken {
+            fn wake(self: Arc<Self>) {
+                self.0.store(true, Ordering::Release);
+            }
+        }
+
+        let (notify, listener) = async_notify();
+        // Emulate a listener in the middle of polling, holding a strong reference to the notifier
+        let inner = listener.inner.as_ref().unwrap().upgrade().unwrap();
+        notify.silent_drop();
+
+        let (flag, mut sub) = async_flag();
+        let woken = Arc::new(Woken(AtomicBool::new(false)));
+        let waker = Waker::from(woken.clone());
+        assert!(Pin::new(&mut sub).poll(&mut Context::from_waker(&waker)).is_pending());
+        inner.wakers.push(flag);
+
+        // We're now the last owner of the notifier's state, so it gets dropped here
+        drop(inner);
+        assert!(!woken.0.load(Ordering::Acquire));
+    }
+
+    #[tokio::test]
+    async fn test_notify_all_count() {
+        use futures::FutureExt;
+
+        const LISTENERS: usize = 5;
+
+        let (notify, listener) = async_notify();
+        let mut listeners = (0..LISTENERS).map(|_| listener.clone()).collect::<Vec<_>>();
+
+        // Polling once registers each listener
+        for listener in &mut listeners {
+            assert!(listener.next().now_or_never().is_none());
+        }
+        // Registered listeners that stop waiting aren't counted
+        let mut dropped = notify.listen();
+        assert!(dropped.next().now_or_never().is_none());
+        drop(dropped);
+
+        assert_eq!(notify.notify_all(), LISTENERS);
+        for listener in &mut listeners {
+            assert_eq!(listener.next().await, Some(()));
+        }
+        assert_eq!(notify.notify_all(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_notify_between_polls() {
+        let (notify, mut listener) = async_notify();
+
+        let handle = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            notify.notify_all();
+            notify
+        });
+        assert_eq!(listener.next().await, Some(()));
+        let notify = handle.await.unwrap();
+
+        // Fired while the listener isn't registered (in the re-subscribe gap)
+        notify.notify_all();
+        notify.notify_all();
+        let next = tokio::time::timeout(Duration::from_secs(1), listener.next()).await;
+        assert_eq!(next, Ok(Some(())));
+
+        // Missed notifications are coalesced, and don't cause spurious items
+        let next = tokio::time::timeout(Duration::from_millis(100), listener.next()).await;
+        assert!(next.is_err());
+
+        // Listeners created after a notification don't observe it
+        let mut late = notify.listen();
+        let next = tokio::time::timeout(Duration::from_millis(100), late.next()).await;
+        assert!(next.is_err());
+    }
+
     #[tokio::test]
     async fn test_multi_task_async_tokio() {
         let (notify, listener) = async_notify();
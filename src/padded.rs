@@ -0,0 +1,164 @@
+use crate::traits::AtomicOps;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+/// Pads and aligns a value to the length of a cache line, to avoid false sharing between neighbouring values.
+///
+/// The wrapper dereferences into it's inner value, so an array of `Padded` atomics can be used just like an array of atomics,
+/// with every element living in it's own cache line.
+///
+/// Generic code can use padded atomics through [`AtomicOps`], which `Padded` forwards to it's inner value.
+/// It can't implement [`Atomic`](crate::traits::Atomic) itself though, since every primitive is associated with exactly one atomic type
+/// through [`HasAtomic`](crate::traits::HasAtomic), and atomics must have the same layout as their primitives.
+///
+/// # Example
+/// ```rust
+/// use utils_atomics::Padded;
+/// use core::sync::atomic::{AtomicU64, Ordering};
+///
+/// let counters: [Padded<AtomicU64>; 4] = Default::default();
+/// std::thread::scope(|s| {
+///     for counter in counters.iter() {
+///         s.spawn(move || {
+///             for _ in 0..1000 {
+///                 counter.fetch_add(1, Ordering::Relaxed);
+///             }
+///         });
+///     }
+/// });
+///
+/// assert!(counters.iter().all(|x| x.load(Ordering::Relaxed) == 1000));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(align(64))]
+pub struct Padded<A> {
+    inner: A,
+}
+
+static_assertions::const_assert_eq!(core::mem::align_of::<Padded<AtomicU8>>(), 64);
+static_assertions::assert_eq_size!(Padded<AtomicU8>, Padded<AtomicU64>, [u8; 64]);
+
+impl<A> Padded<A> {
+    /// Creates a new padded value
+    #[inline]
+    pub const fn new(inner: A) -> Self {
+        return Self { inner };
+    }
+
+    /// Consumes the padding, returning the inner value
+    #[inline]
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+}
+
+impl<A> Deref for Padded<A> {
+    type Target = A;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<A> DerefMut for Padded<A> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<A> From<A> for Padded<A> {
+    #[inline]
+    fn from(inner: A) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<A: AtomicOps> AtomicOps for Padded<A> {
+    type Primitive = A::Primitive;
+
+    #[inline]
+    fn load(&self, order: Ordering) -> Self::Primitive {
+        self.inner.load(order)
+    }
+
+    #[inline]
+    fn store(&self, val: Self::Primitive, order: Ordering) {
+        self.inner.store(val, order)
+    }
+
+    #[inline]
+    fn swap(&self, val: Self::Primitive, order: Ordering) -> Self::Primitive {
+        self.inner.swap(val, order)
+    }
+
+    #[inline]
+    fn compare_exchange(
+        &self,
+        current: Self::Primitive,
+        new: Self::Primitive,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self::Primitive, Self::Primitive> {
+        self.inner.compare_exchange(current, new, success, failure)
+    }
+
+    #[inline]
+    fn compare_exchange_weak(
+        &self,
+        current: Self::Primitive,
+        new: Self::Primitive,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self::Primitive, Self::Primitive> {
+        self.inner.compare_exchange_weak(current, new, success, failure)
+    }
+
+    #[inline]
+    fn fetch_update<F: FnMut(Self::Primitive) -> Option<Self::Primitive>>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        f: F,
+    ) -> Result<Self::Primitive, Self::Primitive> {
+        self.inner.fetch_update(set_order, fetch_order, f)
+    }
+}
+
+// Thanks ChatGPT!
+#[cfg(test)]
+mod tests {
+    use super::Padded;
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_padded_atomic() {
+        let mut values = [Padded::new(AtomicU32::new(1)), Padded::new(AtomicU32::new(2))];
+        let stride = core::ptr::addr_of!(values[1]) as usize - core::ptr::addr_of!(values[0]) as usize;
+        assert_eq!(stride, 64);
+
+        values[0].fetch_add(1, Ordering::Relaxed);
+        *values[1].get_mut() = 5;
+        assert_eq!(values[0].load(Ordering::Relaxed), 2);
+        assert_eq!(values[1].load(Ordering::Relaxed), 5);
+
+        let [first, _] = values;
+        assert_eq!(first.into_inner().into_inner(), 2);
+    }
+
+    #[test]
+    fn test_padded_atomic_ops() {
+        use crate::traits::AtomicOps;
+
+        fn bump<A: AtomicOps<Primitive = u32>>(atomic: &A) -> u32 {
+            let prev = atomic.swap(0, Ordering::AcqRel);
+            assert_eq!(atomic.compare_exchange(0, prev + 1, Ordering::AcqRel, Ordering::Acquire), Ok(0));
+            atomic.load(Ordering::Acquire)
+        }
+
+        let value = Padded::new(AtomicU32::new(1));
+        assert_eq!(bump(&value), 2);
+        assert_eq!(bump(&&value), 3);
+    }
+}
@@ -0,0 +1,265 @@
+use crossbeam::epoch::{self, Guard};
+use std::{
+    fmt::Debug,
+    marker::PhantomData,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicPtr, Ordering},
+        Arc,
+    },
+};
+
+/// A read-copy-update cell, optimized for read-mostly shared values (e.g. configuration).
+///
+/// Reading the cell only pins the current thread's epoch and loads the current pointer, without touching the value's reference count.
+/// Updates publish a new [`Arc`], and the previous one is released once every reader that could have observed it has unpinned.
+///
+/// # Example
+/// ```rust
+/// use utils_atomics::RcuCell;
+///
+/// let config = RcuCell::new(String::from("v1"));
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         let current = config.read();
+///         assert!(*current == "v1" || *current == "v2");
+///     });
+///
+///     config.update(|prev| prev.replace('1', "2"));
+/// });
+///
+/// assert_eq!(*config.read(), "v2");
+/// ```
+pub struct RcuCell<T> {
+    inner: AtomicPtr<T>,
+    _phtm: PhantomData<Arc<T>>,
+}
+
+impl<T: 'static + Send + Sync> RcuCell<T> {
+    /// Creates a new cell with the specified value
+    #[inline]
+    pub fn new(v: T) -> Self {
+        return Self::from_arc(Arc::new(v));
+    }
+
+    /// Creates a new cell with the specified shared value
+    #[inline]
+    pub fn from_arc(v: Arc<T>) -> Self {
+        return Self {
+            inner: AtomicPtr::new(Arc::into_raw(v).cast_mut()),
+            _phtm: PhantomData,
+        };
+    }
+
+    /// Returns a guard to the current value of the cell.
+    ///
+    /// The guard keeps the current thread pinned, so it's value won't be released while it's alive.
+    /// Long-lived guards delay the reclamation of replaced values, so prefer [`load_full`](RcuCell::load_full) if the value must be kept around.
+    #[inline]
+    pub fn read(&self) -> RcuGuard<'_, T> {
+        let guard = epoch::pin();
+        let ptr = self.inner.load(Ordering::Acquire);
+        return RcuGuard {
+            _guard: guard,
+            ptr,
+            _phtm: PhantomData,
+        };
+    }
+
+    /// Returns a new strong reference to the current value of the cell.
+    #[inline]
+    pub fn load_full(&self) -> Arc<T> {
+        let _guard = epoch::pin();
+        let ptr = self.inner.load(Ordering::Acquire);
+        unsafe {
+            Arc::increment_strong_count(ptr);
+            return Arc::from_raw(ptr);
+        }
+    }
+
+    /// Publishes a new value, returning the previous one.
+    #[inline]
+    pub fn replace(&self, v: impl Into<Arc<T>>) -> Arc<T> {
+        let guard = epoch::pin();
+        let prev = self
+            .inner
+            .swap(Arc::into_raw(v.into()).cast_mut(), Ordering::AcqRel);
+
+        unsafe {
+            Arc::increment_strong_count(prev);
+            Self::defer_release(&guard, prev);
+            return Arc::from_raw(prev);
+        }
+    }
+
+    /// Publishes a new value.
+    #[inline]
+    pub fn store(&self, v: impl Into<Arc<T>>) {
+        let _: Arc<T> = self.replace(v);
+    }
+
+    /// Publishes the value returned by `f`, which is called with the current value.
+    ///
+    /// If another update is published concurrently, `f` is called again with the newer value, so it may be called multiple times.
+    pub fn update<F: FnMut(&T) -> T>(&self, mut f: F) {
+        let guard = epoch::pin();
+        let mut current = self.inner.load(Ordering::Acquire);
+
+        loop {
+            let new = Arc::into_raw(Arc::new(f(unsafe { &*current }))).cast_mut();
+            match self
+                .inner
+                .compare_exchange_weak(current, new, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(prev) => unsafe { return Self::defer_release(&guard, prev) },
+                Err(actual) => unsafe {
+                    drop(Arc::from_raw(new));
+                    current = actual;
+                },
+            }
+        }
+    }
+
+    /// Releases the cell's reference to `ptr` once no pinned reader can observe it anymore.
+    ///
+    /// The release may run arbitrarily late (even on another thread), which is why `T` must be `'static`.
+    #[inline]
+    unsafe fn defer_release(guard: &Guard, ptr: *mut T) {
+        let ptr = ptr.cast_const();
+        guard.defer_unchecked(move || drop(Arc::from_raw(ptr)));
+    }
+}
+
+impl<T> RcuCell<T> {
+    /// Returns a mutable reference to the current value, if it isn't shared.
+    ///
+    /// Values returned by [`load_full`](RcuCell::load_full) or [`replace`](RcuCell::replace) also count as shared, as do the ones of pending reclamations.
+    #[inline]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        let mut arc = unsafe { core::mem::ManuallyDrop::new(Arc::from_raw(*self.inner.get_mut())) };
+        // The cell keeps owning the value, so the reference may outlive the local handle
+        let ptr = Arc::get_mut(&mut arc).map(core::ptr::from_mut);
+        return ptr.map(|x| unsafe { &mut *x });
+    }
+
+    /// Returns the cell's current value.
+    #[inline]
+    pub fn into_inner(self) -> Arc<T> {
+        let this = core::mem::ManuallyDrop::new(self);
+        return unsafe { Arc::from_raw(this.inner.load(Ordering::Relaxed)) };
+    }
+}
+
+impl<T> Drop for RcuCell<T> {
+    #[inline]
+    fn drop(&mut self) {
+        // No reader can be borrowing the cell, so the current value can be released right away
+        unsafe { drop(Arc::from_raw(*self.inner.get_mut())) }
+    }
+}
+
+impl<T: 'static + Send + Sync + Default> Default for RcuCell<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: 'static + Send + Sync + Debug> Debug for RcuCell<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("RcuCell").field(&*self.read()).finish()
+    }
+}
+
+unsafe impl<T: Send + Sync> Send for RcuCell<T> {}
+unsafe impl<T: Send + Sync> Sync for RcuCell<T> {}
+
+/// Guard returned by [`RcuCell::read`]
+pub struct RcuGuard<'a, T> {
+    _guard: Guard,
+    ptr: *const T,
+    _phtm: PhantomData<&'a T>,
+}
+
+impl<T> Deref for RcuGuard<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.ptr }
+    }
+}
+
+impl<T: Debug> Debug for RcuGuard<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RcuCell;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_basic_functionality() {
+        let mut cell = RcuCell::new(1);
+        assert_eq!(*cell.read(), 1);
+
+        let guard = cell.read();
+        cell.store(2);
+        assert_eq!(*guard, 1);
+        assert_eq!(*cell.read(), 2);
+        drop(guard);
+
+        cell.update(|x| x * 10);
+        assert_eq!(*cell.load_full(), 20);
+
+        let prev = cell.replace(Arc::new(30));
+        assert_eq!(*prev, 20);
+        drop(prev);
+
+        if let Some(x) = cell.get_mut() {
+            *x += 1;
+            assert_eq!(*cell.read(), 31);
+        }
+
+        let shared = cell.load_full();
+        assert!(cell.get_mut().is_none());
+        assert!(Arc::ptr_eq(&shared, &cell.into_inner()));
+    }
+
+    #[test]
+    fn test_concurrent_read_update() {
+        const THREADS: usize = 4;
+        const RUNS: usize = if cfg!(miri) { 20 } else { 2000 };
+
+        let cell = RcuCell::new((0usize, 0usize));
+
+        std::thread::scope(|s| {
+            for _ in 0..THREADS {
+                s.spawn(|| {
+                    for _ in 0..RUNS {
+                        cell.update(|&(a, b)| (a + 1, b + 2));
+                    }
+                });
+
+                s.spawn(|| {
+                    let mut last = 0;
+                    for _ in 0..RUNS {
+                        let guard = cell.read();
+                        // Updates are never observed partially, nor out of order
+                        assert_eq!(guard.1, 2 * guard.0);
+                        assert!(guard.0 >= last);
+                        last = guard.0;
+                    }
+                });
+            }
+        });
+
+        assert_eq!(*cell.read(), (THREADS * RUNS, 2 * THREADS * RUNS));
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{
+    locks::{waiter, WaitQueue, Waiter},
+    AtomicCell,
+};
+
+/// A single-value mailbox that can be filled and emptied by multiple threads.
+///
+/// Values are stored with [`put`](Slot::put), which wakes one of the threads blocked on [`take_wait`](Slot::take_wait).
+/// If the slot already contains a value, it gets overwritten (the last value wins) and the previous value is returned.
+/// Every stored value is received by, at most, one taker.
+///
+/// # Example
+///
+/// ```rust
+/// use utils_atomics::Slot;
+///
+/// let slot = Slot::new();
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| slot.put(42));
+///     assert_eq!(slot.take_wait(), 42);
+/// });
+/// ```
+#[derive(Debug)]
+pub struct Slot<T> {
+    cell: AtomicCell<T>,
+    wakers: WaitQueue<Waiter>,
+}
+
+impl<T> Slot<T> {
+    /// Creates a new empty [`Slot`]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            cell: AtomicCell::new(None),
+            wakers: WaitQueue::new(),
+        }
+    }
+
+    /// Stores a value inside the slot, waking one of the threads waiting for it.
+    /// Returns the previous value of the slot, if it hadn't been taken yet.
+    ///
+    /// Threads that already stopped waiting are skipped (and unregistered), so the value is always announced to a thread
+    /// that's still blocked, if there's one.
+    #[inline]
+    pub fn put(&self, v: T) -> Option<T> {
+        let prev = self.cell.replace(v);
+        Waiter::wake_one(&self.wakers);
+        return prev;
+    }
+
+    /// Attempts to take the value of the slot without blocking, returning `None` if the slot is empty.
+    #[inline]
+    pub fn try_take(&self) -> Option<T> {
+        self.cell.take()
+    }
+
+    /// Blocks the current thread until a value is available, and then takes it.
+    pub fn take_wait(&self) -> T {
+        loop {
+            if let Some(v) = self.cell.take() {
+                return v;
+            }
+
+            // The waiter is unregistered when the guard is dropped, so that puts aren't announced to it once we return
+            let (waiter, sub, _guard) = waiter();
+            self.wakers.push(waiter);
+
+            // A value may have been stored before our waker was registered
+            if let Some(v) = self.cell.take() {
+                return v;
+            }
+
+            sub.wait();
+        }
+    }
+
+    /// Returns `true` if the slot currently contains a value.
+    /// # Safety
+    /// Whilst this method is not unsafe, it's result should be considered immediately stale.
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.cell.is_some()
+    }
+}
+
+impl<T> Default for Slot<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(feature = "std", test))]
+mod tests {
+    use super::Slot;
+    use crate::locks::waiter;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[test]
+    fn put_and_take() {
+        let slot = Slot::new();
+        assert_eq!(slot.try_take(), None);
+        assert_eq!(slot.put(1), None);
+        assert_eq!(slot.put(2), Some(1));
+        assert_eq!(slot.take_wait(), 2);
+        assert!(!slot.is_some());
+    }
+
+    #[test]
+    fn put_skips_returned_takers() {
+        let slot = Slot::new();
+
+        std::thread::scope(|s| {
+            let taker = s.spawn(|| slot.take_wait());
+            while slot.wakers.is_empty() {
+                std::thread::yield_now();
+            }
+
+            // Registers takers that already returned, on top of the blocked one
+            for _ in 0..3 {
+                let (waiter, _, _guard) = waiter();
+                slot.wakers.push(waiter);
+            }
+
+            assert_eq!(slot.put(1), None);
+            assert_eq!(taker.join().unwrap(), 1);
+        });
+
+        // Takers that already returned are removed by the next put
+        let (waiter, _, guard) = waiter();
+        slot.wakers.push(waiter);
+        drop(guard);
+        assert_eq!(slot.put(2), None);
+        assert!(slot.wakers.is_empty());
+    }
+
+    #[test]
+    fn two_putters_two_takers() {
+        const RUNS: usize = if cfg!(miri) { 10 } else { 500 };
+
+        for _ in 0..RUNS {
+            let slot = Slot::new();
+
+            std::thread::scope(|s| {
+                let takers = [s.spawn(|| slot.take_wait()), s.spawn(|| slot.take_wait())];
+                while slot.wakers.is_empty() {
+                    std::thread::yield_now();
+                }
+
+                for v in [1, 2] {
+                    let slot = &slot;
+                    // Values overwritten before being taken are put again, so that each taker gets exactly one
+                    s.spawn(move || {
+                        let mut v = Some(v);
+                        while let Some(x) = v {
+                            v = slot.put(x);
+                        }
+                    });
+                }
+
+                let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+                while takers.iter().any(|x| !x.is_finished()) && std::time::Instant::now() < deadline {
+                    std::thread::yield_now();
+                }
+
+                let lost = takers.iter().filter(|x| !x.is_finished()).count();
+                // Releases the takers either way, so that a failure doesn't hang the test
+                for _ in 0..lost {
+                    slot.put(0);
+                }
+
+                let taken = takers.map(|x| x.join().unwrap());
+                assert_eq!(lost, 0, "a put was announced to no one");
+                assert_eq!(taken.iter().sum::<i32>(), 3);
+            });
+        }
+    }
+
+    #[test]
+    fn producers_and_consumers() {
+        const PRODUCERS: u64 = 4;
+        const CONSUMERS: usize = 4;
+        const VALUES: u64 = 250;
+        const POISON: u64 = u64::MAX;
+
+        let slot = Slot::new();
+        let taken = AtomicU64::new(0);
+        let mut lost = 0;
+
+        std::thread::scope(|s| {
+            let consumers = (0..CONSUMERS)
+                .map(|_| {
+                    s.spawn(|| loop {
+                        match slot.take_wait() {
+                            POISON => break,
+                            v => taken.fetch_add(v, Ordering::Relaxed),
+                        };
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let producers = (0..PRODUCERS)
+                .map(|p| {
+                    let slot = &slot;
+                    s.spawn(move || {
+                        let mut lost = 0;
+                        for i in 1..=VALUES {
+                            lost += slot.put(p * VALUES + i).unwrap_or_default();
+                        }
+                        lost
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            for handle in producers {
+                lost += handle.join().unwrap();
+            }
+
+            let mut poisons = 0;
+            while poisons < CONSUMERS {
+                match slot.put(POISON) {
+                    Some(POISON) => {}
+                    prev => {
+                        lost += prev.unwrap_or_default();
+                        poisons += 1;
+                    }
+                }
+            }
+
+            for handle in consumers {
+                handle.join().unwrap();
+            }
+        });
+
+        let total = (1..=PRODUCERS * VALUES).sum::<u64>();
+        assert_eq!(taken.into_inner() + lost, total);
+        assert_eq!(slot.try_take(), None);
+    }
+}
@@ -0,0 +1,24 @@
+//! Atomic primitives used by the crate's lock-free structures.
+//!
+//! When compiled with `--cfg loom`, these are replaced by [`loom`](https://docs.rs/loom)'s
+//! instrumented atomics, so the structures can be verified under it's model checker.
+
+cfg_if::cfg_if! {
+    if #[cfg(loom)] {
+        pub(crate) use loom::sync::atomic;
+
+        /// Non-atomically reads the pointer, made safe by the mutable reference.
+        #[inline]
+        pub(crate) fn get_mut_ptr<T>(ptr: &mut atomic::AtomicPtr<T>) -> *mut T {
+            return ptr.with_mut(|x| *x);
+        }
+    } else {
+        pub(crate) use core::sync::atomic;
+
+        /// Non-atomically reads the pointer, made safe by the mutable reference.
+        #[inline]
+        pub(crate) fn get_mut_ptr<T>(ptr: &mut atomic::AtomicPtr<T>) -> *mut T {
+            return *ptr.get_mut();
+        }
+    }
+}
@@ -0,0 +1,426 @@
+use alloc::boxed::Box;
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+cfg_if::cfg_if! {
+    if #[cfg(target_pointer_width = "64")] {
+        // Current 64-bit platforms don't use the top 16 bits of user-space addresses
+        const HIGH_TAG_BITS: u32 = 16;
+    } else {
+        const HIGH_TAG_BITS: u32 = 0;
+    }
+}
+
+/// A snapshot of the contents of a [`TaggedCell`], as returned by [`TaggedCell::load`].
+///
+/// It contains the address of the cell's value alongside the cell's generation tag,
+/// and is used as the expected value of [`TaggedCell::compare_exchange`].
+pub struct TaggedPtr<T> {
+    inner: usize,
+    _phantom: PhantomData<*mut T>,
+}
+
+impl<T> TaggedPtr<T> {
+    /// Returns the pointer stored in the cell at the moment of the snapshot.
+    ///
+    /// # Safety
+    /// Whilst this method is not unsafe, the returned pointer may be dangling by the time it's used,
+    /// since other threads may have taken (and dropped) it's value.
+    #[inline]
+    pub fn as_ptr(self) -> *mut T {
+        (self.inner & !TaggedCell::<T>::TAG_MASK) as *mut T
+    }
+
+    /// Returns the generation tag of the cell at the moment of the snapshot
+    #[inline]
+    pub fn tag(self) -> usize {
+        let low = self.inner & TaggedCell::<T>::LOW_TAG_MASK;
+        let high = (self.inner & TaggedCell::<T>::HIGH_TAG_MASK).rotate_left(HIGH_TAG_BITS);
+        return low | (high << TaggedCell::<T>::LOW_TAG_BITS);
+    }
+
+    /// Returns `true` if the cell was empty at the moment of the snapshot
+    #[inline]
+    pub fn is_null(self) -> bool {
+        self.as_ptr().is_null()
+    }
+}
+
+impl<T> Clone for TaggedPtr<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TaggedPtr<T> {}
+
+impl<T> PartialEq for TaggedPtr<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T> Eq for TaggedPtr<T> {}
+
+impl<T> core::fmt::Debug for TaggedPtr<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TaggedPtr")
+            .field("ptr", &self.as_ptr())
+            .field("tag", &self.tag())
+            .finish()
+    }
+}
+
+/// An atomic cell, akin to [`AtomicCell`](crate::AtomicCell), that protects it's compare-and-swap operations against the ABA problem.
+///
+/// Every store to the cell increments a generation tag, which is packed into the bits of the pointer that are left unused: the low bits
+/// that the alignment of `T` keeps clear and, on 64-bit targets, the top 16 bits, which current platforms don't use for user-space addresses.
+/// A [`compare_exchange`](TaggedCell::compare_exchange) only succeeds if both the pointer and the tag match, so a value that has been
+/// replaced and later stored back (or an allocation that has been freed and reused) isn't mistaken for the original one.
+///
+/// The tag wraps around after `align_of::<T>() << 16` generations on 64-bit targets, and after `align_of::<T>()` generations on the rest,
+/// so types with bigger alignments are better protected.
+///
+/// Types with an alignment of 1 are rejected at compile time, since their pointers have no spare bits for a tag.
+///
+/// ```compile_fail
+/// let cell = utils_atomics::TaggedCell::new(1u8);
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use utils_atomics::TaggedCell;
+///
+/// let cell = TaggedCell::new(1u64);
+/// let snapshot = cell.load(std::sync::atomic::Ordering::Acquire);
+///
+/// // Take the value and store it back. The pointer is the same, but the generation isn't.
+/// let value = cell.take_boxed();
+/// cell.replace_boxed(value);
+///
+/// assert!(cell.compare_exchange(snapshot, Some(2)).is_err());
+/// ```
+pub struct TaggedCell<T> {
+    inner: AtomicUsize,
+    _phantom: PhantomData<Option<Box<T>>>,
+}
+
+impl<T> TaggedCell<T> {
+    const LOW_TAG_MASK: usize = {
+        assert!(
+            core::mem::align_of::<T>() > 1,
+            "the alignment of the type leaves no spare bits for a tag"
+        );
+        core::mem::align_of::<T>() - 1
+    };
+    const LOW_TAG_BITS: u32 = Self::LOW_TAG_MASK.count_ones();
+    const HIGH_TAG_MASK: usize = !(usize::MAX >> HIGH_TAG_BITS);
+    /// Bits of the packed pointer that are used by the tag
+    const TAG_MASK: usize = Self::LOW_TAG_MASK | Self::HIGH_TAG_MASK;
+    /// Amount of bits of the tag, which wraps around after `2^TAG_BITS` generations
+    const TAG_BITS: u32 = Self::LOW_TAG_BITS + HIGH_TAG_BITS;
+
+    /// Constructs a new `TaggedCell` containing an optional value `t`, with a generation tag of zero.
+    #[inline]
+    pub fn new(t: impl Into<Option<T>>) -> Self {
+        Self::new_boxed(t.into().map(Box::new))
+    }
+
+    /// Constructs a new `TaggedCell` from an optional boxed value `t`, with a generation tag of zero.
+    #[inline]
+    pub fn new_boxed(t: impl Into<Option<Box<T>>>) -> Self {
+        return Self {
+            inner: AtomicUsize::new(Self::pack(Self::into_ptr(t.into()), 0)),
+            _phantom: PhantomData,
+        };
+    }
+
+    /// Loads a snapshot of the cell's pointer and generation tag
+    #[inline]
+    pub fn load(&self, order: Ordering) -> TaggedPtr<T> {
+        TaggedPtr {
+            inner: self.inner.load(order),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the current generation tag of the cell
+    #[inline]
+    pub fn tag(&self) -> usize {
+        self.load(Ordering::Relaxed).tag()
+    }
+
+    /// Replaces the value inside the `TaggedCell` with a new optional value `new`, incrementing the generation tag.
+    /// Returns the old value as an optional value. If the `TaggedCell` was empty, returns `None`.
+    #[inline]
+    pub fn replace(&self, new: impl Into<Option<T>>) -> Option<T> {
+        self.replace_boxed(new.into().map(Box::new)).map(|x| *x)
+    }
+
+    /// Replaces the value inside the `TaggedCell` with a new optional boxed value `new`, incrementing the generation tag.
+    /// Returns the old value as an optional boxed value. If the `TaggedCell` was empty, returns `None`.
+    pub fn replace_boxed(&self, new: impl Into<Option<Box<T>>>) -> Option<Box<T>> {
+        let new = Self::into_ptr(new.into());
+        let mut current = self.load(Ordering::Relaxed);
+
+        loop {
+            let next = Self::pack(new, Self::next_tag(current));
+            match self.inner.compare_exchange_weak(
+                current.inner,
+                next,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return unsafe { Self::from_ptr(current.as_ptr()) },
+                Err(actual) => current.inner = actual,
+            }
+        }
+    }
+
+    /// Takes the value out of the `TaggedCell`, leaving it empty and incrementing the generation tag.
+    /// Returns an optional value. If the `TaggedCell` is empty, returns `None`.
+    #[inline]
+    pub fn take(&self) -> Option<T> {
+        self.take_boxed().map(|x| *x)
+    }
+
+    /// Takes the value out of the `TaggedCell`, leaving it empty and incrementing the generation tag.
+    /// Returns an optional boxed value. If the `TaggedCell` is empty, returns `None`.
+    #[inline]
+    pub fn take_boxed(&self) -> Option<Box<T>> {
+        self.replace_boxed(None)
+    }
+
+    /// Stores `new` into the cell if both it's pointer and generation tag are the same as in `current`, incrementing the tag.
+    ///
+    /// On success, the previous value is returned.
+    ///
+    /// # Errors
+    /// If the cell's contents don't match `current`, `new` is handed back alongside a snapshot of the cell's actual contents.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use utils_atomics::TaggedCell;
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// let cell = TaggedCell::new(1u32);
+    /// let current = cell.load(Ordering::Acquire);
+    ///
+    /// assert_eq!(cell.compare_exchange(current, Some(2)), Ok(Some(1)));
+    /// let (actual, new) = cell.compare_exchange(current, Some(3)).unwrap_err();
+    ///
+    /// assert_eq!(actual.tag(), current.tag() + 1);
+    /// assert_eq!(new, Some(3));
+    /// ```
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: TaggedPtr<T>,
+        new: impl Into<Option<T>>,
+    ) -> Result<Option<T>, (TaggedPtr<T>, Option<T>)> {
+        match self.compare_exchange_boxed(current, new.into().map(Box::new)) {
+            Ok(prev) => Ok(prev.map(|x| *x)),
+            Err((actual, new)) => Err((actual, new.map(|x| *x))),
+        }
+    }
+
+    /// Stores `new` into the cell if both it's pointer and generation tag are the same as in `current`, incrementing the tag.
+    ///
+    /// On success, the previous boxed value is returned.
+    ///
+    /// # Errors
+    /// If the cell's contents don't match `current`, `new` is handed back alongside a snapshot of the cell's actual contents.
+    #[allow(clippy::type_complexity)]
+    pub fn compare_exchange_boxed(
+        &self,
+        current: TaggedPtr<T>,
+        new: impl Into<Option<Box<T>>>,
+    ) -> Result<Option<Box<T>>, (TaggedPtr<T>, Option<Box<T>>)> {
+        let new = Self::into_ptr(new.into());
+        let next = Self::pack(new, Self::next_tag(current));
+
+        match self.inner.compare_exchange(
+            current.inner,
+            next,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => unsafe { Ok(Self::from_ptr(current.as_ptr())) },
+            Err(actual) => {
+                let actual = TaggedPtr {
+                    inner: actual,
+                    _phantom: PhantomData,
+                };
+                return unsafe { Err((actual, Self::from_ptr(new))) };
+            }
+        }
+    }
+
+    /// Returns a mutable reference to the value inside the `TaggedCell`, if any.
+    /// If the `TaggedCell` is empty, returns `None`.
+    #[inline]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        let ptr = (*self.inner.get_mut() & !Self::TAG_MASK) as *mut T;
+        return unsafe { ptr.as_mut() };
+    }
+
+    /// Returns `true` if the `TaggedCell` contains a value.
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        return !self.is_none();
+    }
+
+    /// Returns `true` if the `TaggedCell` is empty.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        return self.load(Ordering::Relaxed).is_null();
+    }
+
+    #[inline]
+    fn into_ptr(t: Option<Box<T>>) -> *mut T {
+        match t {
+            Some(t) => Box::into_raw(t),
+            None => core::ptr::null_mut(),
+        }
+    }
+
+    #[inline]
+    unsafe fn from_ptr(ptr: *mut T) -> Option<Box<T>> {
+        if ptr.is_null() {
+            return None;
+        }
+        return Some(Box::from_raw(ptr));
+    }
+
+    #[inline]
+    fn next_tag(current: TaggedPtr<T>) -> usize {
+        return current.tag().wrapping_add(1) & (usize::MAX >> (usize::BITS - Self::TAG_BITS));
+    }
+
+    #[inline]
+    fn pack(ptr: *mut T, tag: usize) -> usize {
+        let addr = ptr as usize;
+        debug_assert_eq!(addr & Self::LOW_TAG_MASK, 0, "pointer isn't sufficiently aligned");
+        debug_assert_eq!(addr & Self::HIGH_TAG_MASK, 0, "pointer uses the high bits reserved for the tag");
+        debug_assert_eq!(tag >> Self::TAG_BITS, 0, "tag is wider than the bits reserved for it");
+
+        let low = tag & Self::LOW_TAG_MASK;
+        let high = (tag >> Self::LOW_TAG_BITS).rotate_right(HIGH_TAG_BITS) & Self::HIGH_TAG_MASK;
+        return addr | low | high;
+    }
+}
+
+impl<T> Drop for TaggedCell<T> {
+    #[inline]
+    fn drop(&mut self) {
+        let ptr = (*self.inner.get_mut() & !Self::TAG_MASK) as *mut T;
+        let _: Option<Box<T>> = unsafe { Self::from_ptr(ptr) };
+    }
+}
+
+impl<T> core::fmt::Debug for TaggedCell<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("TaggedCell")
+            .field(&self.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+unsafe impl<T: Send> Send for TaggedCell<T> {}
+unsafe impl<T: Sync> Sync for TaggedCell<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::TaggedCell;
+    use alloc::boxed::Box;
+    use core::sync::atomic::Ordering;
+
+    #[test]
+    fn replace_increments_tag() {
+        let cell = TaggedCell::new(1u64);
+        assert_eq!(cell.tag(), 0);
+        assert_eq!(cell.replace(2), Some(1));
+        assert_eq!(cell.tag(), 1);
+        assert_eq!(cell.take(), Some(2));
+        assert_eq!(cell.tag(), 2);
+        assert!(cell.is_none());
+    }
+
+    #[test]
+    fn tag_wraps_around() {
+        let cell = TaggedCell::<u32>::new(None);
+        let generations = 1usize << TaggedCell::<u32>::TAG_BITS;
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(generations, core::mem::align_of::<u32>() << 16);
+
+        // Every generation has it's own tag, and none of them disturbs the pointer
+        for i in 1..generations - 1 {
+            assert_eq!(cell.take(), None);
+            assert_eq!(cell.tag(), i);
+        }
+
+        // Values are still reachable while every bit of the tag is set
+        assert_eq!(cell.replace(1), None);
+        assert_eq!(cell.tag(), generations - 1);
+        assert_eq!(cell.take(), Some(1));
+        assert_eq!(cell.tag(), 0);
+    }
+
+    #[test]
+    fn aba_is_detected() {
+        let cell = TaggedCell::new_boxed(Box::new(1u64));
+        let snapshot = cell.load(Ordering::Acquire);
+
+        // Another thread takes the value out and puts the same allocation back (A -> B -> A)
+        let value = cell.take_boxed().unwrap();
+        let addr = core::ptr::addr_of!(*value);
+        assert_eq!(cell.replace_boxed(value), None);
+
+        let current = cell.load(Ordering::Acquire);
+        assert_eq!(current.as_ptr().cast_const(), addr);
+        assert_eq!(snapshot.as_ptr(), current.as_ptr());
+        assert_ne!(snapshot.tag(), current.tag());
+
+        // A plain pointer comparison would succeed here, but the tag makes it fail
+        let (actual, new) = cell.compare_exchange(snapshot, Some(2)).unwrap_err();
+        assert_eq!(actual, current);
+        assert_eq!(new, Some(2));
+
+        assert_eq!(cell.compare_exchange(current, Some(2)), Ok(Some(1)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn token_passing() {
+        const THREADS: u64 = 8;
+        const ITERATIONS: u64 = 1000;
+
+        let cell = TaggedCell::new(0u64);
+        std::thread::scope(|s| {
+            for _ in 0..THREADS {
+                s.spawn(|| {
+                    let mut done = 0;
+                    while done < ITERATIONS {
+                        let Some(v) = cell.take() else { continue };
+
+                        // Only the holder of the value may store into the cell, but empty takes still bump the tag
+                        let mut empty = cell.load(Ordering::Acquire);
+                        while let Err((actual, _)) = cell.compare_exchange(empty, Some(v + 1)) {
+                            assert!(actual.is_null());
+                            empty = actual;
+                        }
+                        done += 1;
+                    }
+                });
+            }
+        });
+
+        assert_eq!(cell.take(), Some(THREADS * ITERATIONS));
+    }
+}
@@ -1,10 +1,24 @@
-use crate::{InnerAtomicFlag, FALSE, TRUE};
+use crate::{InnerAtomicFlag, InnerFlag, FALSE, TRUE};
 use core::{
     cell::UnsafeCell,
     mem::{needs_drop, MaybeUninit},
     sync::atomic::Ordering,
 };
 
+const UNINIT: InnerFlag = 2;
+const SETTING: InnerFlag = 3;
+
+/// State of a [`TakeCell`], as returned by [`state`](TakeCell::state)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TakeState {
+    /// The cell was created with [`new_uninit`](TakeCell::new_uninit), and it's value hasn't been [`set`](TakeCell::set) yet
+    Empty,
+    /// The cell holds a value that hasn't been taken yet
+    Available,
+    /// The cell's value has already been taken
+    Taken,
+}
+
 /// Inverse of a `OnceCell`. It initializes with a value, which then can be raced by other threads to take.
 ///
 /// Once the value is taken, it can never be taken again.
@@ -32,12 +46,65 @@ impl<T> TakeCell<T> {
         }
     }
 
+    /// Creates a [`TakeCell`] without a value, which can later be initialized with [`set`](TakeCell::set)
+    #[inline]
+    pub const fn new_uninit() -> Self {
+        Self {
+            taken: InnerAtomicFlag::new(UNINIT),
+            v: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Initializes the value of a cell created with [`new_uninit`](TakeCell::new_uninit).
+    ///
+    /// # Errors
+    /// This method returns the value back if the cell has already been initialized or taken.
+    pub fn set(&self, v: T) -> Result<(), T> {
+        if self
+            .taken
+            .compare_exchange(UNINIT, SETTING, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(v);
+        }
+
+        unsafe { (*self.v.get()).write(v) };
+        self.taken.store(FALSE, Ordering::Release);
+        return Ok(());
+    }
+
     /// Checks if the cell has alredy been taken
     #[inline]
     pub fn is_taken(&self) -> bool {
         self.taken.load(Ordering::Relaxed) == TRUE
     }
 
+    /// Returns the current state of the cell.
+    ///
+    /// A cell whose value is still being [`set`](TakeCell::set) is reported as [`Empty`](TakeState::Empty).
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::{TakeCell, TakeState};
+    ///
+    /// let cell = TakeCell::new_uninit();
+    /// assert_eq!(cell.state(), TakeState::Empty);
+    ///
+    /// cell.set(42).unwrap();
+    /// assert_eq!(cell.state(), TakeState::Available);
+    ///
+    /// assert_eq!(cell.try_take(), Some(42));
+    /// assert_eq!(cell.state(), TakeState::Taken);
+    /// ```
+    #[inline]
+    pub fn state(&self) -> TakeState {
+        return match self.taken.load(Ordering::Relaxed) {
+            FALSE => TakeState::Available,
+            TRUE => TakeState::Taken,
+            _ => TakeState::Empty,
+        };
+    }
+
     /// Attempts to take the value from the cell, returning `None` if the value has already been taken
     #[inline]
     pub fn try_take(&self) -> Option<T> {
@@ -69,6 +136,49 @@ impl<T> TakeCell<T> {
         }
         None
     }
+
+    /// Consumes the cell, returning it's value if it hasn't been taken yet.
+    ///
+    /// Since the cell is owned, no atomic operations are needed to take it's value.
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::TakeCell;
+    ///
+    /// assert_eq!(TakeCell::new(42).into_inner(), Some(42));
+    ///
+    /// let cell = TakeCell::new(42);
+    /// cell.try_take();
+    /// assert_eq!(cell.into_inner(), None);
+    /// ```
+    #[inline]
+    pub fn into_inner(mut self) -> Option<T> {
+        // Marks the cell as taken, so it's destructor won't drop the value again
+        return self.try_take_mut();
+    }
+
+    /// Consumes the cell, returning a new one with the result of applying `f` to it's value (or `None` if it has already been taken).
+    ///
+    /// If `f` returns `None`, the new cell is created already taken.
+    /// Since the cell is owned, no atomic operations are needed to take it's value.
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::TakeCell;
+    ///
+    /// let cell = TakeCell::new(21).map(|x| x.map(|x| x * 2));
+    /// assert_eq!(cell.try_take(), Some(42));
+    ///
+    /// let cell = TakeCell::<i32>::new_taken().map(|x| Some(x.unwrap_or_default()));
+    /// assert_eq!(cell.try_take(), Some(0));
+    /// ```
+    #[inline]
+    pub fn map<U, F: FnOnce(Option<T>) -> Option<U>>(mut self, f: F) -> TakeCell<U> {
+        return match f(self.try_take_mut()) {
+            Some(v) => TakeCell::new(v),
+            None => TakeCell::new_taken(),
+        };
+    }
 }
 
 impl<T> Drop for TakeCell<T> {
@@ -83,6 +193,246 @@ impl<T> Drop for TakeCell<T> {
 unsafe impl<T: Send> Send for TakeCell<T> {}
 unsafe impl<T: Sync> Sync for TakeCell<T> {}
 
+/// Takes the value of the first cell that hasn't been taken yet, returning it alongside it's index.
+/// Returns `None` if every cell has already been taken (or hasn't been initialized).
+///
+/// This turns a slice of [`TakeCell`]s into a simple pool of one-shot resources. Since every cell is taken atomically,
+/// concurrent callers will never take the same slot.
+///
+/// # Example
+///
+/// ```rust
+/// use utils_atomics::{take_first, TakeCell};
+///
+/// let pool = [TakeCell::new_taken(), TakeCell::new('a'), TakeCell::new('b')];
+/// assert_eq!(take_first(&pool), Some((1, 'a')));
+/// assert_eq!(take_first(&pool), Some((2, 'b')));
+/// assert_eq!(take_first(&pool), None);
+/// ```
+pub fn take_first<T>(cells: &[TakeCell<T>]) -> Option<(usize, T)> {
+    return cells
+        .iter()
+        .enumerate()
+        .find_map(|(i, cell)| Some((i, cell.try_take()?)));
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "std")] {
+        use crate::{locks::{lock, Lock}, FillQueue};
+
+        /// A [`TakeCell`] whose value may be set after it's creation, allowing threads to block until it's available.
+        #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+        pub struct TakeLatch<T> {
+            cell: TakeCell<T>,
+            wakers: FillQueue<Lock>,
+        }
+
+        impl<T> TakeLatch<T> {
+            /// Creates a new [`TakeLatch`] that already contains a value
+            #[inline]
+            pub const fn new(v: T) -> Self {
+                Self {
+                    cell: TakeCell::new(v),
+                    wakers: FillQueue::new(),
+                }
+            }
+
+            /// Creates a new [`TakeLatch`] without a value, which can later be initialized with [`set`](TakeLatch::set)
+            #[inline]
+            pub const fn new_uninit() -> Self {
+                Self {
+                    cell: TakeCell::new_uninit(),
+                    wakers: FillQueue::new(),
+                }
+            }
+
+            /// Initializes the value of the latch, waking the threads that are waiting for it.
+            ///
+            /// # Errors
+            /// This method returns the value back if the latch has already been initialized or taken.
+            #[inline]
+            pub fn set(&self, v: T) -> Result<(), T> {
+                self.cell.set(v)?;
+                self.wakers.chop().for_each(Lock::wake);
+                return Ok(());
+            }
+
+            /// Checks if the latch has alredy been taken
+            #[inline]
+            pub fn is_taken(&self) -> bool {
+                self.cell.is_taken()
+            }
+
+            /// Returns the current state of the latch
+            #[inline]
+            pub fn state(&self) -> TakeState {
+                self.cell.state()
+            }
+
+            /// Attempts to take the value from the latch, returning `None` if the value isn't available
+            #[inline]
+            pub fn try_take(&self) -> Option<T> {
+                let v = self.cell.try_take()?;
+                // Waiting threads will no longer be able to take the value
+                self.wakers.chop().for_each(Lock::wake);
+                return Some(v);
+            }
+
+            /// Blocks the current thread until the value is available and takes it,
+            /// or until the timeout expires.
+            ///
+            /// Returns `None` if the timeout expired or if the value was taken by another thread.
+            pub fn take_wait_timeout(&self, dur: core::time::Duration) -> Option<T> {
+                if let Some(v) = self.try_take() {
+                    return Some(v);
+                }
+
+                let deadline = std::time::Instant::now() + dur;
+                loop {
+                    let (lock, sub) = lock();
+                    self.wakers.push(lock);
+
+                    // The value may have been set before our waker was registered
+                    if let Some(v) = self.try_take() {
+                        return Some(v);
+                    } else if self.is_taken() {
+                        return None;
+                    }
+
+                    let now = std::time::Instant::now();
+                    if now >= deadline {
+                        return None;
+                    }
+                    sub.wait_timeout(deadline - now);
+                }
+            }
+        }
+
+        impl<T> Default for TakeLatch<T> {
+            #[inline]
+            fn default() -> Self {
+                Self::new_uninit()
+            }
+        }
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(all(feature = "alloc", feature = "futures"))] {
+        use crate::flag::mpsc::{async_flag, AsyncFlag};
+
+        /// An asynchronous [`TakeCell`] whose value may be set after it's creation, allowing a task to wait until it's available.
+        ///
+        /// # Example
+        /// ```rust
+        /// use utils_atomics::AsyncTakeLatch;
+        /// use std::sync::Arc;
+        ///
+        /// # #[tokio::main]
+        /// # async fn main() {
+        /// let latch = Arc::new(AsyncTakeLatch::new_uninit());
+        ///
+        /// let handle = tokio::spawn({
+        ///     let latch = latch.clone();
+        ///     async move { latch.take().await }
+        /// });
+        ///
+        /// assert_eq!(latch.set(42), Ok(()));
+        /// assert_eq!(handle.await.unwrap(), Some(42));
+        /// # }
+        /// ```
+        #[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "futures"))))]
+        pub struct AsyncTakeLatch<T> {
+            cell: TakeCell<T>,
+            wakers: crate::FillQueue<AsyncFlag>,
+        }
+
+        impl<T> AsyncTakeLatch<T> {
+            /// Creates a new [`AsyncTakeLatch`] that already contains a value
+            #[inline]
+            pub const fn new(v: T) -> Self {
+                Self {
+                    cell: TakeCell::new(v),
+                    wakers: crate::FillQueue::new(),
+                }
+            }
+
+            /// Creates a new [`AsyncTakeLatch`] without a value, which can later be initialized with [`set`](AsyncTakeLatch::set)
+            #[inline]
+            pub const fn new_uninit() -> Self {
+                Self {
+                    cell: TakeCell::new_uninit(),
+                    wakers: crate::FillQueue::new(),
+                }
+            }
+
+            /// Initializes the value of the latch, waking the task that is waiting for it.
+            ///
+            /// # Errors
+            /// This method returns the value back if the latch has already been initialized or taken.
+            #[inline]
+            pub fn set(&self, v: T) -> Result<(), T> {
+                self.cell.set(v)?;
+                self.wakers.chop().for_each(AsyncFlag::mark);
+                return Ok(());
+            }
+
+            /// Checks if the latch has alredy been taken
+            #[inline]
+            pub fn is_taken(&self) -> bool {
+                self.cell.is_taken()
+            }
+
+            /// Returns the current state of the latch
+            #[inline]
+            pub fn state(&self) -> TakeState {
+                self.cell.state()
+            }
+
+            /// Attempts to take the value from the latch, returning `None` if the value isn't available
+            #[inline]
+            pub fn try_take(&self) -> Option<T> {
+                let v = self.cell.try_take()?;
+                // Waiting tasks will no longer be able to take the value
+                self.wakers.chop().for_each(AsyncFlag::mark);
+                return Some(v);
+            }
+
+            /// Waits until the value is available and takes it.
+            /// If the value has already been set, the future resolves immediately.
+            ///
+            /// Returns `None` if the value was taken by someone else.
+            pub async fn take(&self) -> Option<T> {
+                loop {
+                    if let Some(v) = self.try_take() {
+                        return Some(v);
+                    } else if self.is_taken() {
+                        return None;
+                    }
+
+                    let (flag, sub) = async_flag();
+                    self.wakers.push(flag);
+
+                    // The value may have been set before our flag was registered
+                    if let Some(v) = self.try_take() {
+                        return Some(v);
+                    } else if self.is_taken() {
+                        return None;
+                    }
+                    sub.await;
+                }
+            }
+        }
+
+        impl<T> Default for AsyncTakeLatch<T> {
+            #[inline]
+            fn default() -> Self {
+                Self::new_uninit()
+            }
+        }
+    }
+}
+
 // Thanks ChatGPT!
 #[cfg(test)]
 mod tests {
@@ -101,6 +451,178 @@ mod tests {
         assert_eq!(cell.try_take_mut(), None);
     }
 
+    #[test]
+    fn test_take_first() {
+        use super::take_first;
+
+        let cells = [TakeCell::new(1), TakeCell::new_uninit(), TakeCell::new(3)];
+        assert_eq!(take_first(&cells), Some((0, 1)));
+        assert_eq!(take_first(&cells), Some((2, 3)));
+        assert_eq!(take_first(&cells), None);
+
+        assert_eq!(cells[1].set(2), Ok(()));
+        assert_eq!(take_first(&cells), Some((1, 2)));
+        assert_eq!(take_first::<i32>(&[]), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_take_first_stressed() {
+        use super::take_first;
+        use alloc::vec::Vec;
+
+        const THREADS: usize = 8;
+        const SLOTS: usize = 1000;
+
+        let cells = (0..SLOTS).map(TakeCell::new).collect::<Vec<_>>();
+        let mut taken = std::thread::scope(|s| {
+            let handles = (0..THREADS)
+                .map(|_| {
+                    s.spawn(|| {
+                        let mut taken = Vec::new();
+                        while let Some((i, v)) = take_first(&cells) {
+                            assert_eq!(i, v);
+                            taken.push(i);
+                        }
+                        taken
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        // Every slot must have been taken exactly once
+        taken.sort_unstable();
+        assert_eq!(taken, (0..SLOTS).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_uninit() {
+        let cell = TakeCell::new_uninit();
+        assert_eq!(cell.is_taken(), false);
+        assert_eq!(cell.try_take(), None);
+        assert_eq!(cell.set(42), Ok(()));
+        assert_eq!(cell.set(43), Err(43));
+        assert_eq!(cell.try_take(), Some(42));
+        assert_eq!(cell.set(44), Err(44));
+        assert_eq!(cell.is_taken(), true);
+    }
+
+    #[test]
+    fn test_state() {
+        use super::TakeState;
+
+        let cell = TakeCell::new(42);
+        assert_eq!(cell.state(), TakeState::Available);
+        assert_eq!(cell.try_take(), Some(42));
+        assert_eq!(cell.state(), TakeState::Taken);
+        assert_eq!(cell.try_take(), None);
+        assert_eq!(cell.state(), TakeState::Taken);
+
+        let cell = TakeCell::new_uninit();
+        assert_eq!(cell.state(), TakeState::Empty);
+        assert_eq!(cell.try_take(), None);
+        assert_eq!(cell.state(), TakeState::Empty);
+        assert_eq!(cell.set(42), Ok(()));
+        assert_eq!(cell.state(), TakeState::Available);
+        assert_eq!(cell.set(43), Err(43));
+        assert_eq!(cell.state(), TakeState::Available);
+
+        let mut cell = cell;
+        assert_eq!(cell.try_take_mut(), Some(42));
+        assert_eq!(cell.state(), TakeState::Taken);
+        assert_eq!(TakeCell::<i32>::new_taken().state(), TakeState::Taken);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_into_inner() {
+        use alloc::sync::Arc;
+
+        let value = Arc::new(42);
+
+        let cell = TakeCell::new(value.clone());
+        assert_eq!(Arc::strong_count(&value), 2);
+        let inner = cell.into_inner().unwrap();
+        assert!(Arc::ptr_eq(&inner, &value));
+        assert_eq!(Arc::strong_count(&value), 2);
+        drop(inner);
+        assert_eq!(Arc::strong_count(&value), 1);
+
+        let cell = TakeCell::new(value.clone());
+        drop(cell.try_take());
+        assert_eq!(Arc::strong_count(&value), 1);
+        assert!(cell.into_inner().is_none());
+        assert_eq!(Arc::strong_count(&value), 1);
+
+        assert!(TakeCell::<Arc<i32>>::new_uninit().into_inner().is_none());
+        assert!(TakeCell::<Arc<i32>>::new_taken().into_inner().is_none());
+    }
+
+    #[test]
+    fn test_map() {
+        let cell = TakeCell::new(21).map(|x| x.map(|x| x * 2));
+        assert_eq!(cell.is_taken(), false);
+        assert_eq!(cell.try_take(), Some(42));
+
+        let cell = cell.map(|x: Option<i32>| x.map(i64::from));
+        assert_eq!(cell.is_taken(), true);
+        assert_eq!(cell.try_take(), None);
+
+        let cell = TakeCell::<i32>::new_uninit().map(|x| Some(x.is_none()));
+        assert_eq!(cell.try_take(), Some(true));
+
+        let cell = TakeCell::new(1).map(|_| None::<i32>);
+        assert_eq!(cell.is_taken(), true);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_latch_immediate() {
+        use super::TakeLatch;
+        use core::time::Duration;
+
+        let latch = TakeLatch::new(42);
+        assert_eq!(latch.take_wait_timeout(Duration::from_secs(10)), Some(42));
+        assert_eq!(latch.take_wait_timeout(Duration::ZERO), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_latch_delayed() {
+        use super::TakeLatch;
+        use core::time::Duration;
+
+        let latch = TakeLatch::new_uninit();
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                std::thread::sleep(Duration::from_millis(100));
+                latch.set(42).unwrap();
+            });
+            assert_eq!(latch.take_wait_timeout(Duration::from_secs(10)), Some(42));
+        });
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_latch_timeout() {
+        use super::TakeLatch;
+        use core::time::Duration;
+        use std::time::Instant;
+
+        let latch = TakeLatch::<i32>::new_uninit();
+        let now = Instant::now();
+        assert_eq!(latch.take_wait_timeout(Duration::from_millis(100)), None);
+        assert!(now.elapsed() >= Duration::from_millis(100));
+
+        assert_eq!(latch.set(42), Ok(()));
+        assert_eq!(latch.try_take(), Some(42));
+    }
+
     #[cfg(feature = "std")]
     #[test]
     fn test_stressed_conditions() {
@@ -131,4 +653,50 @@ mod tests {
         assert_eq!(cell.is_taken(), true);
         assert_eq!(cell.try_take(), None);
     }
+
+    #[cfg(all(feature = "std", feature = "futures"))]
+    #[tokio::test]
+    async fn test_async_latch_set_before_take() {
+        use super::AsyncTakeLatch;
+
+        let latch = AsyncTakeLatch::new_uninit();
+        assert_eq!(latch.set(1), Ok(()));
+        assert_eq!(latch.take().await, Some(1));
+        assert!(latch.is_taken());
+        assert_eq!(latch.take().await, None);
+    }
+
+    #[cfg(all(feature = "std", feature = "futures"))]
+    #[tokio::test]
+    async fn test_async_latch_take_before_set() {
+        use super::AsyncTakeLatch;
+        use std::{sync::Arc, time::Duration};
+
+        let latch = Arc::new(AsyncTakeLatch::new_uninit());
+        let handle = tokio::spawn({
+            let latch = latch.clone();
+            async move { latch.take().await }
+        });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(latch.set(2), Ok(()));
+
+        let v = tokio::time::timeout(Duration::from_secs(10), handle)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(v, Some(2));
+    }
+
+    #[cfg(all(feature = "std", feature = "futures"))]
+    #[tokio::test]
+    async fn test_async_latch_double_set() {
+        use super::AsyncTakeLatch;
+
+        let latch = AsyncTakeLatch::new_uninit();
+        assert_eq!(latch.set(1), Ok(()));
+        assert_eq!(latch.set(2), Err(2));
+        assert_eq!(latch.take().await, Some(1));
+        assert_eq!(latch.set(3), Err(3));
+    }
 }
@@ -1,5 +1,6 @@
 #[allow(unused_imports)]
 use core::sync::atomic::Ordering::{self, *};
+use core::ops::Not;
 use docfg::docfg;
 
 #[allow(non_camel_case_types)]
@@ -77,6 +78,94 @@ pub unsafe trait Atomic: Send + Sync {
     ///
     /// Panics if `order` is [`Acquire`] or [`AcqRel`].
     fn store(&self, val: Self::Primitive, order: Ordering);
+
+    /// Loads a value from the atomic integer.
+    ///
+    /// This method is equivalent to [`load`](Atomic::load), but [`LoadOrdering`] makes invalid orderings unrepresentable,
+    /// so it can't panic.
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::traits::{Atomic, LoadOrdering};
+    /// use core::sync::atomic::AtomicU32;
+    ///
+    /// let atomic = AtomicU32::new(5);
+    /// assert_eq!(atomic.load_typed(LoadOrdering::Acquire), 5);
+    /// ```
+    #[inline]
+    fn load_typed(&self, order: LoadOrdering) -> Self::Primitive {
+        return self.load(order.into());
+    }
+
+    /// Stores a value into the atomic integer.
+    ///
+    /// This method is equivalent to [`store`](Atomic::store), but [`StoreOrdering`] makes invalid orderings unrepresentable,
+    /// so it can't panic.
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::traits::{Atomic, LoadOrdering, StoreOrdering};
+    /// use core::sync::atomic::AtomicU32;
+    ///
+    /// let atomic = AtomicU32::new(5);
+    /// atomic.store_typed(10, StoreOrdering::Release);
+    /// assert_eq!(atomic.load_typed(LoadOrdering::Acquire), 10);
+    /// ```
+    #[inline]
+    fn store_typed(&self, val: Self::Primitive, order: StoreOrdering) {
+        self.store(val, order.into());
+    }
+
+    /// Loads a value from the atomic integer with [`Relaxed`] ordering.
+    #[inline]
+    fn load_relaxed(&self) -> Self::Primitive {
+        return self.load(Ordering::Relaxed);
+    }
+
+    /// Loads a value from the atomic integer with [`Acquire`] ordering.
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::traits::Atomic;
+    /// use core::sync::atomic::{AtomicBool, AtomicU32};
+    ///
+    /// let data = AtomicU32::new(0);
+    /// let ready = AtomicBool::new(false);
+    ///
+    /// std::thread::scope(|s| {
+    ///     s.spawn(|| {
+    ///         data.store_relaxed(42);
+    ///         ready.store_release(true);
+    ///     });
+    ///
+    ///     while !ready.load_acquire() {
+    ///         core::hint::spin_loop();
+    ///     }
+    ///     assert_eq!(data.load_relaxed(), 42);
+    /// });
+    /// ```
+    #[inline]
+    fn load_acquire(&self) -> Self::Primitive {
+        return self.load(Ordering::Acquire);
+    }
+
+    /// Stores a value into the atomic integer with [`Relaxed`] ordering.
+    #[inline]
+    fn store_relaxed(&self, val: Self::Primitive) {
+        self.store(val, Ordering::Relaxed);
+    }
+
+    /// Stores a value into the atomic integer with [`Release`] ordering.
+    #[inline]
+    fn store_release(&self, val: Self::Primitive) {
+        self.store(val, Ordering::Release);
+    }
+
+    /// Stores a value into the atomic integer with [`AcqRel`] ordering, returning the previous value.
+    #[inline]
+    fn swap_acqrel(&self, val: Self::Primitive) -> Self::Primitive {
+        return self.swap(val, Ordering::AcqRel);
+    }
     /// Stores a value into the atomic integer, returning the previous value.
     ///
     /// `swap` takes an [`Ordering`] argument which describes the memory ordering
@@ -161,6 +250,413 @@ pub unsafe trait Atomic: Send + Sync {
         fetch_ordering: Ordering,
         f: F,
     ) -> Result<Self::Primitive, Self::Primitive>;
+
+    /// Fetches the value, and applies a function to it that returns an optional new value, like [`fetch_update`](Atomic::fetch_update),
+    /// but returning the new value instead of the previous one.
+    ///
+    /// Returns `Ok(new_value)` if the function returned `Some(_)`, and `Err(previous_value)` otherwise,
+    /// mirroring the difference between GCC's `__atomic_fetch_op` and `__atomic_op_fetch` builtins.
+    /// The memory orderings and considerations of [`fetch_update`](Atomic::fetch_update) apply to this method too.
+    ///
+    /// # Errors
+    /// This method returns the previous value if `f` returned `None`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::traits::Atomic;
+    /// use core::sync::atomic::{AtomicU32, Ordering};
+    ///
+    /// let atomic = AtomicU32::new(7);
+    /// assert_eq!(Atomic::update_and_fetch(&atomic, Ordering::AcqRel, Ordering::Acquire, |x| Some(x * 2)), Ok(14));
+    /// assert_eq!(Atomic::update_and_fetch(&atomic, Ordering::AcqRel, Ordering::Acquire, |_| None), Err(14));
+    /// ```
+    #[inline]
+    fn update_and_fetch<F: FnMut(Self::Primitive) -> Option<Self::Primitive>>(
+        &self,
+        set_order: Ordering,
+        fetch_ordering: Ordering,
+        mut f: F,
+    ) -> Result<Self::Primitive, Self::Primitive>
+    where
+        Self::Primitive: Copy,
+    {
+        // `f` may be retried, so only the value computed by the successful attempt is kept
+        let mut new = None;
+        let prev = self.fetch_update(set_order, fetch_ordering, |x| {
+            new = f(x);
+            new
+        })?;
+        return Ok(new.unwrap_or(prev));
+    }
+
+    /// Fetches the value, and applies a function to it that returns an optional new value, like [`fetch_update`](Atomic::fetch_update),
+    /// but giving up after `max_tries` attempts to store the new value.
+    ///
+    /// Returns `Ok(Ok(previous_value))` if the function returned `Some(_)` and the new value was stored,
+    /// and `Ok(Err(previous_value))` if the function returned `None`. The function may be called up to `max_tries` times.
+    ///
+    /// # Errors
+    /// This method returns [`BoundExceeded`](crate::BoundExceeded) if every one of the `max_tries` attempts failed,
+    /// which lets callers fall back to another strategy when the atomic is under heavy contention.
+    fn fetch_update_bounded<F: FnMut(Self::Primitive) -> Option<Self::Primitive>>(
+        &self,
+        max_tries: usize,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<Result<Self::Primitive, Self::Primitive>, crate::BoundExceeded>
+    where
+        Self::Primitive: Copy,
+    {
+        let mut prev = self.load(fetch_order);
+        for _ in 0..max_tries {
+            let Some(next) = f(prev) else {
+                return Ok(Err(prev));
+            };
+
+            match self.compare_exchange_weak(prev, next, set_order, fetch_order) {
+                Ok(x) => return Ok(Ok(x)),
+                Err(next_prev) => prev = next_prev,
+            }
+        }
+        return Err(crate::BoundExceeded);
+    }
+
+    /// Stores a value into the atomic if the current value is the same as the `current` value,
+    /// returning `true` if the new value was written, and `false` otherwise.
+    ///
+    /// This is a convenience over [`compare_exchange`](Atomic::compare_exchange) for when the previous value isn't needed.
+    /// `order` describes the memory ordering of the read-modify-write operation, with the failure ordering
+    /// derived from it ([`Release`] becomes [`Relaxed`] and [`AcqRel`] becomes [`Acquire`]).
+    #[inline]
+    fn swap_if_eq(&self, current: Self::Primitive, new: Self::Primitive, order: Ordering) -> bool {
+        self.compare_exchange(current, new, order, failure_ordering(order))
+            .is_ok()
+    }
+
+    /// Stores a value into the atomic if the current value is the same as the `current` value, returning the previous value.
+    ///
+    /// The new value was written if the returned value is equal to `current`. `order` describes the memory ordering of this operation,
+    /// with the failure ordering derived from it ([`Release`] becomes [`Relaxed`] and [`AcqRel`] becomes [`Acquire`]).
+    ///
+    /// This method mirrors the standard library's deprecated atomic `compare_and_swap` methods, to ease porting older code
+    /// into generic code over [`Atomic`].
+    #[deprecated = "use `compare_exchange` or `compare_exchange_weak` instead"]
+    #[inline]
+    fn compare_and_swap(
+        &self,
+        current: Self::Primitive,
+        new: Self::Primitive,
+        order: Ordering,
+    ) -> Self::Primitive {
+        return match self.compare_exchange(current, new, order, failure_ordering(order)) {
+            Ok(x) | Err(x) => x,
+        };
+    }
+
+    /// Creates a new vector of atomics, initialized with the values of `v`.
+    #[docfg(feature = "alloc")]
+    #[inline]
+    fn from_slice(v: &[Self::Primitive]) -> alloc::vec::Vec<Self>
+    where
+        Self: Sized,
+        Self::Primitive: Copy,
+    {
+        return v.iter().copied().map(Self::new).collect();
+    }
+
+    /// Consumes a vector of atomics, returning the contained values without copying them.
+    ///
+    /// This is safe because passing the vector by value guarantees that no other threads are
+    /// concurrently accessing the atomic data.
+    ///
+    /// The vector's allocation is reused, so this method fails to compile on targets where the atomic is more strictly aligned
+    /// than it's primitive (like 64-bit atomics on some 32-bit targets).
+    #[docfg(feature = "alloc")]
+    #[inline]
+    fn into_primitive_vec(v: alloc::vec::Vec<Self>) -> alloc::vec::Vec<Self::Primitive>
+    where
+        Self: Sized,
+    {
+        const {
+            assert!(
+                core::mem::align_of::<Self>() == core::mem::align_of::<Self::Primitive>(),
+                "the atomic is more strictly aligned than it's primitive"
+            )
+        };
+
+        let mut v = core::mem::ManuallyDrop::new(v);
+        // SAFETY: atomics have the same layout as their primitives, and the allocation's alignment doesn't change
+        return unsafe {
+            alloc::vec::Vec::from_raw_parts(v.as_mut_ptr().cast(), v.len(), v.capacity())
+        };
+    }
+
+    /// Reinterprets a mutable reference to a primitive as a mutable reference to an atomic, without copying it.
+    ///
+    /// This is safe because the mutable reference guarantees that no other threads are
+    /// concurrently accessing the primitive, and atomics have the same layout as their primitives.
+    ///
+    /// Since the standard library's atomics have unstable inherent methods with the same name,
+    /// it's recommended to call this method with fully qualified syntax.
+    ///
+    /// # Panics
+    /// This method panics if `v` isn't aligned for `Self`. This can only happen on targets where the atomic is more strictly aligned
+    /// than it's primitive (like 64-bit atomics on some 32-bit targets). Everywhere else, the check is optimized away.
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::traits::Atomic;
+    /// use core::sync::atomic::{AtomicU32, Ordering};
+    ///
+    /// let mut value = 1u32;
+    /// let atomic = <AtomicU32 as Atomic>::from_mut(&mut value);
+    /// std::thread::scope(|s| {
+    ///     for _ in 0..4 {
+    ///         s.spawn(|| atomic.fetch_add(1, Ordering::Relaxed));
+    ///     }
+    /// });
+    /// assert_eq!(value, 5);
+    /// ```
+    #[inline]
+    fn from_mut(v: &mut Self::Primitive) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let ptr = core::ptr::from_mut(v).cast::<Self>();
+        assert!(ptr.is_aligned(), "the primitive isn't aligned for it's atomic type");
+        // SAFETY: atomics have the same layout as their primitives, and the pointer is aligned
+        return unsafe { &mut *ptr };
+    }
+
+    /// Reinterprets a mutable slice of primitives as a mutable slice of atomics, without copying it.
+    ///
+    /// This is safe because the mutable reference guarantees that no other threads are
+    /// concurrently accessing the primitives, and atomics have the same layout as their primitives.
+    ///
+    /// Since the standard library's atomics have unstable inherent methods with the same name,
+    /// it's recommended to call this method with fully qualified syntax.
+    ///
+    /// # Panics
+    /// This method panics if `v` isn't aligned for `Self`, just like [`from_mut`](Atomic::from_mut).
+    ///
+    /// # Example
+    /// ```rust
+    /// use utils_atomics::traits::Atomic;
+    /// use core::sync::atomic::{AtomicU32, Ordering};
+    ///
+    /// let mut values = [1u32, 2, 3];
+    /// let atomics = <AtomicU32 as Atomic>::from_mut_slice(&mut values);
+    /// std::thread::scope(|s| {
+    ///     for atomic in atomics.iter() {
+    ///         s.spawn(move || atomic.fetch_add(10, Ordering::Relaxed));
+    ///     }
+    /// });
+    /// assert_eq!(values, [11, 12, 13]);
+    /// ```
+    #[inline]
+    fn from_mut_slice(v: &mut [Self::Primitive]) -> &mut [Self]
+    where
+        Self: Sized,
+    {
+        let ptr = v.as_mut_ptr().cast::<Self>();
+        // Every element has the size of `Self`, so they're all aligned if the first one is
+        assert!(ptr.is_aligned(), "the primitives aren't aligned for their atomic type");
+        // SAFETY: atomics have the same layout as their primitives, and the pointer is aligned
+        return unsafe { core::slice::from_raw_parts_mut(ptr, v.len()) };
+    }
+
+    /// Reinterprets a mutable slice of atomics as a mutable slice of primitives, without copying it.
+    ///
+    /// This is safe because the mutable reference guarantees that no other threads are
+    /// concurrently accessing the atomic data.
+    #[inline]
+    fn get_mut_slice(v: &mut [Self]) -> &mut [Self::Primitive]
+    where
+        Self: Sized,
+    {
+        // SAFETY: atomics have the same layout as their primitives
+        return unsafe { core::slice::from_raw_parts_mut(v.as_mut_ptr().cast(), v.len()) };
+    }
+}
+
+/// The memory orderings that are valid for atomic loads.
+///
+/// See [`Atomic::load_typed`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LoadOrdering {
+    /// See [`Ordering::Relaxed`]
+    Relaxed,
+    /// See [`Ordering::Acquire`]
+    Acquire,
+    /// See [`Ordering::SeqCst`]
+    SeqCst,
+}
+
+/// The memory orderings that are valid for atomic stores.
+///
+/// See [`Atomic::store_typed`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StoreOrdering {
+    /// See [`Ordering::Relaxed`]
+    Relaxed,
+    /// See [`Ordering::Release`]
+    Release,
+    /// See [`Ordering::SeqCst`]
+    SeqCst,
+}
+
+impl From<LoadOrdering> for Ordering {
+    #[inline]
+    fn from(value: LoadOrdering) -> Self {
+        match value {
+            LoadOrdering::Relaxed => Ordering::Relaxed,
+            LoadOrdering::Acquire => Ordering::Acquire,
+            LoadOrdering::SeqCst => Ordering::SeqCst,
+        }
+    }
+}
+
+impl From<StoreOrdering> for Ordering {
+    #[inline]
+    fn from(value: StoreOrdering) -> Self {
+        match value {
+            StoreOrdering::Relaxed => Ordering::Relaxed,
+            StoreOrdering::Release => Ordering::Release,
+            StoreOrdering::SeqCst => Ordering::SeqCst,
+        }
+    }
+}
+
+/// The operations of [`Atomic`] that only need a shared reference to the atomic.
+///
+/// Unlike [`Atomic`], this trait is also implemented for references to it's implementors, so generic code can accept
+/// either an atomic or a reference to one (e.g. a `&AtomicU32` borrowed from a shared structure).
+/// Every method forwards to it's [`Atomic`] counterpart.
+///
+/// # Example
+/// ```rust
+/// use utils_atomics::traits::AtomicOps;
+/// use core::sync::atomic::{AtomicU32, Ordering};
+///
+/// fn bump<A: AtomicOps<Primitive = u32>>(atomic: A) -> u32 {
+///     let prev = atomic.load(Ordering::Acquire);
+///     atomic.store(prev + 1, Ordering::Release);
+///     prev
+/// }
+///
+/// let atomic = AtomicU32::new(1);
+/// assert_eq!(bump(&atomic), 1);
+/// assert_eq!(bump(&&atomic), 2);
+/// assert_eq!(atomic.into_inner(), 3);
+/// ```
+pub trait AtomicOps {
+    type Primitive;
+
+    /// See [`Atomic::load`]
+    fn load(&self, order: Ordering) -> Self::Primitive;
+
+    /// See [`Atomic::store`]
+    fn store(&self, val: Self::Primitive, order: Ordering);
+
+    /// See [`Atomic::swap`]
+    fn swap(&self, val: Self::Primitive, order: Ordering) -> Self::Primitive;
+
+    /// See [`Atomic::compare_exchange`]
+    ///
+    /// # Errors
+    /// See [`Atomic::compare_exchange`]
+    fn compare_exchange(
+        &self,
+        current: Self::Primitive,
+        new: Self::Primitive,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self::Primitive, Self::Primitive>;
+
+    /// See [`Atomic::compare_exchange_weak`]
+    ///
+    /// # Errors
+    /// See [`Atomic::compare_exchange_weak`]
+    fn compare_exchange_weak(
+        &self,
+        current: Self::Primitive,
+        new: Self::Primitive,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self::Primitive, Self::Primitive>;
+
+    /// See [`Atomic::fetch_update`]
+    ///
+    /// # Errors
+    /// See [`Atomic::fetch_update`]
+    fn fetch_update<F: FnMut(Self::Primitive) -> Option<Self::Primitive>>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        f: F,
+    ) -> Result<Self::Primitive, Self::Primitive>;
+}
+
+impl<A: ?Sized + AtomicOps> AtomicOps for &A {
+    type Primitive = A::Primitive;
+
+    #[inline]
+    fn load(&self, order: Ordering) -> Self::Primitive {
+        A::load(self, order)
+    }
+
+    #[inline]
+    fn store(&self, val: Self::Primitive, order: Ordering) {
+        A::store(self, val, order)
+    }
+
+    #[inline]
+    fn swap(&self, val: Self::Primitive, order: Ordering) -> Self::Primitive {
+        A::swap(self, val, order)
+    }
+
+    #[inline]
+    fn compare_exchange(
+        &self,
+        current: Self::Primitive,
+        new: Self::Primitive,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self::Primitive, Self::Primitive> {
+        A::compare_exchange(self, current, new, success, failure)
+    }
+
+    #[inline]
+    fn compare_exchange_weak(
+        &self,
+        current: Self::Primitive,
+        new: Self::Primitive,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self::Primitive, Self::Primitive> {
+        A::compare_exchange_weak(self, current, new, success, failure)
+    }
+
+    #[inline]
+    fn fetch_update<F: FnMut(Self::Primitive) -> Option<Self::Primitive>>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        f: F,
+    ) -> Result<Self::Primitive, Self::Primitive> {
+        A::fetch_update(self, set_order, fetch_order, f)
+    }
+}
+
+/// Returns the strongest failure ordering allowed for the given success ordering.
+#[inline]
+pub(crate) fn failure_ordering(order: Ordering) -> Ordering {
+    match order {
+        Ordering::Release | Ordering::Relaxed => Ordering::Relaxed,
+        Ordering::AcqRel | Ordering::Acquire => Ordering::Acquire,
+        _ => Ordering::SeqCst,
+    }
 }
 
 /// A trait representing atomic types that can be constructed in a "const" context.
@@ -325,8 +821,85 @@ impl<T, U> AtomicOrd<T> for U where U: Atomic + AtomicMin<T> + AtomicMax<T> {}
 impl<T> AtomicNum for T where T: AtomicNumOps + AtomicOrd {}
 impl<T> AtomicInt for T where T: AtomicNum + AtomicBitOps {}
 
+/// Sets the bits of `a` that are set in `mask`, returning the previous value.
+///
+/// This works generically over every atomic that supports bitwise operations.
+///
+/// # Example
+/// ```rust
+/// use utils_atomics::traits::set_bit;
+/// use core::sync::atomic::{AtomicU8, Ordering};
+///
+/// let atomic = AtomicU8::new(0b0001);
+/// assert_eq!(set_bit(&atomic, 0b0100, Ordering::Relaxed), 0b0001);
+/// assert_eq!(atomic.load(Ordering::Relaxed), 0b0101);
+/// ```
+#[inline]
+pub fn set_bit<A: AtomicBitOps>(a: &A, mask: A::Primitive, order: Ordering) -> A::Primitive {
+    return a.fetch_or(mask, order);
+}
+
+/// Clears the bits of `a` that are set in `mask`, returning the previous value.
+///
+/// This works generically over every atomic that supports bitwise operations.
+///
+/// # Example
+/// ```rust
+/// use utils_atomics::traits::clear_bit;
+/// use core::sync::atomic::{AtomicU8, Ordering};
+///
+/// let atomic = AtomicU8::new(0b0101);
+/// assert_eq!(clear_bit(&atomic, 0b0100, Ordering::Relaxed), 0b0101);
+/// assert_eq!(atomic.load(Ordering::Relaxed), 0b0001);
+/// ```
+#[inline]
+pub fn clear_bit<A: AtomicBitOps>(a: &A, mask: A::Primitive, order: Ordering) -> A::Primitive
+where
+    A::Primitive: Not<Output = A::Primitive>,
+{
+    return a.fetch_and(!mask, order);
+}
+
 // IMPLEMENTATION
 
+macro_rules! impl_ops {
+    (impl$(<$($gen:ident),+>)? for $atomic:ty) => {
+        impl$(<$($gen),+>)? AtomicOps for $atomic {
+            type Primitive = <Self as Atomic>::Primitive;
+
+            #[inline]
+            fn load (&self, order: Ordering) -> Self::Primitive {
+                <Self as Atomic>::load(self, order)
+            }
+
+            #[inline]
+            fn store (&self, val: Self::Primitive, order: Ordering) {
+                <Self as Atomic>::store(self, val, order)
+            }
+
+            #[inline]
+            fn swap (&self, val: Self::Primitive, order: Ordering) -> Self::Primitive {
+                <Self as Atomic>::swap(self, val, order)
+            }
+
+            #[inline]
+            fn compare_exchange (&self, current: Self::Primitive, new: Self::Primitive, success: Ordering, failure: Ordering) -> Result<Self::Primitive, Self::Primitive> {
+                <Self as Atomic>::compare_exchange(self, current, new, success, failure)
+            }
+
+            #[inline]
+            fn compare_exchange_weak (&self, current: Self::Primitive, new: Self::Primitive, success: Ordering, failure: Ordering) -> Result<Self::Primitive, Self::Primitive> {
+                <Self as Atomic>::compare_exchange_weak(self, current, new, success, failure)
+            }
+
+            #[inline]
+            fn fetch_update<F: FnMut(Self::Primitive) -> Option<Self::Primitive>> (&self, set_order: Ordering, fetch_order: Ordering, f: F) -> Result<Self::Primitive, Self::Primitive> {
+                <Self as Atomic>::fetch_update(self, set_order, fetch_order, f)
+            }
+        }
+    };
+}
+
 macro_rules! impl_atomic {
     ($($len:literal: $prim:ty => $atomic:ty),+) => {
         $(
@@ -385,6 +958,9 @@ macro_rules! impl_atomic {
                 }
             }
 
+            #[docfg(target_has_atomic = $len)]
+            impl_ops!(impl for $atomic);
+
             cfg_if::cfg_if! {
                 if #[cfg(feature = "const")] {
                     #[cfg_attr(docsrs, doc(cfg(feature = "const")))]
@@ -482,7 +1058,12 @@ impl_int! {
     "32": (u32, i32) => (core::sync::atomic::AtomicU32, core::sync::atomic::AtomicI32),
     "64": (u64, i64) => (core::sync::atomic::AtomicU64, core::sync::atomic::AtomicI64),
     "ptr": (usize, isize) => (core::sync::atomic::AtomicUsize, core::sync::atomic::AtomicIsize)
-    //"128": (u128, i128) => (core::sync::atomic::AtomicU128, core::sync::atomic::AtomicI128)
+}
+
+// 128-bit atomics are still unstable, and only available on some targets
+#[cfg(all(feature = "nightly", target_has_atomic = "128"))]
+impl_int! {
+    "128": (u128, i128) => (core::sync::atomic::AtomicU128, core::sync::atomic::AtomicI128)
 }
 
 impl_atomic! {
@@ -560,3 +1141,245 @@ unsafe impl<T> Atomic for core::sync::atomic::AtomicPtr<T> {
         core::sync::atomic::AtomicPtr::fetch_update(self, set_order, fetch_ordering, f)
     }
 }
+
+#[docfg(target_has_atomic = "ptr")]
+impl_ops!(impl<T> for core::sync::atomic::AtomicPtr<T>);
+
+#[cfg(test)]
+mod tests {
+    use super::{Atomic, LoadOrdering, StoreOrdering};
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn from_mut_slice() {
+        let mut values = [1u32, 2, 3, 4];
+        let atomics = <AtomicU32 as Atomic>::from_mut_slice(&mut values);
+        atomics[0].store(10, Ordering::Relaxed);
+        for atomic in atomics.iter() {
+            atomic.fetch_add(1, Ordering::Relaxed);
+        }
+
+        assert_eq!(<AtomicU32 as Atomic>::get_mut_slice(atomics), [11, 3, 4, 5]);
+        assert_eq!(values, [11, 3, 4, 5]);
+    }
+
+    #[test]
+    fn update_and_fetch() {
+        let atomic = AtomicU32::new(3);
+        assert_eq!(Atomic::update_and_fetch(&atomic, Ordering::AcqRel, Ordering::Acquire, |x| Some(x + 1)), Ok(4));
+        assert_eq!(atomic.load(Ordering::Relaxed), 4);
+        assert_eq!(Atomic::update_and_fetch(&atomic, Ordering::AcqRel, Ordering::Acquire, |x| (x < 4).then_some(0)), Err(4));
+
+        // Under contention, the returned value is the one computed by the attempt that was stored
+        #[cfg(feature = "std")]
+        {
+            let atomic = core::sync::atomic::AtomicI64::new(0);
+            let mut returned = std::thread::scope(|s| {
+                let handles = (0..4)
+                    .map(|_| {
+                        s.spawn(|| {
+                            (0..1000)
+                                .map(|_| Atomic::update_and_fetch(&atomic, Ordering::AcqRel, Ordering::Acquire, |x| Some(x + 1)).unwrap())
+                                .collect::<std::vec::Vec<_>>()
+                        })
+                    })
+                    .collect::<std::vec::Vec<_>>();
+
+                handles.into_iter().flat_map(|x| x.join().unwrap()).collect::<std::vec::Vec<_>>()
+            });
+
+            // Every increment returned the value it stored, so each one is seen exactly once
+            returned.sort_unstable();
+            assert_eq!(returned, (1..=4000).collect::<std::vec::Vec<_>>());
+            assert_eq!(atomic.load(Ordering::Relaxed), 4000);
+        }
+    }
+
+    #[test]
+    fn ops_through_reference() {
+        use super::AtomicOps;
+
+        fn exchange<A: AtomicOps<Primitive = u32>>(atomic: A) -> (u32, u32) {
+            atomic.store(5, Ordering::Relaxed);
+            let prev = atomic.swap(6, Ordering::AcqRel);
+            let cas = atomic.compare_exchange_weak(6, 7, Ordering::AcqRel, Ordering::Acquire);
+            let cas = cas.or_else(|_| atomic.compare_exchange(6, 7, Ordering::AcqRel, Ordering::Acquire));
+            assert_eq!(cas.unwrap_or(7), 6);
+            assert_eq!(atomic.fetch_update(Ordering::AcqRel, Ordering::Acquire, |x| Some(x * 2)), Ok(7));
+            (prev, atomic.load(Ordering::Acquire))
+        }
+
+        let atomic = AtomicU32::new(0);
+        assert_eq!(exchange(&atomic), (5, 14));
+        assert_eq!(exchange(atomic), (5, 14));
+    }
+
+    #[test]
+    fn bit_helpers() {
+        use super::{clear_bit, set_bit};
+        use core::sync::atomic::AtomicU8;
+
+        let atomic = AtomicU32::new(0);
+        assert_eq!(set_bit(&atomic, 1 << 31, Ordering::Relaxed), 0);
+        assert_eq!(set_bit(&atomic, 0b11, Ordering::Relaxed), 1 << 31);
+        assert_eq!(clear_bit(&atomic, 0b10, Ordering::Relaxed), (1 << 31) | 0b11);
+        assert_eq!(atomic.load(Ordering::Relaxed), (1 << 31) | 0b01);
+
+        let atomic = AtomicU8::new(u8::MAX);
+        assert_eq!(clear_bit(&atomic, 0xF0, Ordering::Relaxed), u8::MAX);
+        assert_eq!(clear_bit(&atomic, 0xF0, Ordering::Relaxed), 0x0F);
+        assert_eq!(set_bit(&atomic, 0x80, Ordering::Relaxed), 0x0F);
+        assert_eq!(atomic.load(Ordering::Relaxed), 0x8F);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn ordering_shorthands() {
+        use core::sync::atomic::AtomicBool;
+
+        const ROUNDS: u32 = 1000;
+
+        let atomic = AtomicU32::new(1);
+        atomic.store_relaxed(2);
+        assert_eq!(atomic.load_relaxed(), 2);
+        atomic.store_release(3);
+        assert_eq!(atomic.load_acquire(), 3);
+        assert_eq!(atomic.swap_acqrel(4), 3);
+        assert_eq!(atomic.load_acquire(), 4);
+
+        // Message passing: the relaxed write to `data` must be visible once the released flag is acquired
+        let data = AtomicU32::new(0);
+        let ready = AtomicBool::new(false);
+        let ack = AtomicBool::new(false);
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                for i in 1..=ROUNDS {
+                    data.store_relaxed(i);
+                    ready.store_release(true);
+                    while !ack.swap_acqrel(false) {
+                        core::hint::spin_loop();
+                    }
+                }
+            });
+
+            for i in 1..=ROUNDS {
+                while !ready.swap_acqrel(false) {
+                    core::hint::spin_loop();
+                }
+                assert_eq!(data.load_relaxed(), i);
+                ack.store_release(true);
+            }
+        });
+    }
+
+    #[test]
+    fn typed_orderings() {
+        let atomic = AtomicU32::new(1);
+        for (load, store) in [
+            (LoadOrdering::Relaxed, StoreOrdering::Relaxed),
+            (LoadOrdering::Acquire, StoreOrdering::Release),
+            (LoadOrdering::SeqCst, StoreOrdering::SeqCst),
+        ] {
+            let v = atomic.load_typed(load);
+            atomic.store_typed(v * 2, store);
+        }
+        assert_eq!(atomic.load_typed(LoadOrdering::SeqCst), 8);
+        assert_eq!(Ordering::from(LoadOrdering::Acquire), Ordering::Acquire);
+        assert_eq!(Ordering::from(StoreOrdering::Release), Ordering::Release);
+    }
+
+    #[test]
+    fn from_mut() {
+        use core::sync::atomic::AtomicI8;
+
+        let mut value = 1u32;
+        let atomic = <AtomicU32 as Atomic>::from_mut(&mut value);
+        assert_eq!(atomic.fetch_add(2, Ordering::Relaxed), 1);
+        atomic.store(atomic.load(Ordering::Relaxed) * 10, Ordering::Relaxed);
+        assert_eq!(value, 30);
+
+        let mut value = -1i8;
+        assert_eq!(<AtomicI8 as Atomic>::from_mut(&mut value).swap(5, Ordering::Relaxed), -1);
+        assert_eq!(value, 5);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn from_slice() {
+        let atomics = AtomicU32::from_slice(&[1, 2, 3]);
+        atomics[1].fetch_add(10, Ordering::Relaxed);
+        assert_eq!(AtomicU32::into_primitive_vec(atomics), [1, 12, 3]);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn compare_and_swap() {
+        let atomic = AtomicU32::new(1);
+        assert_eq!(Atomic::compare_and_swap(&atomic, 1, 2, Ordering::AcqRel), 1);
+        assert_eq!(Atomic::compare_and_swap(&atomic, 1, 3, Ordering::Release), 2);
+        assert_eq!(atomic.into_inner(), 2);
+    }
+
+    #[test]
+    fn swap_if_eq() {
+        let atomic = AtomicU32::new(1);
+        assert!(Atomic::swap_if_eq(&atomic, 1, 2, Ordering::AcqRel));
+        assert!(!Atomic::swap_if_eq(&atomic, 1, 3, Ordering::Release));
+        assert_eq!(atomic.into_inner(), 2);
+    }
+
+    #[test]
+    fn fetch_update_bounded() {
+        let atomic = AtomicU32::new(1);
+        assert_eq!(
+            Atomic::fetch_update_bounded(
+                &atomic,
+                3,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+                |x| Some(x * 2)
+            ),
+            Ok(Ok(1))
+        );
+        assert_eq!(
+            Atomic::fetch_update_bounded(&atomic, 3, Ordering::AcqRel, Ordering::Acquire, |_| None),
+            Ok(Err(2))
+        );
+        assert_eq!(
+            Atomic::fetch_update_bounded(
+                &atomic,
+                0,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+                Some
+            ),
+            Err(crate::BoundExceeded)
+        );
+        assert_eq!(atomic.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn fetch_update_bounded_under_contention() {
+        const MAX_TRIES: u32 = 5;
+
+        let atomic = AtomicU32::new(0);
+        let mut tries = 0;
+
+        // Every attempt races against a store that changes the value before it can be swapped
+        let result = Atomic::fetch_update_bounded(
+            &atomic,
+            MAX_TRIES as usize,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+            |x| {
+                tries += 1;
+                atomic.fetch_add(1, Ordering::AcqRel);
+                Some(x + 100)
+            },
+        );
+
+        assert_eq!(result, Err(crate::BoundExceeded));
+        assert_eq!(tries, MAX_TRIES);
+        assert_eq!(atomic.into_inner(), MAX_TRIES);
+    }
+}
@@ -0,0 +1,420 @@
+use crossbeam::epoch::{self, Guard};
+use std::{
+    cell::UnsafeCell,
+    fmt::Debug,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    sync::{
+        atomic::{fence, AtomicIsize, AtomicPtr, Ordering},
+        Arc,
+    },
+};
+
+const MIN_CAPACITY: usize = 32;
+
+struct Buffer<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+}
+
+impl<T> Buffer<T> {
+    #[inline]
+    fn alloc(capacity: usize) -> *mut Self {
+        debug_assert!(capacity.is_power_of_two());
+        let slots = (0..capacity)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect();
+        return Box::into_raw(Box::new(Self { slots }));
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    #[inline]
+    #[allow(clippy::cast_sign_loss)]
+    fn slot(&self, idx: isize) -> *mut MaybeUninit<T> {
+        // The capacity is a power of two, so masking is equivalent to an euclidean remainder
+        self.slots[(idx as usize) & (self.capacity() - 1)].get()
+    }
+
+    /// # Safety
+    /// Only the owner may write to the buffer, and only to slots outside the `top..bottom` range.
+    #[inline]
+    unsafe fn write(&self, idx: isize, v: T) {
+        self.slot(idx).write(MaybeUninit::new(v))
+    }
+
+    /// # Safety
+    /// The returned value is a bitwise copy, which may only be assumed initialized once the caller has claimed it's index.
+    #[inline]
+    unsafe fn read(&self, idx: isize) -> MaybeUninit<T> {
+        // Thieves may race with the owner overwritting a slot they'll fail to claim, so the read must be volatile
+        self.slot(idx).read_volatile()
+    }
+}
+
+struct Inner<T> {
+    top: AtomicIsize,
+    bottom: AtomicIsize,
+    buffer: AtomicPtr<Buffer<T>>,
+}
+
+impl<T> Drop for Inner<T> {
+    fn drop(&mut self) {
+        let top = *self.top.get_mut();
+        let bottom = *self.bottom.get_mut();
+        unsafe {
+            let buffer = Box::from_raw(*self.buffer.get_mut());
+            for i in top..bottom {
+                buffer.read(i).assume_init_drop();
+            }
+        }
+    }
+}
+
+/// A Chase-Lev work-stealing deque.
+///
+/// The owner of the queue pushes and pops elements from one end in LIFO (Last In First Out) order,
+/// whilst any number of [`Stealer`]s take elements from the other end in FIFO (First In First Out) order.
+/// This makes it a good fit for schedulers, where workers process their own recently produced tasks and idle workers
+/// steal the oldest tasks of others.
+///
+/// The queue grows as needed, and old buffers are reclaimed once no stealer can observe them.
+///
+/// # Example
+/// ```rust
+/// use utils_atomics::WorkStealingQueue;
+///
+/// let queue = WorkStealingQueue::new();
+/// let stealer = queue.stealer();
+///
+/// queue.push(1);
+/// queue.push(2);
+/// queue.push(3);
+///
+/// std::thread::scope(|s| {
+///     s.spawn(move || assert_eq!(stealer.steal(), Some(1)));
+/// });
+///
+/// assert_eq!(queue.pop(), Some(3));
+/// assert_eq!(queue.pop(), Some(2));
+/// assert_eq!(queue.pop(), None);
+/// ```
+pub struct WorkStealingQueue<T> {
+    inner: Arc<Inner<T>>,
+    // Only a single thread may own the queue
+    _phtm: PhantomData<*mut ()>,
+}
+
+/// A handle used to steal elements from a [`WorkStealingQueue`]
+pub struct Stealer<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> WorkStealingQueue<T> {
+    /// Creates a new empty queue
+    #[inline]
+    pub fn new() -> Self {
+        return Self::with_capacity(MIN_CAPACITY);
+    }
+
+    /// Creates a new empty queue, with space for at least `capacity` elements before it needs to grow.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(MIN_CAPACITY).next_power_of_two();
+        return Self {
+            inner: Arc::new(Inner {
+                top: AtomicIsize::new(0),
+                bottom: AtomicIsize::new(0),
+                buffer: AtomicPtr::new(Buffer::alloc(capacity)),
+            }),
+            _phtm: PhantomData,
+        };
+    }
+
+    /// Creates a new [`Stealer`] for this queue
+    #[inline]
+    pub fn stealer(&self) -> Stealer<T> {
+        return Stealer {
+            inner: self.inner.clone(),
+        };
+    }
+
+    /// Returns the number of elements in the queue.
+    /// # Safety
+    /// Whilst this method is not unsafe, it's result should be considered immediately stale.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the queue is currently empty, `false` otherwise.
+    /// # Safety
+    /// Whilst this method is not unsafe, it's result should be considered immediately stale.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Pushes an element into the owner's end of the queue.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn push(&self, v: T) {
+        let b = self.inner.bottom.load(Ordering::Relaxed);
+        let t = self.inner.top.load(Ordering::Acquire);
+        let mut buffer = self.inner.buffer.load(Ordering::Relaxed);
+
+        unsafe {
+            if b - t >= (*buffer).capacity() as isize {
+                buffer = self.grow(buffer, t, b, &epoch::pin());
+            }
+
+            (*buffer).write(b, v);
+        }
+
+        fence(Ordering::Release);
+        self.inner.bottom.store(b + 1, Ordering::Relaxed);
+    }
+
+    /// Pops the most recently pushed element from the owner's end of the queue.
+    pub fn pop(&self) -> Option<T> {
+        let b = self.inner.bottom.load(Ordering::Relaxed) - 1;
+        let buffer = self.inner.buffer.load(Ordering::Relaxed);
+        self.inner.bottom.store(b, Ordering::Relaxed);
+
+        fence(Ordering::SeqCst);
+        let t = self.inner.top.load(Ordering::Relaxed);
+
+        if t > b {
+            // The queue was empty
+            self.inner.bottom.store(b + 1, Ordering::Relaxed);
+            return None;
+        }
+
+        if t == b {
+            // Last element, which stealers may be competing for
+            let claimed = self
+                .inner
+                .top
+                .compare_exchange(t, t + 1, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok();
+
+            self.inner.bottom.store(b + 1, Ordering::Relaxed);
+            if !claimed {
+                return None;
+            }
+        }
+
+        return unsafe { Some((*buffer).read(b).assume_init()) };
+    }
+
+    /// Replaces the buffer with one of twice it's capacity, moving the elements in `top..bottom` to it.
+    unsafe fn grow(&self, old: *mut Buffer<T>, top: isize, bottom: isize, guard: &Guard) -> *mut Buffer<T> {
+        let new = Buffer::alloc(2 * (*old).capacity());
+        for i in top..bottom {
+            core::ptr::copy_nonoverlapping((*old).slot(i), (*new).slot(i), 1);
+        }
+
+        self.inner.buffer.store(new, Ordering::Release);
+        // Stealers may still be reading from the old buffer. It's elements have been moved, so only it's memory is released.
+        let old = old.cast_const();
+        guard.defer_unchecked(move || drop(Box::from_raw(old.cast_mut())));
+        guard.flush();
+        return new;
+    }
+}
+
+impl<T> Stealer<T> {
+    /// Steals the oldest element of the queue, returning `None` if the queue is empty.
+    pub fn steal(&self) -> Option<T> {
+        let guard = epoch::pin();
+        loop {
+            let t = self.inner.top.load(Ordering::Acquire);
+            fence(Ordering::SeqCst);
+            let b = self.inner.bottom.load(Ordering::Acquire);
+
+            if t >= b {
+                return None;
+            }
+
+            let buffer = self.inner.buffer.load(Ordering::Acquire);
+            let v = unsafe { (*buffer).read(t) };
+
+            if self
+                .inner
+                .top
+                .compare_exchange(t, t + 1, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok()
+            {
+                drop(guard);
+                return unsafe { Some(v.assume_init()) };
+            }
+
+            // Another thread claimed the element first, so our copy must be discarded without dropping it
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Returns the number of elements in the queue.
+    /// # Safety
+    /// Whilst this method is not unsafe, it's result should be considered immediately stale.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the queue is currently empty, `false` otherwise.
+    /// # Safety
+    /// Whilst this method is not unsafe, it's result should be considered immediately stale.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Inner<T> {
+    #[inline]
+    #[allow(clippy::cast_sign_loss)]
+    fn len(&self) -> usize {
+        let t = self.top.load(Ordering::Acquire);
+        let b = self.bottom.load(Ordering::Acquire);
+        return (b - t).max(0) as usize;
+    }
+}
+
+impl<T> Default for WorkStealingQueue<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for Stealer<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        return Self {
+            inner: self.inner.clone(),
+        };
+    }
+}
+
+impl<T> Debug for WorkStealingQueue<T> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkStealingQueue")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Debug for Stealer<T> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Stealer")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+unsafe impl<T: Send> Send for WorkStealingQueue<T> {}
+unsafe impl<T: Send> Send for Stealer<T> {}
+unsafe impl<T: Send> Sync for Stealer<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::WorkStealingQueue;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    #[test]
+    fn test_basic_functionality() {
+        let queue = WorkStealingQueue::new();
+        let stealer = queue.stealer();
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop(), None);
+        assert_eq!(stealer.steal(), None);
+
+        // Enough elements to force the buffer to grow
+        for i in 0..100 {
+            queue.push(i);
+        }
+        assert_eq!(stealer.len(), 100);
+
+        assert_eq!(stealer.steal(), Some(0));
+        assert_eq!(stealer.steal(), Some(1));
+        assert_eq!(queue.pop(), Some(99));
+        assert_eq!(queue.pop(), Some(98));
+        assert_eq!(queue.len(), 96);
+    }
+
+    #[test]
+    fn test_drops_remaining() {
+        struct DropCounter<'a>(&'a AtomicUsize);
+        impl Drop for DropCounter<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let drops = AtomicUsize::new(0);
+        let queue = WorkStealingQueue::new();
+        let stealer = queue.stealer();
+        for _ in 0..50 {
+            queue.push(DropCounter(&drops));
+        }
+
+        drop(stealer.steal());
+        drop(queue.pop());
+        assert_eq!(drops.load(Ordering::Relaxed), 2);
+
+        drop(queue);
+        assert_eq!(drops.load(Ordering::Relaxed), 2);
+        drop(stealer);
+        assert_eq!(drops.load(Ordering::Relaxed), 50);
+    }
+
+    #[test]
+    fn test_owner_and_stealers() {
+        const STEALERS: usize = 4;
+        const ITEMS: usize = if cfg!(miri) { 200 } else { 100_000 };
+
+        let queue = WorkStealingQueue::new();
+        let seen = (0..ITEMS).map(|_| AtomicBool::new(false)).collect::<Vec<_>>();
+        let done = AtomicBool::new(false);
+        let count = AtomicUsize::new(0);
+
+        let mark = |i: usize| {
+            assert!(!seen[i].swap(true, Ordering::Relaxed), "item {i} was duplicated");
+            count.fetch_add(1, Ordering::Relaxed);
+        };
+
+        std::thread::scope(|s| {
+            for _ in 0..STEALERS {
+                let stealer = queue.stealer();
+                let (mark, done) = (&mark, &done);
+                s.spawn(move || loop {
+                    match stealer.steal() {
+                        Some(i) => mark(i),
+                        None if done.load(Ordering::Acquire) => break,
+                        None => core::hint::spin_loop(),
+                    }
+                });
+            }
+
+            for i in 0..ITEMS {
+                queue.push(i);
+                if i % 3 == 0 {
+                    if let Some(i) = queue.pop() {
+                        mark(i);
+                    }
+                }
+            }
+            while let Some(i) = queue.pop() {
+                mark(i);
+            }
+            done.store(true, Ordering::Release);
+        });
+
+        assert_eq!(count.into_inner(), ITEMS);
+        assert!(seen.iter().all(|x| x.load(Ordering::Relaxed)));
+    }
+}
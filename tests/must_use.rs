@@ -0,0 +1,7 @@
+// The snapshot names the types' generic parameters, which include the allocator with `alloc_api`
+#[cfg(all(feature = "alloc", not(feature = "alloc_api")))]
+#[test]
+fn must_use() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/must_use.rs");
+}
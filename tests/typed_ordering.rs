@@ -0,0 +1,7 @@
+// The snapshot follows the stable compiler's diagnostics, which nightly words differently
+#[cfg(not(feature = "nightly"))]
+#[test]
+fn typed_ordering() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/load_typed_release.rs");
+}
@@ -0,0 +1,8 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+use utils_atomics::traits::{Atomic, LoadOrdering};
+
+fn main() {
+    let atomic = AtomicU32::new(1);
+    atomic.load_typed(Ordering::Release);
+    atomic.load_typed(LoadOrdering::Release);
+}
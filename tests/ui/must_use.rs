@@ -0,0 +1,15 @@
+#![deny(unused_must_use)]
+
+use utils_atomics::{AtomicCell, FillQueue};
+
+fn main() {
+    let cell = AtomicCell::new(Some(1));
+    cell.replace(2);
+    cell.take();
+    cell.take_boxed();
+
+    let mut queue = FillQueue::new();
+    queue.push(1);
+    queue.chop();
+    queue.chop_mut();
+}